@@ -262,12 +262,17 @@ fn main() {
             .allowlist_function("jl_gc_add_ptr_finalizer")
             .allowlist_function("jl_gc_alloc_typed")
             .allowlist_function("jl_gc_collect")
+            .allowlist_function("jl_gc_counted_free_with_size")
+            .allowlist_function("jl_gc_counted_malloc")
             .allowlist_function("jl_gc_enable")
             .allowlist_function("jl_gc_is_enabled")
             .allowlist_function("jl_gc_mark_queue_obj")
             .allowlist_function("jl_gc_mark_queue_objarray")
             .allowlist_function("jl_gc_queue_root")
             .allowlist_function("jl_gc_safepoint")
+            .allowlist_function("jl_gc_set_cb_post_gc")
+            .allowlist_function("jl_gc_set_cb_pre_gc")
+            .allowlist_function("jl_gc_set_max_memory")
             .allowlist_function("jl_gc_schedule_foreign_sweepfunc")
             .allowlist_function("jl_get_current_task")
             .allowlist_function("jl_get_global")
@@ -278,6 +283,7 @@ fn main() {
             .allowlist_function("jl_get_ptls_states")
             .allowlist_function("jl_get_ARCH")
             .allowlist_function("jl_get_UNAME")
+            .allowlist_function("jl_get_world_counter")
             .allowlist_function("jl_getallocationgranularity")
             .allowlist_function("jl_getpagesize")
             .allowlist_function("jl_git_branch")
@@ -338,6 +344,8 @@ fn main() {
             .allowlist_function("jlrs_lock")
             .allowlist_function("jlrs_unlock")
             .allowlist_function("jlrs_array_data_owner_offset")
+            .allowlist_function("jlrs_gc_count_allocd")
+            .allowlist_function("jlrs_gc_count_freed")
             .allowlist_type("jl_callptr_t")
             .allowlist_type("jl_code_instance_t")
             .allowlist_type("jl_datatype_t")
@@ -1292,6 +1292,12 @@ pub type jl_gc_collection_t = ::std::os::raw::c_uint;
 extern "C" {
     pub fn jl_gc_collect(arg1: jl_gc_collection_t);
 }
+extern "C" {
+    pub fn jl_gc_counted_malloc(sz: usize) -> *mut ::std::os::raw::c_void;
+}
+extern "C" {
+    pub fn jl_gc_counted_free_with_size(p: *mut ::std::os::raw::c_void, sz: usize);
+}
 extern "C" {
     pub fn jl_gc_add_finalizer(v: *mut jl_value_t, f: *mut jl_function_t);
 }
@@ -1824,6 +1830,19 @@ extern "C" {
 extern "C" {
     pub fn jl_get_current_task() -> *mut jl_value_t;
 }
+extern "C" {
+    pub fn jl_get_world_counter() -> usize;
+}
+pub type jl_gc_cb_pre_gc_t =
+    ::std::option::Option<unsafe extern "C" fn(full: ::std::os::raw::c_int)>;
+pub type jl_gc_cb_post_gc_t =
+    ::std::option::Option<unsafe extern "C" fn(full: ::std::os::raw::c_int)>;
+extern "C" {
+    pub fn jl_gc_set_cb_pre_gc(cb: jl_gc_cb_pre_gc_t, enable: ::std::os::raw::c_int);
+}
+extern "C" {
+    pub fn jl_gc_set_cb_post_gc(cb: jl_gc_cb_post_gc_t, enable: ::std::os::raw::c_int);
+}
 pub type jl_markfunc_t =
     ::std::option::Option<unsafe extern "C" fn(arg1: jl_ptls_t, obj: *mut jl_value_t) -> usize>;
 pub type jl_sweepfunc_t = ::std::option::Option<unsafe extern "C" fn(obj: *mut jl_value_t)>;
@@ -1888,6 +1907,12 @@ extern "C" {
 extern "C" {
     pub fn jlrs_array_data_owner_offset(n_dims: u16) -> uint_t;
 }
+extern "C" {
+    pub fn jlrs_gc_count_allocd(sz: usize);
+}
+extern "C" {
+    pub fn jlrs_gc_count_freed(sz: usize);
+}
 extern "C" {
     pub fn jlrs_lock(v: *mut jl_value_t);
 }
@@ -27,6 +27,28 @@ extern "C"
         return jl_array_data_owner_offset(n_dims);
     }
 
+    void jlrs_gc_count_allocd(size_t sz)
+    {
+#if defined(JLRS_LTS)
+        jl_ptls_t ptls = jl_get_ptls_states();
+        ptls->gc_num.allocd += sz;
+#else
+        jl_ptls_t ptls = jl_current_task->ptls;
+        jl_atomic_store_relaxed(&ptls->gc_num.allocd, jl_atomic_load_relaxed(&ptls->gc_num.allocd) + sz);
+#endif
+    }
+
+    void jlrs_gc_count_freed(size_t sz)
+    {
+#if defined(JLRS_LTS)
+        jl_ptls_t ptls = jl_get_ptls_states();
+        ptls->gc_num.freed += sz;
+#else
+        jl_ptls_t ptls = jl_current_task->ptls;
+        jl_atomic_store_relaxed(&ptls->gc_num.freed, jl_atomic_load_relaxed(&ptls->gc_num.freed) + sz);
+#endif
+    }
+
 #if !defined(JLRS_LTS)
     void jlrs_lock(jl_value_t *v)
     {
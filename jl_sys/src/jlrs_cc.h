@@ -37,6 +37,8 @@ extern "C"
 #endif
 
     uint_t jlrs_array_data_owner_offset(uint16_t n_dims);
+    void jlrs_gc_count_allocd(size_t sz);
+    void jlrs_gc_count_freed(size_t sz);
 
 #if !defined(JLRS_WINDOWS_LTS)
     void jlrs_lock(jl_value_t *v);
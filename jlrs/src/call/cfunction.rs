@@ -0,0 +1,211 @@
+//! Call Rust functions from Julia through a function pointer.
+//!
+//! A [`RustFunction`] can wrap any closure, but every call has to box its arguments and go
+//! through dynamic dispatch. When a function is called many times, e.g. as the right-hand side
+//! of an ODE, it's more efficient to call an `extern "C"` function directly with `ccall`.
+//!
+//! The [`CFunction`] trait is implemented for `extern "C"` function pointers whose argument and
+//! return types implement both [`ConstructType`] and [`ValidField`], functions that return
+//! nothing are also supported. The signature of the function in Julia is derived from these
+//! types, e.g. `extern "C" fn(f64, i32) -> f64` is called as
+//! `ccall(ptr, Float64, (Float64, Int32), arg1, arg2)`. A pointer to the function can be
+//! converted to a `Ptr{Cvoid}` with [`CFunction::julia_pointer`], and
+//! [`CFunction::julia_function`] creates a Julia function with typed arguments that `ccall`s it.
+//!
+//! Example:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! # use jlrs::call::cfunction::CFunction;
+//! extern "C" fn add(a: f64, b: f64) -> f64 {
+//!     a + b
+//! }
+//!
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         let func = (add as extern "C" fn(f64, f64) -> f64)
+//!             .julia_function(frame.as_extended_target())?;
+//!
+//!         let a = Value::new(&mut frame, 1.0f64);
+//!         let b = Value::new(&mut frame, 2.0f64);
+//!         let res = unsafe { func.call2(&mut frame, a, b) }
+//!             .into_jlrs_result()?
+//!             .unbox::<f64>()?;
+//!         assert_eq!(res, 3.0);
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+//!
+//! The wrapper function is defined with `eval`, so it can only be called from Julia code that
+//! runs in a newer world age, e.g. by using `Base.invokelatest`.
+//!
+//! [`RustFunction`]: crate::call::rust_function::RustFunction
+//! [`ConstructType`]: crate::convert::construct_type::ConstructType
+//! [`ValidField`]: crate::layout::valid_layout::ValidField
+
+use std::ffi::c_void;
+
+use smallvec::SmallVec;
+
+use crate::{
+    call::Call,
+    convert::{construct_type::ConstructType, into_jlrs_result::IntoJlrsResult},
+    error::JlrsResult,
+    layout::valid_layout::ValidField,
+    memory::target::{frame::GcFrame, ExtendedTarget, Target},
+    wrappers::{
+        inline::nothing::Nothing,
+        ptr::{
+            module::Module,
+            value::{Value, ValueData, MAX_SIZE},
+            Wrapper,
+        },
+    },
+};
+
+/// A type that can be returned by a [`CFunction`].
+///
+/// This trait is implemented for `()` and all types that implement both [`ConstructType`] and
+/// [`ValidField`].
+///
+/// Safety: the constructed type must be a valid return type for `ccall`.
+pub unsafe trait CFunctionReturn {
+    /// Construct the return type used by `ccall`.
+    fn return_type<'target, 'current, 'borrow, T>(
+        target: ExtendedTarget<'target, 'current, 'borrow, T>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>;
+}
+
+unsafe impl CFunctionReturn for () {
+    fn return_type<'target, 'current, 'borrow, T>(
+        target: ExtendedTarget<'target, 'current, 'borrow, T>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        Nothing::construct_type(target)
+    }
+}
+
+unsafe impl<R: ConstructType + ValidField> CFunctionReturn for R {
+    fn return_type<'target, 'current, 'borrow, T>(
+        target: ExtendedTarget<'target, 'current, 'borrow, T>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        R::construct_type(target)
+    }
+}
+
+/// An `extern "C"` function that can be called from Julia with `ccall`.
+///
+/// Safety: the function pointer must be callable with the arguments and return types
+/// constructed by [`CFunction::signature`].
+pub unsafe trait CFunction: Copy + Send + Sync + 'static {
+    /// Returns the function pointer.
+    fn as_ptr(self) -> *mut c_void;
+
+    /// Construct the return type and argument types of this function. The types are rooted in
+    /// `frame`.
+    fn signature<'target>(
+        frame: &mut GcFrame<'target>,
+    ) -> (
+        Value<'target, 'static>,
+        SmallVec<[Value<'target, 'static>; MAX_SIZE]>,
+    );
+
+    /// Convert the function pointer to a `Ptr{Cvoid}`.
+    fn julia_pointer<'target, T>(self, target: T) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        Value::new(target, self.as_ptr())
+    }
+
+    /// Create a Julia function with typed arguments that calls this function with `ccall`.
+    fn julia_function<'target, T>(
+        self,
+        target: ExtendedTarget<'target, '_, '_, T>,
+    ) -> JlrsResult<ValueData<'target, 'static, T>>
+    where
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let (ret_ty, arg_tys) = Self::signature(&mut frame);
+            let ptr = self.julia_pointer(&mut frame);
+
+            let mut args = SmallVec::<[Value; MAX_SIZE]>::with_capacity(arg_tys.len() + 2);
+            args.push(ptr);
+            args.push(ret_ty);
+            args.extend_from_slice(&arg_tys);
+
+            // Safety: cfunctionwrapper only defines a new function.
+            unsafe {
+                let func = Module::main(&frame)
+                    .submodule(&frame, "Jlrs")?
+                    .wrapper()
+                    .function(&frame, "cfunctionwrapper")?
+                    .wrapper()
+                    .call(&mut frame, args)
+                    .into_jlrs_result()?;
+
+                Ok(func.root(output))
+            }
+        })
+    }
+}
+
+macro_rules! impl_cfunction {
+    (@impl [$($fn_ty:tt)+] $($name:ident),*) => {
+        unsafe impl<R, $($name),*> CFunction for $($fn_ty)+($($name),*) -> R
+        where
+            R: CFunctionReturn + 'static,
+            $($name: ConstructType + ValidField + 'static),*
+        {
+            fn as_ptr(self) -> *mut c_void {
+                self as *mut c_void
+            }
+
+            fn signature<'target>(
+                frame: &mut GcFrame<'target>,
+            ) -> (
+                Value<'target, 'static>,
+                SmallVec<[Value<'target, 'static>; MAX_SIZE]>,
+            ) {
+                let ret_ty = R::return_type(frame.as_extended_target());
+                #[allow(unused_mut)]
+                let mut arg_tys = SmallVec::new();
+                $(arg_tys.push($name::construct_type(frame.as_extended_target()));)*
+                (ret_ty, arg_tys)
+            }
+        }
+    };
+    ($($name:ident),*) => {
+        impl_cfunction!(@impl [extern "C" fn] $($name),*);
+        impl_cfunction!(@impl [unsafe extern "C" fn] $($name),*);
+    };
+}
+
+impl_cfunction!();
+impl_cfunction!(A1);
+impl_cfunction!(A1, A2);
+impl_cfunction!(A1, A2, A3);
+impl_cfunction!(A1, A2, A3, A4);
+impl_cfunction!(A1, A2, A3, A4, A5);
+impl_cfunction!(A1, A2, A3, A4, A5, A6);
+impl_cfunction!(A1, A2, A3, A4, A5, A6, A7);
+impl_cfunction!(A1, A2, A3, A4, A5, A6, A7, A8);
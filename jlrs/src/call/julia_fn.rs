@@ -0,0 +1,215 @@
+//! Call Julia functions with a statically typed signature.
+//!
+//! The methods of the [`Call`] trait take a slice of [`Value`]s and return a `Value`. When the
+//! argument and return types of a function are known in advance, a [`JuliaFn`] can be used
+//! instead. Its arguments are converted to Julia data with [`IntoJulia`] and the result is
+//! unboxed with [`Unbox`] after checking its type.
+//!
+//! Example:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! # use jlrs::call::julia_fn::JuliaFn;
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         let func = Module::base(&frame).function(&frame, "+")?;
+//!         let func = unsafe { func.wrapper() }.as_value();
+//!
+//!         // Checks that a method of `+` exists for the signature (Float64, Int64).
+//!         let add = JuliaFn::<(f64, i64), f64>::new_checked(&mut frame, func)?;
+//!         let sum = unsafe { add.call(&mut frame, (1.0, 2))? };
+//!         assert_eq!(sum, 3.0);
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+//!
+//! [`Call`]: crate::call::Call
+//! [`IntoJulia`]: crate::convert::into_julia::IntoJulia
+//! [`Unbox`]: crate::convert::unbox::Unbox
+
+use std::marker::PhantomData;
+
+use smallvec::SmallVec;
+
+use crate::{
+    call::Call,
+    convert::{into_jlrs_result::IntoJlrsResult, unbox::Unbox},
+    error::{JlrsResult, TypeError, CANNOT_DISPLAY_TYPE},
+    layout::typecheck::Typecheck,
+    memory::target::frame::GcFrame,
+    wrappers::ptr::{
+        datatype::DataType,
+        module::Module,
+        value::{Value, MAX_SIZE},
+        Wrapper,
+    },
+};
+
+/// A Julia function with the argument types `Args` and the return type `Ret`.
+///
+/// `Args` must be a tuple of types that implement [`IntoJulia`], and `Ret` must implement
+/// [`Unbox`] and [`Typecheck`].
+///
+/// [`IntoJulia`]: crate::convert::into_julia::IntoJulia
+pub struct JuliaFn<'scope, Args, Ret> {
+    func: Value<'scope, 'static>,
+    _marker: PhantomData<fn(Args) -> Ret>,
+}
+
+impl<'scope, Args, Ret> Clone for JuliaFn<'scope, Args, Ret> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'scope, Args, Ret> Copy for JuliaFn<'scope, Args, Ret> {}
+
+impl<'scope, Args, Ret> JuliaFn<'scope, Args, Ret>
+where
+    Args: JuliaArgs,
+    Ret: Unbox + Typecheck,
+{
+    /// Wrap `func` without checking if it has a method that matches `Args`.
+    pub fn new(func: Value<'scope, 'static>) -> Self {
+        JuliaFn {
+            func,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Wrap `func` after checking if it has a method that can be called with arguments of the
+    /// types `Args` by calling `Base.hasmethod`.
+    pub fn new_checked(frame: &mut GcFrame, func: Value<'scope, 'static>) -> JlrsResult<Self> {
+        frame.scope(|mut frame| unsafe {
+            let types = Args::julia_types(&mut frame);
+            let tt = DataType::anytuple_type(&frame)
+                .as_value()
+                .apply_type(&mut frame, types)
+                .into_jlrs_result()?;
+
+            let has_method = Module::base(&frame)
+                .function(&frame, "hasmethod")?
+                .wrapper()
+                .call2(&mut frame, func, tt)
+                .into_jlrs_result()?
+                .unbox::<bool>()?
+                .as_bool();
+
+            if !has_method {
+                let signature = tt.display_string_or(CANNOT_DISPLAY_TYPE);
+                Err(TypeError::NoMatchingMethod { signature })?
+            }
+
+            Ok(())
+        })?;
+
+        Ok(Self::new(func))
+    }
+
+    /// Returns the function.
+    pub fn function(self) -> Value<'scope, 'static> {
+        self.func
+    }
+
+    /// Call the function with `args`.
+    ///
+    /// The arguments are converted to Julia data in a new scope. If the function throws an
+    /// exception it's converted to an error, an error is also returned if the result is not an
+    /// instance of `Ret`.
+    ///
+    /// Safety: this method lets you call arbitrary Julia functions which can't be checked for
+    /// correctness. More information can be found in the [`safety`] module.
+    ///
+    /// [`safety`]: crate::safety
+    pub unsafe fn call(self, frame: &mut GcFrame, args: Args) -> JlrsResult<Ret::Output> {
+        frame.scope(|mut frame| {
+            let args = args.into_values(&mut frame);
+            self.func
+                .call(&mut frame, args)
+                .into_jlrs_result()?
+                .unbox::<Ret>()
+        })
+    }
+}
+
+/// Tuples of arguments that can be converted to Julia data.
+///
+/// This trait is implemented for tuples with up to eight elements that implement
+/// [`IntoJulia`].
+///
+/// [`IntoJulia`]: crate::convert::into_julia::IntoJulia
+pub trait JuliaArgs: private::JuliaArgsPriv {}
+
+macro_rules! impl_julia_args {
+    ($($types:ident),*) => {
+        impl<$($types),*> JuliaArgs for ($($types,)*)
+        where
+            $($types: $crate::convert::into_julia::IntoJulia),*
+        {}
+
+        impl<$($types),*> private::JuliaArgsPriv for ($($types,)*)
+        where
+            $($types: $crate::convert::into_julia::IntoJulia),*
+        {
+            #[allow(non_snake_case, unused_mut, unused_variables)]
+            fn into_values<'target>(
+                self,
+                frame: &mut GcFrame<'target>,
+            ) -> SmallVec<[Value<'target, 'static>; MAX_SIZE]> {
+                let ($($types,)*) = self;
+                let mut values = SmallVec::new();
+                $(values.push(Value::new(&mut *frame, $types));)*
+                values
+            }
+
+            #[allow(unused_mut, unused_variables)]
+            fn julia_types<'target>(
+                frame: &mut GcFrame<'target>,
+            ) -> SmallVec<[Value<'target, 'static>; MAX_SIZE]> {
+                let mut types = SmallVec::new();
+                $(types.push(<$types as $crate::convert::into_julia::IntoJulia>::julia_type(&mut *frame).as_value());)*
+                types
+            }
+        }
+    };
+}
+
+impl_julia_args!();
+impl_julia_args!(T1);
+impl_julia_args!(T1, T2);
+impl_julia_args!(T1, T2, T3);
+impl_julia_args!(T1, T2, T3, T4);
+impl_julia_args!(T1, T2, T3, T4, T5);
+impl_julia_args!(T1, T2, T3, T4, T5, T6);
+impl_julia_args!(T1, T2, T3, T4, T5, T6, T7);
+impl_julia_args!(T1, T2, T3, T4, T5, T6, T7, T8);
+
+mod private {
+    use smallvec::SmallVec;
+
+    use crate::{
+        memory::target::frame::GcFrame,
+        wrappers::ptr::value::{Value, MAX_SIZE},
+    };
+
+    pub trait JuliaArgsPriv {
+        fn into_values<'target>(
+            self,
+            frame: &mut GcFrame<'target>,
+        ) -> SmallVec<[Value<'target, 'static>; MAX_SIZE]>;
+
+        fn julia_types<'target>(
+            frame: &mut GcFrame<'target>,
+        ) -> SmallVec<[Value<'target, 'static>; MAX_SIZE]>;
+    }
+}
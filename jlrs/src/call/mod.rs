@@ -3,6 +3,28 @@
 //! This module provides the [`Call`], [`CallAsync`] and [`ProvideKeywords`] traits. Their methods
 //! can be used to call Julia functions, including inner and outer constructors; schedule a
 //! function call as a new Julia task; and provide keyword arguments respectively.
+//!
+//! A [`JuliaFn`] wraps a function with a statically typed signature, its arguments are converted
+//! to Julia data and its result is unboxed automatically. If the `internal-types` feature is
+//! enabled, a [`TypedCallable`] can be used to call a function that has been specialized for a
+//! fixed signature without dynamic dispatch. A [`RustFunction`] works in the opposite direction,
+//! it wraps a Rust closure in a Julia object that can be called from Julia. Functions that are
+//! called very often can be called more efficiently through a function pointer with
+//! [`CFunction`].
+//!
+//! [`CFunction`]: crate::call::cfunction::CFunction
+//! [`JuliaFn`]: crate::call::julia_fn::JuliaFn
+//! [`RustFunction`]: crate::call::rust_function::RustFunction
+//! [`TypedCallable`]: crate::call::typed_callable::TypedCallable
+
+pub mod cfunction;
+pub mod julia_fn;
+pub mod rust_function;
+#[cfg(all(
+    feature = "internal-types",
+    not(all(target_os = "windows", feature = "lts"))
+))]
+pub mod typed_callable;
 
 use std::ptr::NonNull;
 
@@ -0,0 +1,227 @@
+//! Rust closures that can be called from Julia.
+//!
+//! A [`RustFunction`] wraps a Rust closure in a callable Julia object. It can be passed to
+//! higher-order functions like `map`, or used as the objective of an optimizer, without writing
+//! an `extern "C"` function and a `@cfunction` shim. When it's called from Julia, its arguments
+//! are passed to the closure as a slice of [`Value`]s. If the closure returns an error or
+//! panics, a `Jlrs.RustFunctionError` is thrown in Julia.
+//!
+//! Example:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! # use jlrs::call::rust_function::RustFunction;
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         let double = RustFunction::create(frame.as_extended_target(), |frame, args| {
+//!             let x = args[0].unbox::<f64>()?;
+//!             Ok(Value::new(frame, 2.0 * x))
+//!         })?;
+//!
+//!         let data = Value::new(&mut frame, Tuple2::from((1.0f64, 2.0f64)));
+//!         let map = Module::base(&frame).function(&frame, "map")?;
+//!         let res = unsafe {
+//!             map.wrapper()
+//!                 .call2(&mut frame, double, data)
+//!                 .into_jlrs_result()?
+//!                 .unbox::<(f64, f64)>()?
+//!         };
+//!         assert_eq!(res, (2.0, 4.0));
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+
+use std::{
+    any::Any,
+    ffi::c_void,
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr::NonNull,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use jl_sys::{jl_gc_safepoint, jl_value_t};
+use smallvec::SmallVec;
+
+use crate::{
+    call::Call,
+    convert::{into_jlrs_result::IntoJlrsResult, to_symbol::ToSymbol},
+    error::JlrsResult,
+    memory::{
+        stack_frame::with_base_frame,
+        target::{frame::GcFrame, ExtendedTarget, Target},
+    },
+    private::Private,
+    wrappers::{
+        foreign::{create_foreign_type, ForeignType},
+        ptr::{
+            module::Module,
+            private::WrapperPriv,
+            simple_vector::SimpleVector,
+            string::JuliaString,
+            value::{Value, ValueData, MAX_SIZE},
+            Wrapper,
+        },
+    },
+};
+
+/// The signature of the closure wrapped by a [`RustFunction`].
+pub type RustFn = dyn for<'scope> Fn(
+        &mut GcFrame<'scope>,
+        &[Value<'scope, 'static>],
+    ) -> JlrsResult<Value<'scope, 'static>>
+    + Send
+    + Sync;
+
+/// A Rust closure that can be called from Julia.
+///
+/// A `RustFunction` is a foreign type, `Jlrs.RustFunction`, with a method that accepts any
+/// number of positional arguments. The closure is called with a new frame and the arguments,
+/// the value it returns is returned to Julia.
+pub struct RustFunction {
+    func: Box<RustFn>,
+}
+
+impl RustFunction {
+    /// Convert `func` to a callable Julia object.
+    ///
+    /// The first time this method is called the type `Jlrs.RustFunction` is created. This
+    /// requires the `Jlrs` module, which is loaded by the sync and async runtimes but not when
+    /// jlrs is used through `ccall`; in that case an error is returned.
+    pub fn create<'target, T, F>(
+        target: ExtendedTarget<'target, '_, '_, T>,
+        func: F,
+    ) -> JlrsResult<ValueData<'target, 'static, T>>
+    where
+        T: Target<'target>,
+        F: for<'scope> Fn(
+                &mut GcFrame<'scope>,
+                &[Value<'scope, 'static>],
+            ) -> JlrsResult<Value<'scope, 'static>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            init(&mut frame)?;
+
+            let func = RustFunction {
+                func: Box::new(func),
+            };
+            let value = Value::new(&mut frame, func);
+            Ok(value.root(output))
+        })
+    }
+}
+
+// Safety: the closure can't contain references to Julia data because it must be 'static.
+unsafe impl ForeignType for RustFunction {}
+
+const UNINIT: u8 = 0;
+const CREATING: u8 = 1;
+const CREATED: u8 = 2;
+
+// Tracks whether `Jlrs.RustFunction` has been created.
+static STATE: AtomicU8 = AtomicU8::new(UNINIT);
+
+fn init(frame: &mut GcFrame) -> JlrsResult<()> {
+    loop {
+        match STATE.compare_exchange(UNINIT, CREATING, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => {
+                let res = create_type(frame);
+                let state = if res.is_ok() { CREATED } else { UNINIT };
+                STATE.store(state, Ordering::Release);
+                return res;
+            }
+            Err(CREATED) => return Ok(()),
+            // Another thread is creating the type. A blocking primitive can't be used to wait
+            // for it because this thread must reach a safepoint if the GC is triggered.
+            Err(_) => unsafe {
+                jl_gc_safepoint();
+                std::thread::yield_now();
+            },
+        }
+    }
+}
+
+fn create_type(frame: &mut GcFrame) -> JlrsResult<()> {
+    // Safety: only one thread at a time calls this function, the type is set after the method
+    // has been defined successfully.
+    unsafe {
+        let module = Module::main(&frame).submodule(&frame, "Jlrs")?.wrapper();
+        if module.global(&frame, "RustFunction").is_ok() {
+            return Ok(());
+        }
+
+        let name = "RustFunction".to_symbol(&frame);
+        let ty =
+            create_foreign_type::<RustFunction, _>(&mut *frame, name, module, None, false, false);
+
+        let ptr = Value::new(&mut *frame, call_rust_function as *mut c_void);
+        module
+            .function(&frame, "initrustfunction")?
+            .wrapper()
+            .call2(&mut *frame, ty.as_value(), ptr)
+            .into_jlrs_result()?;
+
+        module.set_const_unchecked(name, ty.as_value());
+    }
+
+    Ok(())
+}
+
+// Called by `Jlrs.callrustfunction`. If the closure returns an error or panics, `failed` is set to
+// `true` and the error message is returned instead.
+unsafe extern "C" fn call_rust_function(
+    func: NonNull<RustFunction>,
+    args: SimpleVector,
+    failed: *mut bool,
+) -> *mut jl_value_t {
+    with_base_frame(|mut frame| {
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            let args = args
+                .data()
+                .as_slice()
+                .iter()
+                .map(|arg| arg.expect("Arguments are never undefined").value())
+                .collect::<SmallVec<[_; MAX_SIZE]>>();
+
+            (func.as_ref().func)(&mut frame, &args).map(|v| v.unwrap(Private))
+        }));
+
+        match res {
+            Ok(Ok(value)) => value,
+            Ok(Err(err)) => {
+                *failed = true;
+                JuliaString::new(&mut frame, err.to_string())
+                    .unwrap(Private)
+                    .cast()
+            }
+            Err(payload) => {
+                *failed = true;
+                let msg = format!("Rust function panicked: {}", panic_message(&*payload));
+                JuliaString::new(&mut frame, msg).unwrap(Private).cast()
+            }
+        }
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg
+    } else {
+        "unknown cause"
+    }
+}
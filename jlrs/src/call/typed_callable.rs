@@ -0,0 +1,329 @@
+//! Call a Julia function with a fixed signature without dynamic dispatch.
+//!
+//! Every time a function is called with the methods of the [`Call`] trait, Julia has to find the
+//! method that matches the types of the arguments and look up the code that has been compiled for
+//! that specialization. If the same function is called many times with arguments of the same
+//! types, this work can be avoided by resolving the `MethodInstance` and `CodeInstance` once and
+//! calling the compiled code directly. A [`TypedCallable`] does exactly this.
+//!
+//! Compiled code is only valid for a range of world ages. When a new method is defined the world
+//! age is incremented and code that depends on the replaced method is invalidated. A
+//! `TypedCallable` checks if the world age has changed before every call and looks up new code if
+//! necessary. If the method has been replaced, its old `MethodInstance` never gets new code; in
+//! this case the `MethodInstance` is resolved again automatically.
+//!
+//! [`Call`]: crate::call::Call
+
+use std::{mem::MaybeUninit, ptr::NonNull};
+
+use cfg_if::cfg_if;
+use jl_sys::{jl_code_instance_t, jl_get_world_counter, jl_value_t};
+use smallvec::SmallVec;
+
+use crate::{
+    call::Call,
+    catch::catch_exceptions,
+    convert::into_jlrs_result::IntoJlrsResult,
+    error::{JlrsResult, TypeError, CANNOT_DISPLAY_TYPE},
+    memory::{
+        stack_frame::with_base_frame,
+        target::{frame::GcFrame, unrooted::Unrooted, Target},
+    },
+    private::Private,
+    wrappers::ptr::{
+        datatype::DataType,
+        internal::{code_instance::CodeInstance, method_instance::MethodInstance},
+        module::Module,
+        private::WrapperPriv,
+        value::{Value, ValueResult, MAX_SIZE},
+        Wrapper,
+    },
+};
+
+type InvokeFn = unsafe extern "C" fn(
+    *mut jl_value_t,
+    *mut *mut jl_value_t,
+    u32,
+    *mut jl_code_instance_t,
+) -> *mut jl_value_t;
+
+/// A Julia function that has been specialized for a fixed set of argument types.
+///
+/// When a `TypedCallable` is created, the function is compiled for the given argument types and
+/// the resulting `MethodInstance` is stored. When it's called, the `CodeInstance` that is valid
+/// in the current world age is invoked directly through its `invoke` pointer, which forwards the
+/// arguments to the specialized code in `specptr`.
+pub struct TypedCallable<'scope> {
+    func: Value<'scope, 'static>,
+    arg_types: SmallVec<[DataType<'scope>; MAX_SIZE]>,
+    method_instance: MethodInstance<'scope>,
+    code_instance: Option<CodeInstance<'scope>>,
+    world: usize,
+}
+
+impl<'scope> TypedCallable<'scope> {
+    /// Compile `func` for arguments of the types `arg_types` and resolve its `MethodInstance`.
+    ///
+    /// All argument types must be concrete, and exactly one method of `func` must be applicable
+    /// to them. An error is returned if this is not the case or if the method can't be compiled.
+    pub fn new(
+        frame: &mut GcFrame<'scope>,
+        func: Value<'scope, 'static>,
+        arg_types: &[DataType<'scope>],
+    ) -> JlrsResult<Self> {
+        for ty in arg_types.iter().copied() {
+            if !ty.is_concrete_type() {
+                let ty = ty.display_string_or(CANNOT_DISPLAY_TYPE);
+                Err(TypeError::NotConcrete { ty })?
+            }
+        }
+
+        let method_instance = Self::resolve(frame, func, arg_types)?;
+        let mut callable = TypedCallable {
+            func,
+            arg_types: arg_types.iter().copied().collect(),
+            method_instance,
+            code_instance: None,
+            world: 0,
+        };
+
+        callable.update_code_instance();
+        Ok(callable)
+    }
+
+    /// Returns the function.
+    pub fn function(&self) -> Value<'scope, 'static> {
+        self.func
+    }
+
+    /// Returns the argument types this function has been specialized for.
+    pub fn arg_types(&self) -> &[DataType<'scope>] {
+        self.arg_types.as_ref()
+    }
+
+    /// Returns the `MethodInstance` of the specialization.
+    pub fn method_instance(&self) -> MethodInstance<'scope> {
+        self.method_instance
+    }
+
+    /// Returns the `CodeInstance` that was valid the last time the world age was checked, if
+    /// there is one.
+    pub fn code_instance(&self) -> Option<CodeInstance<'scope>> {
+        self.code_instance
+    }
+
+    /// Resolve the `MethodInstance` again.
+    ///
+    /// This happens automatically when the world age has changed and the current
+    /// `MethodInstance` has no valid code, e.g. because its method has been redefined. A more
+    /// specific method that has been defined after this `TypedCallable` was created doesn't
+    /// invalidate the code of the original method, calling this method switches to the
+    /// specialization of the new method.
+    pub fn refresh(&mut self, frame: &mut GcFrame<'scope>) -> JlrsResult<()> {
+        self.method_instance = Self::resolve(frame, self.func, self.arg_types.as_ref())?;
+        self.update_code_instance();
+        Ok(())
+    }
+
+    /// Call the function with `args`.
+    ///
+    /// The number of arguments and their types must match the argument types this
+    /// `TypedCallable` has been created with exactly, otherwise an error is returned. If the
+    /// world age has changed and no valid code exists, the `MethodInstance` is resolved again;
+    /// an error is returned if this fails. If the function throws an exception it's caught and
+    /// returned.
+    ///
+    /// Safety: this method lets you call arbitrary Julia functions which can't be checked for
+    /// correctness. More information can be found in the [`safety`] module.
+    ///
+    /// [`safety`]: crate::safety
+    pub unsafe fn call<'target, 'value, 'data, V, T>(
+        &mut self,
+        target: T,
+        args: V,
+    ) -> JlrsResult<ValueResult<'target, 'data, T>>
+    where
+        V: AsRef<[Value<'value, 'data>]>,
+        T: Target<'target>,
+    {
+        let args = args.as_ref();
+        self.check_args(args)?;
+
+        if self.world != jl_get_world_counter() {
+            self.update_code_instance();
+
+            // The method has been replaced, its MethodInstance will never get new code.
+            if self.code_instance.is_none() {
+                self.re_resolve()?;
+            }
+        }
+
+        let code_instance = match self.code_instance {
+            Some(code_instance) => code_instance,
+            None => return Ok(self.func.call(target, args)),
+        };
+
+        let invoke: InvokeFn = std::mem::transmute(code_instance.invoke());
+        let func = self.func.unwrap(Private);
+        let code_instance = code_instance.unwrap(Private);
+        let world = self.world;
+
+        let mut callback = |result: &mut MaybeUninit<*mut jl_value_t>| {
+            // The world age is restored by the exception handler if an exception is thrown.
+            let world_age = world_age();
+            let old_world = *world_age;
+            *world_age = world;
+
+            let res = invoke(
+                func,
+                args.as_ptr() as *const _ as *mut _,
+                args.len() as _,
+                code_instance,
+            );
+
+            *world_age = old_world;
+            result.write(res);
+            Ok(())
+        };
+
+        let res = match catch_exceptions(&mut callback)? {
+            Ok(ptr) => Ok(NonNull::new_unchecked(ptr)),
+            Err(e) => Err(e.ptr()),
+        };
+
+        Ok(target.result_from_ptr(res, Private))
+    }
+
+    fn check_args(&self, args: &[Value]) -> JlrsResult<()> {
+        if args.len() != self.arg_types.len() {
+            Err(TypeError::ArgumentCountMismatch {
+                expected: self.arg_types.len(),
+                found: args.len(),
+            })?
+        }
+
+        for (idx, (arg, ty)) in args.iter().zip(self.arg_types.iter()).enumerate() {
+            let arg_ty = arg.datatype();
+            if arg_ty.unwrap_non_null(Private) != ty.unwrap_non_null(Private) {
+                Err(TypeError::ArgumentTypeMismatch {
+                    idx,
+                    value_type: arg_ty.display_string_or(CANNOT_DISPLAY_TYPE),
+                    expected_type: ty.display_string_or(CANNOT_DISPLAY_TYPE),
+                })?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn resolve(
+        frame: &mut GcFrame,
+        func: Value<'_, 'static>,
+        arg_types: &[DataType],
+    ) -> JlrsResult<MethodInstance<'scope>> {
+        let method_instance = frame.scope(|mut frame| unsafe {
+            let types: SmallVec<[Value; MAX_SIZE]> =
+                arg_types.iter().map(|ty| ty.as_value()).collect();
+            let tt = DataType::anytuple_type(&frame)
+                .as_value()
+                .apply_type(&mut frame, types)
+                .into_jlrs_result()?;
+
+            // precompile(f, args::Tuple) expects a tuple of types, the full signature
+            // Tuple{typeof(f), args...} is used instead.
+            let sig_types: SmallVec<[Value; MAX_SIZE]> =
+                std::iter::once(func.datatype().as_value())
+                    .chain(arg_types.iter().map(|ty| ty.as_value()))
+                    .collect();
+            let sig = DataType::anytuple_type(&frame)
+                .as_value()
+                .apply_type(&mut frame, sig_types)
+                .into_jlrs_result()?;
+
+            let base = Module::base(&frame);
+            let compiled = base
+                .function(&frame, "precompile")?
+                .wrapper()
+                .call1(&mut frame, sig)
+                .into_jlrs_result()?
+                .unbox::<bool>()?
+                .as_bool();
+
+            if !compiled {
+                let signature = tt.display_string_or(CANNOT_DISPLAY_TYPE);
+                Err(TypeError::NoSpecialization { signature })?
+            }
+
+            let instances = base
+                .function(&frame, "method_instances")?
+                .wrapper()
+                .call2(&mut frame, func, tt)
+                .into_jlrs_result()?;
+
+            let method_instance = base
+                .function(&frame, "only")?
+                .wrapper()
+                .call1(&mut frame, instances)
+                .into_jlrs_result()?
+                .cast::<MethodInstance>()?;
+
+            Ok(method_instance.unwrap_non_null(Private))
+        })?;
+
+        // Safety: the MethodInstance is referenced by the method's specializations, it's not
+        // freed while the function exists.
+        unsafe { Ok(MethodInstance::wrap_non_null(method_instance, Private)) }
+    }
+
+    // Resolves the MethodInstance again without a frame provided by the caller. A temporary frame
+    // is pushed to the GC stack of the current task, it's popped before this method returns.
+    //
+    // Safety: must be called from a thread known to Julia.
+    unsafe fn re_resolve(&mut self) -> JlrsResult<()> {
+        let func = self.func;
+        let arg_types = self.arg_types.as_ref();
+        let res = with_base_frame(|mut frame| Self::resolve(&mut frame, func, arg_types));
+
+        self.method_instance = res?;
+        self.update_code_instance();
+        Ok(())
+    }
+
+    fn update_code_instance(&mut self) {
+        // Safety: the world counter can be read at any time. The CodeInstances in the cache of
+        // a MethodInstance are never removed.
+        unsafe {
+            let world = jl_get_world_counter();
+            let unrooted = Unrooted::new();
+            let mut current = self.method_instance.cache(unrooted);
+            self.code_instance = None;
+
+            while let Some(code_instance) = current {
+                let code_instance = code_instance.wrapper();
+                if code_instance.min_world() <= world
+                    && world <= code_instance.max_world()
+                    && !code_instance.invoke().is_null()
+                {
+                    self.code_instance = Some(code_instance);
+                    break;
+                }
+
+                current = code_instance.next(unrooted);
+            }
+
+            self.world = world;
+        }
+    }
+}
+
+unsafe fn world_age() -> *mut usize {
+    cfg_if! {
+        if #[cfg(feature = "lts")] {
+            use jl_sys::jl_get_ptls_states;
+            std::ptr::addr_of_mut!((*jl_get_ptls_states()).world_age)
+        } else {
+            use jl_sys::{jl_get_current_task, jl_task_t};
+            std::ptr::addr_of_mut!((*jl_get_current_task().cast::<jl_task_t>()).world_age)
+        }
+    }
+}
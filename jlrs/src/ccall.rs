@@ -1,11 +1,23 @@
 //! Interact with Julia when calling Rust from Julia.
 //!
-//! This module is only available if the `ccall` feature is enabled.
+//! This module is only available if the `ccall` feature is enabled. If the `jlrs-log` feature is
+//! also enabled, [`JuliaLogger`] can be used to forward messages logged with the `log` crate to
+//! Julia's current logger.
 
+#[cfg(feature = "jlrs-log")]
+use jl_sys::jl_get_current_task;
 use jl_sys::jl_throw;
 #[cfg(feature = "uv")]
 use jl_sys::uv_async_send;
 
+#[cfg(feature = "jlrs-log")]
+use crate::{
+    call::{Call, ProvideKeywords},
+    convert::into_jlrs_result::IntoJlrsResult,
+    error::JlrsError,
+    memory::stack_frame::with_base_frame,
+    wrappers::ptr::{module::Module, string::JuliaString, symbol::Symbol, Wrapper},
+};
 use crate::{
     error::JlrsResult,
     memory::{
@@ -117,3 +129,170 @@ where
     std::mem::drop(owner);
     rewrapped
 }
+
+/// A `log` backend that forwards log records to Julia's current logger.
+///
+/// Records are handled by calling `Base.CoreLogging.handle_message` with the current logger, as
+/// if they were logged with `@logmsg`. The target of the record is used as the group of the
+/// message, and its key-value pairs are converted to strings and provided as keyword arguments.
+/// Records whose level is below the `min_enabled_level` of the current logger, or that are
+/// rejected by `shouldlog`, are ignored. The current logger is looked up every time a record is
+/// logged, so loggers set with `with_logger` or `global_logger` are respected. The levels
+/// of the `log` crate are mapped to `Error` (2000), `Warn` (1000), `Info` (0), `Debug` (-1000)
+/// and `LogLevel(-2000)`.
+///
+/// Records can only be forwarded from threads known to Julia, records logged from other threads
+/// are ignored.
+///
+/// This struct is only available if the `jlrs-log` feature is enabled.
+#[cfg(feature = "jlrs-log")]
+#[derive(Debug)]
+pub struct JuliaLogger {
+    _priv: (),
+}
+
+#[cfg(feature = "jlrs-log")]
+impl JuliaLogger {
+    /// Set the `JuliaLogger` as the logger used by the `log` crate.
+    ///
+    /// The maximum level of the `log` crate is set to `Trace` so filtering is left to Julia. An
+    /// error is returned if another logger has already been set.
+    pub fn init() -> JlrsResult<()> {
+        static LOGGER: JuliaLogger = JuliaLogger { _priv: () };
+
+        log::set_logger(&LOGGER).map_err(JlrsError::other)?;
+        log::set_max_level(log::LevelFilter::Trace);
+        Ok(())
+    }
+
+    fn julia_level(level: log::Level) -> i32 {
+        match level {
+            log::Level::Error => 2000,
+            log::Level::Warn => 1000,
+            log::Level::Info => 0,
+            log::Level::Debug => -1000,
+            log::Level::Trace => -2000,
+        }
+    }
+
+    fn is_julia_thread() -> bool {
+        // Safety: jl_get_current_task returns a null pointer if the thread is unknown to Julia.
+        unsafe { !jl_get_current_task().is_null() }
+    }
+
+    // Safety: the current thread must be known to Julia.
+    unsafe fn handle_message(record: &log::Record) -> JlrsResult<()> {
+        with_base_frame(|mut frame| {
+            let logging = Module::base(&frame)
+                .submodule(&frame, "CoreLogging")?
+                .wrapper();
+
+            let logger = logging
+                .function(&frame, "current_logger")?
+                .wrapper()
+                .call0(&mut frame)
+                .into_jlrs_result()?;
+
+            let min_level = logging
+                .function(&frame, "min_enabled_level")?
+                .wrapper()
+                .call1(&mut frame, logger)
+                .into_jlrs_result()?
+                .field_accessor()
+                .field("level")?
+                .access::<i32>()?;
+
+            let julia_level = Self::julia_level(record.level());
+            if julia_level < min_level {
+                return Ok(());
+            }
+
+            let level = Value::new(&mut frame, julia_level);
+            let level = logging
+                .global(&frame, "LogLevel")?
+                .value()
+                .call1(&mut frame, level)
+                .into_jlrs_result()?;
+
+            let module = Module::main(&frame).as_value();
+            let group = Symbol::new(&frame, record.target()).as_value();
+            let line = record.line().unwrap_or(0);
+            let id = format!("{}_{}", record.module_path().unwrap_or(""), line);
+            let id = Symbol::new(&frame, id).as_value();
+
+            let should_log = logging
+                .function(&frame, "shouldlog")?
+                .wrapper()
+                .call(&mut frame, [logger, level, module, group, id])
+                .into_jlrs_result()?
+                .unbox::<bool>()?
+                .as_bool();
+
+            if !should_log {
+                return Ok(());
+            }
+
+            let message = JuliaString::new(&mut frame, record.args().to_string()).as_value();
+            let file = JuliaString::new(&mut frame, record.file().unwrap_or("")).as_value();
+            let line = Value::new(&mut frame, line as i64);
+
+            let mut visitor = KeyValueCollector(Vec::new());
+            record.key_values().visit(&mut visitor).ok();
+
+            let mut names = Vec::with_capacity(visitor.0.len());
+            let mut values = Vec::with_capacity(visitor.0.len());
+            for (name, value) in visitor.0 {
+                names.push(name);
+                values.push(JuliaString::new(&mut frame, value).as_value());
+            }
+
+            let handle_message = logging.function(&frame, "handle_message")?.wrapper();
+            let args = [logger, level, message, module, group, id, file, line];
+
+            if names.is_empty() {
+                handle_message.call(&mut frame, args).into_jlrs_result()?;
+            } else {
+                let kws = Value::new_named_tuple(frame.as_extended_target(), names, values)?;
+                handle_message
+                    .provide_keywords(kws)?
+                    .call(&mut frame, args)
+                    .into_jlrs_result()?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "jlrs-log")]
+impl log::Log for JuliaLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        Self::is_julia_thread()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !Self::is_julia_thread() {
+            return;
+        }
+
+        // Safety: this thread is known to Julia.
+        unsafe { Self::handle_message(record).ok() };
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(feature = "jlrs-log")]
+struct KeyValueCollector(Vec<(String, String)>);
+
+#[cfg(feature = "jlrs-log")]
+impl<'kvs> log::kv::VisitSource<'kvs> for KeyValueCollector {
+    fn visit_pair(
+        &mut self,
+        key: log::kv::Key<'kvs>,
+        value: log::kv::Value<'kvs>,
+    ) -> Result<(), log::kv::Error> {
+        self.0.push((key.to_string(), value.to_string()));
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+//! Convert `BigInt` and `BigFloat` data.
+//!
+//! This module is only available if the `bignum` feature is enabled. Julia's `BigInt` and
+//! `BigFloat` are implemented with GMP and MPFR, their data can't be accessed directly. Instead,
+//! they're converted through their string representations. A `BigInt` is converted to and from a
+//! `num_bigint::BigInt` exactly. A `BigFloat` is converted to a [`BigFloatString`], which
+//! contains the decimal representation of the number and its precision in bits. This
+//! representation can be converted back to a `BigFloat` without loss of precision.
+//!
+//! Example:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! use jlrs::convert::big_num::{FromJuliaBigNum, IntoJuliaBigNum};
+//! use num_bigint::BigInt;
+//!
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         let n = BigInt::from(u64::MAX) * 3;
+//!         let value = n.to_julia_big(frame.as_extended_target())?;
+//!         let back = BigInt::from_julia_big(&mut frame, value)?;
+//!         assert_eq!(n, back);
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+
+use num_bigint::BigInt;
+
+use crate::{
+    call::Call,
+    convert::into_jlrs_result::IntoJlrsResult,
+    error::{JlrsError, JlrsResult, TypeError, CANNOT_DISPLAY_TYPE},
+    memory::target::{frame::GcFrame, ExtendedTarget, Target},
+    wrappers::ptr::{
+        module::{jlrs_function, Module},
+        string::JuliaString,
+        value::{Value, ValueData},
+        Wrapper,
+    },
+};
+
+/// The decimal representation of a `BigFloat` and its precision in bits.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BigFloatString {
+    /// The decimal representation of the number, e.g. `"1.5"` or `"-2.0e+100"`.
+    pub value: String,
+    /// The precision of the number in bits.
+    pub precision: usize,
+}
+
+impl BigFloatString {
+    /// Create a new `BigFloatString` from a decimal representation and a precision in bits.
+    pub fn new<S: Into<String>>(value: S, precision: usize) -> Self {
+        BigFloatString {
+            value: value.into(),
+            precision,
+        }
+    }
+}
+
+/// Convert Rust data to a `BigInt` or `BigFloat`.
+pub trait IntoJuliaBigNum {
+    /// Convert `self` to a new `BigInt` or `BigFloat`.
+    fn to_julia_big<'target, T>(
+        &self,
+        target: ExtendedTarget<'target, '_, '_, T>,
+    ) -> JlrsResult<ValueData<'target, 'static, T>>
+    where
+        T: Target<'target>;
+}
+
+/// Convert a `BigInt` or `BigFloat` to Rust data.
+pub trait FromJuliaBigNum: Sized {
+    /// Convert `value` to a new instance of `Self`. An error is returned if `value` has the wrong
+    /// type.
+    fn from_julia_big(frame: &mut GcFrame, value: Value) -> JlrsResult<Self>;
+}
+
+impl IntoJuliaBigNum for BigInt {
+    fn to_julia_big<'target, T>(
+        &self,
+        target: ExtendedTarget<'target, '_, '_, T>,
+    ) -> JlrsResult<ValueData<'target, 'static, T>>
+    where
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let s = JuliaString::new(&mut frame, self.to_str_radix(16));
+            // Safety: parsebigint only parses the string
+            unsafe {
+                let n = jlrs_function(&frame, "parsebigint")?
+                    .call1(&mut frame, s.as_value())
+                    .into_jlrs_result()?;
+                Ok(n.root(output))
+            }
+        })
+    }
+}
+
+impl FromJuliaBigNum for BigInt {
+    fn from_julia_big(frame: &mut GcFrame, value: Value) -> JlrsResult<Self> {
+        check_type(value, "BigInt")?;
+        frame.scope(|mut frame| {
+            // Safety: bigintstring only converts the number to a string
+            let s = unsafe {
+                jlrs_function(&frame, "bigintstring")?
+                    .call1(&mut frame, value)
+                    .into_jlrs_result()?
+            };
+
+            let s = s.cast::<JuliaString>()?.as_str()?;
+            BigInt::parse_bytes(s.as_bytes(), 16).ok_or_else(|| {
+                Box::new(JlrsError::exception(format!(
+                    "Cannot parse {} as BigInt",
+                    s
+                )))
+            })
+        })
+    }
+}
+
+impl IntoJuliaBigNum for BigFloatString {
+    fn to_julia_big<'target, T>(
+        &self,
+        target: ExtendedTarget<'target, '_, '_, T>,
+    ) -> JlrsResult<ValueData<'target, 'static, T>>
+    where
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let s = JuliaString::new(&mut frame, &self.value);
+            let precision = Value::new(&mut frame, self.precision);
+            // Safety: parsebigfloat only parses the string
+            unsafe {
+                let n = jlrs_function(&frame, "parsebigfloat")?
+                    .call2(&mut frame, s.as_value(), precision)
+                    .into_jlrs_result()?;
+                Ok(n.root(output))
+            }
+        })
+    }
+}
+
+impl FromJuliaBigNum for BigFloatString {
+    fn from_julia_big(frame: &mut GcFrame, value: Value) -> JlrsResult<Self> {
+        check_type(value, "BigFloat")?;
+        frame.scope(|mut frame| {
+            // Safety: these functions only convert the number to a string and return its
+            // precision
+            let (s, precision) = unsafe {
+                let s = jlrs_function(&frame, "bigfloatstring")?
+                    .call1(&mut frame, value)
+                    .into_jlrs_result()?;
+                let precision = Module::base(&frame)
+                    .function(&frame, "precision")?
+                    .wrapper()
+                    .call1(&mut frame, value)
+                    .into_jlrs_result()?;
+                (s, precision)
+            };
+
+            Ok(BigFloatString {
+                value: s.cast::<JuliaString>()?.as_str()?.into(),
+                precision: precision.unbox::<isize>()? as usize,
+            })
+        })
+    }
+}
+
+fn check_type(value: Value, name: &str) -> JlrsResult<()> {
+    // Safety: the type is a constant in Base, it's never freed.
+    let ty = unsafe {
+        let global = value.unrooted_target();
+        Module::base(&global).global(&global, name)?.value()
+    };
+
+    if !value.isa(ty) {
+        Err(TypeError::IncompatibleType {
+            element_type: value.datatype().display_string_or(CANNOT_DISPLAY_TYPE),
+            value_type: name.into(),
+        })?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,258 @@
+//! Construct the Julia type associated with a Rust type.
+//!
+//! The [`IntoJulia`] trait can only provide the Julia type of Rust types that can be converted to
+//! Julia data directly. The [`ConstructType`] trait is more general: it constructs the Julia type
+//! object of any type that can be used as a layout, including wrappers like [`Value`] and
+//! [`Module`], arrays and tuples whose element types are constructed recursively, and structs
+//! with type parameters that derive this trait.
+//!
+//! For example, `TypedArray<f64>` constructs `Array{Float64}` and `Tuple2<i32, f64>` constructs
+//! `Tuple{Int32, Float64}`.
+//!
+//! [`IntoJulia`]: crate::convert::into_julia::IntoJulia
+//! [`Value`]: crate::wrappers::ptr::value::Value
+//! [`Module`]: crate::wrappers::ptr::module::Module
+
+use std::{ffi::c_void, ptr::NonNull};
+
+use jl_sys::{
+    jl_any_type,
+    jl_array_type,
+    jl_bool_type,
+    jl_char_type,
+    jl_datatype_type,
+    jl_function_type,
+    jl_module_type,
+    jl_pointer_type,
+    jl_simplevector_type,
+    jl_string_type,
+    jl_symbol_type,
+    jl_task_type,
+    jl_tvar_type,
+    jl_typename_type,
+    jl_unionall_type,
+    jl_uniontype_type,
+};
+
+use crate::{
+    convert::into_julia::IntoJulia,
+    layout::valid_layout::ValidField,
+    memory::target::{frame::GcFrame, ExtendedTarget, Target},
+    private::Private,
+    wrappers::{
+        foreign::ForeignType,
+        inline::{bool::Bool, char::Char, nothing::Nothing},
+        ptr::{
+            array::{Array, TypedArray},
+            datatype::DataType,
+            function::Function,
+            module::Module,
+            private::WrapperPriv,
+            simple_vector::SimpleVector,
+            string::JuliaString,
+            symbol::Symbol,
+            task::Task,
+            type_name::TypeName,
+            type_var::TypeVar,
+            union::Union,
+            union_all::UnionAll,
+            value::{Value, ValueData},
+            Ref,
+            Wrapper,
+        },
+    },
+};
+
+/// Construct the Julia type object associated with the implementor.
+///
+/// This trait is implemented for all primitive types, the wrapper types, arrays and tuples. It
+/// can be derived for structs that have a corresponding Julia type, if the struct has type
+/// parameters they're applied to the Julia type in the same order. Every type parameter must be
+/// used by a field, and the Julia type must not have any other parameters.
+///
+/// Safety: the constructed type must be a valid layout for the implementor.
+pub unsafe trait ConstructType {
+    /// Construct the type object associated with this type. The type object is rooted using
+    /// `target`, its frame is used to root temporary data.
+    fn construct_type<'target, 'current, 'borrow, T>(
+        target: ExtendedTarget<'target, 'current, 'borrow, T>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>;
+}
+
+macro_rules! impl_construct_type_into_julia {
+    ($type:ty) => {
+        unsafe impl ConstructType for $type {
+            fn construct_type<'target, 'current, 'borrow, T>(
+                target: ExtendedTarget<'target, 'current, 'borrow, T>,
+            ) -> ValueData<'target, 'static, T>
+            where
+                T: Target<'target>,
+            {
+                let (output, _) = target.split();
+                // Safety: the type is a global constant
+                unsafe {
+                    let ty = <$type as IntoJulia>::julia_type(&output).ptr();
+                    output.data_from_ptr(ty.cast(), Private)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_construct_type_constant {
+    ($type:ty, $julia_type:ident) => {
+        unsafe impl ConstructType for $type {
+            fn construct_type<'target, 'current, 'borrow, T>(
+                target: ExtendedTarget<'target, 'current, 'borrow, T>,
+            ) -> ValueData<'target, 'static, T>
+            where
+                T: Target<'target>,
+            {
+                let (output, _) = target.split();
+                // Safety: the type is a global constant
+                unsafe { output.data_from_ptr(NonNull::new_unchecked($julia_type.cast()), Private) }
+            }
+        }
+    };
+}
+
+impl_construct_type_into_julia!(bool);
+impl_construct_type_into_julia!(char);
+impl_construct_type_into_julia!(u8);
+impl_construct_type_into_julia!(u16);
+impl_construct_type_into_julia!(u32);
+impl_construct_type_into_julia!(u64);
+impl_construct_type_into_julia!(usize);
+impl_construct_type_into_julia!(i8);
+impl_construct_type_into_julia!(i16);
+impl_construct_type_into_julia!(i32);
+impl_construct_type_into_julia!(i64);
+impl_construct_type_into_julia!(isize);
+impl_construct_type_into_julia!(f32);
+impl_construct_type_into_julia!(f64);
+impl_construct_type_into_julia!(*mut c_void);
+
+#[cfg(feature = "f16")]
+impl_construct_type_into_julia!(half::f16);
+impl_construct_type_into_julia!(Nothing);
+
+impl_construct_type_constant!(Bool, jl_bool_type);
+impl_construct_type_constant!(Char, jl_char_type);
+impl_construct_type_constant!(Value<'_, '_>, jl_any_type);
+impl_construct_type_constant!(Module<'_>, jl_module_type);
+impl_construct_type_constant!(Symbol<'_>, jl_symbol_type);
+impl_construct_type_constant!(DataType<'_>, jl_datatype_type);
+impl_construct_type_constant!(UnionAll<'_>, jl_unionall_type);
+impl_construct_type_constant!(Union<'_>, jl_uniontype_type);
+impl_construct_type_constant!(TypeVar<'_>, jl_tvar_type);
+impl_construct_type_constant!(TypeName<'_>, jl_typename_type);
+impl_construct_type_constant!(SimpleVector<'_>, jl_simplevector_type);
+impl_construct_type_constant!(JuliaString<'_>, jl_string_type);
+impl_construct_type_constant!(Task<'_>, jl_task_type);
+impl_construct_type_constant!(Function<'_, '_>, jl_function_type);
+impl_construct_type_constant!(Array<'_, '_>, jl_array_type);
+
+unsafe impl<F: ForeignType> ConstructType for F {
+    fn construct_type<'target, 'current, 'borrow, T>(
+        target: ExtendedTarget<'target, 'current, 'borrow, T>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        let (output, _) = target.split();
+        // Safety: foreign types are rooted when they're registered
+        unsafe {
+            let ty = <F as IntoJulia>::julia_type(&output).ptr();
+            output.data_from_ptr(ty.cast(), Private)
+        }
+    }
+}
+
+unsafe impl<'scope, 'data, W> ConstructType for Ref<'scope, 'data, W>
+where
+    W: Wrapper<'scope, 'data> + ConstructType,
+{
+    fn construct_type<'target, 'current, 'borrow, T>(
+        target: ExtendedTarget<'target, 'current, 'borrow, T>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        W::construct_type(target)
+    }
+}
+
+unsafe impl<'scope, 'data, W> ConstructType for Option<Ref<'scope, 'data, W>>
+where
+    W: Wrapper<'scope, 'data> + ConstructType,
+{
+    fn construct_type<'target, 'current, 'borrow, T>(
+        target: ExtendedTarget<'target, 'current, 'borrow, T>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        W::construct_type(target)
+    }
+}
+
+unsafe impl<'scope, 'data, U> ConstructType for TypedArray<'scope, 'data, U>
+where
+    U: ValidField + ConstructType,
+{
+    fn construct_type<'target, 'current, 'borrow, T>(
+        target: ExtendedTarget<'target, 'current, 'borrow, T>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        // Safety: the element type is rooted until Array{T} has been constructed.
+        unsafe {
+            apply_to_constant(target, jl_array_type.cast(), |frame| {
+                [U::construct_type(frame.as_extended_target())]
+            })
+        }
+    }
+}
+
+unsafe impl<U> ConstructType for *mut U
+where
+    U: ConstructType,
+{
+    fn construct_type<'target, 'current, 'borrow, T>(
+        target: ExtendedTarget<'target, 'current, 'borrow, T>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        // Safety: the pointee type is rooted until Ptr{T} has been constructed.
+        unsafe {
+            apply_to_constant(target, jl_pointer_type.cast(), |frame| {
+                [U::construct_type(frame.as_extended_target())]
+            })
+        }
+    }
+}
+
+// Applies the parameters returned by `params` to `base`, a global constant.
+pub(crate) unsafe fn apply_to_constant<'target, T, F, const N: usize>(
+    target: ExtendedTarget<'target, '_, '_, T>,
+    base: *mut jl_sys::jl_value_t,
+    params: F,
+) -> ValueData<'target, 'static, T>
+where
+    T: Target<'target>,
+    F: for<'inner> FnOnce(&mut GcFrame<'inner>) -> [Value<'inner, 'static>; N],
+{
+    let (output, frame) = target.split();
+    frame
+        .scope(|mut frame| {
+            let params = params(&mut frame);
+            let base = Value::wrap_non_null(NonNull::new_unchecked(base), Private);
+            let ty = base.apply_type_unchecked(&mut frame, params);
+            Ok(output.data_from_ptr(ty.unwrap_non_null(Private), Private))
+        })
+        .unwrap()
+}
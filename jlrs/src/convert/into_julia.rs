@@ -2,9 +2,9 @@
 //!
 //! In order to use data from Rust in Julia it must first be converted to a [`Value`]. The
 //! easiest way to do this is by calling [`Value::new`], which is compatible with types that
-//! implement the [`IntoJulia`] trait defined in this module. This trait only supports
-//! isbits-types, and should not be implemented manually. Rather, you should use JlrsReflect.jl to
-//! automatically derive it for compatible types.
+//! implement the [`IntoJulia`] trait defined in this module. This trait supports isbits-types,
+//! immutable types with pointer fields and mutable types. It should not be implemented manually,
+//! rather, you should use JlrsReflect.jl to automatically derive it for compatible types.
 //!
 //! [`Value::new`]: crate::wrappers::ptr::value::Value::new
 //! [`Value`]: crate::wrappers::ptr::value::Value
@@ -30,6 +30,7 @@ use jl_sys::{
     jl_char_type,
     jl_float32_type,
     jl_float64_type,
+    jl_gc_wb,
     jl_int16_type,
     jl_int32_type,
     jl_int64_type,
@@ -39,6 +40,7 @@ use jl_sys::{
     jl_uint32_type,
     jl_uint64_type,
     jl_uint8_type,
+    jl_value_t,
     jl_voidpointer_type,
 };
 
@@ -60,14 +62,20 @@ use crate::{
 /// If you do choose to implement it manually, you only need to implement the `julia_type` method
 /// which must return the `DataType` of the type this data will have in Julia. The layout of this
 /// type and the type in Rust must match exactly. Incompatible layouts will cause undefined
-/// behavior. The type in Rust must always be `#[repr(C)]`. The `DataType` must be an isbits-type.
+/// behavior. The type in Rust must always be `#[repr(C)]`. The `DataType` must be concrete, it
+/// can be mutable and have pointer fields. Pointer fields are represented by `Ref`s or
+/// `Option<Ref>`s in Rust, the data they point to must be rooted until the value has been
+/// converted. The converted value has no data lifetime, so these pointers must not reference
+/// data borrowed from Rust: when this trait is derived every lifetime except the first one must
+/// be `'static`. Arrays can only be created for types whose `DataType` is a bits type, such types
+/// must also implement [`IsBits`].
 ///
 /// [`Value::new`]: crate::wrappers::ptr::value::Value::new
-pub unsafe trait IntoJulia: Sized + 'static {
+pub unsafe trait IntoJulia: Sized {
     /// Returns the associated Julia type of the implementor.
     ///
-    /// The layout of that type and the Rust type must match exactly, and it must be an `isbits`
-    /// type, otherwise this trait has been implemented incorrectly.
+    /// The layout of that type and the Rust type must match exactly, otherwise this trait has
+    /// been implemented incorrectly.
     fn julia_type<'scope, T>(target: T) -> DataTypeData<'scope, T>
     where
         T: Target<'scope>;
@@ -78,16 +86,37 @@ pub unsafe trait IntoJulia: Sized + 'static {
         T: Target<'scope>,
     {
         // Safety: trait is implemented incorrectly if this is incorrect. A new instance of the
-        // associated
+        // associated type is allocated and initialized with the contents of self, a write barrier
+        // is inserted for every pointer that is stored in it.
         unsafe {
             // TODO: root this data until the data has been instantiated.
             let ty = Self::julia_type(&target).wrapper();
-            debug_assert!(ty.is_bits());
+            assert!(
+                ty.is_concrete_type(),
+                "IntoJulia is implemented incorrectly, the Julia type is not concrete"
+            );
 
             let instance = ty.instance();
             if instance.is_none() {
                 let container = jl_new_struct_uninit(ty.unwrap(Private));
                 container.cast::<Self>().write(self);
+
+                if !ty.is_bits() {
+                    let layout = ty.layout();
+                    for idx in 0..layout.n_pointers() as usize {
+                        let offset = layout.pointer_offset(idx);
+                        let ptr = container
+                            .cast::<u8>()
+                            .add(offset)
+                            .cast::<*mut jl_value_t>()
+                            .read();
+
+                        if !ptr.is_null() {
+                            jl_gc_wb(container, ptr);
+                        }
+                    }
+                }
+
                 target.data_from_ptr(NonNull::new_unchecked(container), Private)
             } else {
                 target.data_from_ptr(instance.unwrap().unwrap_non_null(Private), Private)
@@ -96,6 +125,18 @@ pub unsafe trait IntoJulia: Sized + 'static {
     }
 }
 
+/// Marker trait implemented by types that implement `IntoJulia` and whose Julia type is a bits
+/// type.
+///
+/// Arrays created from Rust data store their elements inline, so they can only be created for
+/// types that implement this trait. It can be derived for structs that are mapped to an
+/// immutable Julia type without pointer fields, every field must implement `IsBits`. Because
+/// the derive macro can't check if the Julia type is mutable, the array constructors that use
+/// Rust data return an error if the Julia type is not a bits type.
+///
+/// Safety: the type returned by `IntoJulia::julia_type` must be a bits type.
+pub unsafe trait IsBits: IntoJulia {}
+
 macro_rules! impl_into_julia {
     ($type:ty, $boxer:ident, $julia_type:expr) => {
         // Safety: These implemetations use a boxing function provided by Julia
@@ -131,6 +172,9 @@ macro_rules! impl_into_julia {
                 }
             }
         }
+
+        // Safety: all primitive types are bits types
+        unsafe impl IsBits for $type {}
     };
 }
 
@@ -186,3 +230,6 @@ unsafe impl<U: IntoJulia> IntoJulia for *mut U {
         }
     }
 }
+
+// Safety: Ptr{T} is a bits type
+unsafe impl<U: IntoJulia> IsBits for *mut U {}
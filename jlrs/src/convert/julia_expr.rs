@@ -0,0 +1,392 @@
+//! Build, parse and evaluate Julia expressions.
+//!
+//! Generating Julia code by concatenating strings and evaluating the result with
+//! [`Value::eval_string`] is error-prone, every string that ends up in the generated code must
+//! be escaped correctly. A [`JuliaExpr`] describes an expression as a tree of `Expr`s,
+//! `QuoteNode`s, `LineNumberNode`s, `GlobalRef`s and literals. Existing Julia data can be
+//! interpolated with [`JuliaExpr::value`], it's inserted into the expression as is. Strings are
+//! always inserted as string literals, they're never parsed as code.
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! # use jlrs::convert::julia_expr::JuliaExpr;
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         let data = Value::new(&mut frame, 3.0f64);
+//!         let base = Module::base(&frame);
+//!
+//!         // Base.:+(data, 2.0)
+//!         let expr = JuliaExpr::call(
+//!             JuliaExpr::global_ref(base, "+"),
+//!             [JuliaExpr::value(data), JuliaExpr::from(2.0)],
+//!         );
+//!
+//!         let main = Module::main(&frame);
+//!         let res = unsafe { expr.eval(frame.as_extended_target(), main)? }.into_jlrs_result()?;
+//!         assert_eq!(res.unbox::<f64>()?, 5.0);
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+//!
+//! Code can also be parsed with [`parse`] and evaluated with [`include_string`], both take the
+//! name of a file which is used in the line number information of the parsed code, backtraces
+//! and error messages.
+//!
+//! [`Value::eval_string`]: crate::wrappers::ptr::value::Value::eval_string
+
+use smallvec::SmallVec;
+
+use crate::{
+    call::{Call, ProvideKeywords},
+    convert::{into_jlrs_result::IntoJlrsResult, to_symbol::ToSymbol},
+    error::JlrsResult,
+    memory::target::{frame::GcFrame, output::Output, ExtendedTarget, Target},
+    private::Private,
+    wrappers::ptr::{
+        function::Function,
+        module::Module,
+        private::WrapperPriv,
+        string::JuliaString,
+        value::{Value, ValueData, ValueResult, MAX_SIZE},
+        Wrapper,
+    },
+};
+
+/// A Julia expression.
+#[derive(Clone, Debug)]
+pub enum JuliaExpr<'scope, 'data> {
+    /// An `Expr` with a head and arguments, e.g. `Expr(:call, :f, 1)`.
+    Expr(String, Vec<JuliaExpr<'scope, 'data>>),
+    /// A symbol, which is evaluated as a name.
+    Symbol(String),
+    /// A `QuoteNode`, which is evaluated as its content without evaluating it.
+    QuoteNode(Box<JuliaExpr<'scope, 'data>>),
+    /// A `LineNumberNode` with a line and an optional file name.
+    LineNumber(usize, Option<String>),
+    /// A `GlobalRef`, which refers to a global in a specific module.
+    GlobalRef(Module<'scope>, String),
+    /// Interpolated Julia data.
+    Value(Value<'scope, 'data>),
+    /// An integer literal, which has the type `Int`.
+    Int(isize),
+    /// A floating-point literal.
+    Float(f64),
+    /// A boolean literal.
+    Bool(bool),
+    /// A string literal.
+    String(String),
+    /// `nothing`.
+    Nothing,
+}
+
+impl<'scope, 'data> JuliaExpr<'scope, 'data> {
+    /// An `Expr` with the given head and arguments.
+    pub fn expr<H, I>(head: H, args: I) -> Self
+    where
+        H: Into<String>,
+        I: IntoIterator<Item = Self>,
+    {
+        JuliaExpr::Expr(head.into(), args.into_iter().collect())
+    }
+
+    /// A function call, `func(args...)`.
+    pub fn call<I: IntoIterator<Item = Self>>(func: Self, args: I) -> Self {
+        let mut call_args = vec![func];
+        call_args.extend(args);
+        JuliaExpr::Expr("call".into(), call_args)
+    }
+
+    /// A block of expressions, `begin exprs... end`.
+    pub fn block<I: IntoIterator<Item = Self>>(exprs: I) -> Self {
+        JuliaExpr::expr("block", exprs)
+    }
+
+    /// An assignment, `lhs = rhs`.
+    pub fn assign(lhs: Self, rhs: Self) -> Self {
+        JuliaExpr::Expr("=".into(), vec![lhs, rhs])
+    }
+
+    /// A symbol.
+    pub fn symbol<N: Into<String>>(name: N) -> Self {
+        JuliaExpr::Symbol(name.into())
+    }
+
+    /// A `QuoteNode` that contains `expr`.
+    pub fn quote(expr: Self) -> Self {
+        JuliaExpr::QuoteNode(Box::new(expr))
+    }
+
+    /// A `LineNumberNode`.
+    pub fn line_number(line: usize, file: Option<&str>) -> Self {
+        JuliaExpr::LineNumber(line, file.map(Into::into))
+    }
+
+    /// A `GlobalRef` to the global `name` in `module`.
+    pub fn global_ref<N: Into<String>>(module: Module<'scope>, name: N) -> Self {
+        JuliaExpr::GlobalRef(module, name.into())
+    }
+
+    /// Interpolate `value`.
+    pub fn value(value: Value<'scope, 'data>) -> Self {
+        JuliaExpr::Value(value)
+    }
+
+    /// Convert this expression to Julia data.
+    pub fn build<'target, T>(
+        &self,
+        target: ExtendedTarget<'target, '_, '_, T>,
+    ) -> JlrsResult<ValueData<'target, 'data, T>>
+    where
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let expr = self.build_value(&mut frame)?;
+            // Safety: the expression is rooted until it has been returned
+            unsafe { Ok(output.data_from_ptr(expr.unwrap_non_null(Private), Private)) }
+        })
+    }
+
+    /// Convert this expression to Julia data and evaluate it in `module` with `Core.eval`.
+    ///
+    /// Safety: the expression can't be checked for correctness, evaluating it can cause
+    /// arbitrary code to be executed.
+    pub unsafe fn eval<'target, T>(
+        &self,
+        target: ExtendedTarget<'target, '_, '_, T>,
+        module: Module,
+    ) -> JlrsResult<ValueResult<'target, 'data, T>>
+    where
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let expr = self.build_value(&mut frame)?;
+            Ok(core_eval(&frame)?.call2(output, module.as_value(), expr))
+        })
+    }
+
+    fn build_value<'target>(
+        &self,
+        frame: &mut GcFrame<'target>,
+    ) -> JlrsResult<Value<'target, 'data>> {
+        match self {
+            JuliaExpr::Expr(head, args) => {
+                let output = frame.output();
+                frame.scope(|mut frame| {
+                    let mut values: SmallVec<[Value; MAX_SIZE]> =
+                        SmallVec::with_capacity(args.len() + 1);
+                    values.push(head.to_symbol(&frame).as_value());
+                    for arg in args {
+                        values.push(arg.build_value(&mut frame)?);
+                    }
+
+                    // Safety: Expr only stores its arguments.
+                    unsafe { core_call(&frame, output, "Expr", values) }
+                })
+            }
+            JuliaExpr::Symbol(name) => Ok(name.to_symbol(&*frame).as_value()),
+            JuliaExpr::QuoteNode(expr) => {
+                let output = frame.output();
+                frame.scope(|mut frame| {
+                    let expr = expr.build_value(&mut frame)?;
+                    // Safety: QuoteNode only stores its argument.
+                    unsafe { core_call(&frame, output, "QuoteNode", [expr]) }
+                })
+            }
+            JuliaExpr::LineNumber(line, file) => {
+                let output = frame.output();
+                frame.scope(|mut frame| {
+                    let line = Value::new(&mut frame, *line as isize);
+                    let file = match file {
+                        Some(file) => file.to_symbol(&frame).as_value(),
+                        None => Value::nothing(&frame),
+                    };
+
+                    // Safety: LineNumberNode only stores its arguments.
+                    unsafe { core_call(&frame, output, "LineNumberNode", [line, file]) }
+                })
+            }
+            JuliaExpr::GlobalRef(module, name) => {
+                let output = frame.output();
+                let name = name.to_symbol(&*frame).as_value();
+                // Safety: GlobalRef only stores its arguments.
+                unsafe { core_call(&*frame, output, "GlobalRef", [module.as_value(), name]) }
+            }
+            JuliaExpr::Value(value) => {
+                // Safety: the value is rooted in an outer scope, it's only used while self is
+                // borrowed.
+                unsafe {
+                    Ok(Value::wrap_non_null(
+                        value.unwrap_non_null(Private),
+                        Private,
+                    ))
+                }
+            }
+            JuliaExpr::Int(i) => Ok(Value::new(frame, *i)),
+            JuliaExpr::Float(f) => Ok(Value::new(frame, *f)),
+            JuliaExpr::Bool(b) => Ok(Value::new(frame, *b)),
+            JuliaExpr::String(s) => Ok(JuliaString::new(frame, s).as_value()),
+            JuliaExpr::Nothing => Ok(Value::nothing(&*frame)),
+        }
+    }
+}
+
+impl From<isize> for JuliaExpr<'_, '_> {
+    fn from(i: isize) -> Self {
+        JuliaExpr::Int(i)
+    }
+}
+
+impl From<f64> for JuliaExpr<'_, '_> {
+    fn from(f: f64) -> Self {
+        JuliaExpr::Float(f)
+    }
+}
+
+impl From<bool> for JuliaExpr<'_, '_> {
+    fn from(b: bool) -> Self {
+        JuliaExpr::Bool(b)
+    }
+}
+
+impl From<&str> for JuliaExpr<'_, '_> {
+    fn from(s: &str) -> Self {
+        JuliaExpr::String(s.into())
+    }
+}
+
+impl From<String> for JuliaExpr<'_, '_> {
+    fn from(s: String) -> Self {
+        JuliaExpr::String(s)
+    }
+}
+
+impl<'scope, 'data> From<Value<'scope, 'data>> for JuliaExpr<'scope, 'data> {
+    fn from(value: Value<'scope, 'data>) -> Self {
+        JuliaExpr::Value(value)
+    }
+}
+
+/// Parse `code` with `Meta.parseall`, the result is a `:toplevel` expression. Line number
+/// information refers to `filename`.
+///
+/// Syntax errors don't throw an exception, they're stored in the parsed expression as an
+/// `:error` or `:incomplete` expression. An exception is thrown when such an expression is
+/// evaluated.
+pub fn parse<'target, C, F, T>(
+    target: ExtendedTarget<'target, '_, '_, T>,
+    code: C,
+    filename: F,
+) -> JlrsResult<ValueData<'target, 'static, T>>
+where
+    C: AsRef<str>,
+    F: AsRef<str>,
+    T: Target<'target>,
+{
+    let (output, frame) = target.split();
+    frame.scope(|mut frame| {
+        let code = JuliaString::new(&mut frame, code);
+        let filename = JuliaString::new(&mut frame, filename);
+        let keywords = Value::new_named_tuple(
+            frame.as_extended_target(),
+            ["filename"],
+            [filename.as_value()],
+        )?;
+
+        // Safety: parsing code doesn't evaluate it.
+        unsafe {
+            let expr = Module::base(&frame)
+                .submodule(&frame, "Meta")?
+                .wrapper()
+                .function(&frame, "parseall")?
+                .wrapper()
+                .provide_keywords(keywords)?
+                .call1(&mut frame, code.as_value())
+                .into_jlrs_result()?;
+
+            Ok(output.data_from_ptr(expr.unwrap_non_null(Private), Private))
+        }
+    })
+}
+
+/// Evaluate `expr` in `module` with `Core.eval`.
+///
+/// Safety: the expression can't be checked for correctness, evaluating it can cause arbitrary
+/// code to be executed.
+pub unsafe fn eval<'target, 'data, T>(
+    target: T,
+    module: Module,
+    expr: Value<'_, 'data>,
+) -> JlrsResult<ValueResult<'target, 'data, T>>
+where
+    T: Target<'target>,
+{
+    Ok(core_eval(&target)?.call2(target, module.as_value(), expr))
+}
+
+/// Parse and evaluate `code` in `module` with `Base.include_string`. Line number information,
+/// which is used in backtraces and error messages, refers to `filename`.
+///
+/// Safety: the code can't be checked for correctness, evaluating it can cause arbitrary code to
+/// be executed.
+pub unsafe fn include_string<'target, C, F, T>(
+    target: ExtendedTarget<'target, '_, '_, T>,
+    module: Module,
+    code: C,
+    filename: F,
+) -> JlrsResult<ValueResult<'target, 'static, T>>
+where
+    C: AsRef<str>,
+    F: AsRef<str>,
+    T: Target<'target>,
+{
+    let (output, frame) = target.split();
+    frame.scope(|mut frame| {
+        let code = JuliaString::new(&mut frame, code);
+        let filename = JuliaString::new(&mut frame, filename);
+
+        Ok(Module::base(&frame)
+            .function(&frame, "include_string")?
+            .wrapper()
+            .call3(
+                output,
+                module.as_value(),
+                code.as_value(),
+                filename.as_value(),
+            ))
+    })
+}
+
+fn core_eval<'target, T: Target<'target>>(target: &T) -> JlrsResult<Function<'target, 'static>> {
+    // Safety: Core.eval is globally rooted.
+    unsafe { Ok(Module::core(target).function(target, "eval")?.wrapper()) }
+}
+
+// Safety: calling the constructor must not have any side effects.
+unsafe fn core_call<'target, 'current, 'value, 'data, T, V>(
+    target: &T,
+    output: Output<'target>,
+    name: &str,
+    args: V,
+) -> JlrsResult<Value<'target, 'data>>
+where
+    T: Target<'current>,
+    V: AsRef<[Value<'value, 'data>]>,
+{
+    Module::core(target)
+        .global(target, name)?
+        .value()
+        .call(output, args)
+        .into_jlrs_result()
+}
@@ -0,0 +1,220 @@
+//! Generate Julia struct definitions from Rust types.
+//!
+//! JlrsReflect.jl generates Rust structs from existing Julia types. This module works in the
+//! opposite direction: the [`JuliaStruct`] trait, which can be derived, provides the definition
+//! of a Julia struct whose layout matches the layout of the Rust struct. This definition can be
+//! evaluated in a module with [`JuliaStruct::define`], which checks if the layouts match after
+//! the definition has been evaluated.
+//!
+//! Example:
+//!
+//! ```ignore
+//! #[repr(C)]
+//! #[derive(Clone, Debug, Unbox, ValidLayout, Typecheck, IntoJulia, JuliaStruct)]
+//! #[jlrs(julia_type = "Main.MyStruct")]
+//! pub struct MyStruct {
+//!     a: i32,
+//!     b: Bool,
+//!     c: Option<ValueRef<'static, 'static>>,
+//! }
+//! ```
+//!
+//! The definition of `MyStruct` is:
+//!
+//! ```julia
+//! struct MyStruct
+//!     a::Core.Int32
+//!     b::Core.Bool
+//!     c::Core.Any
+//! end
+//! ```
+//!
+//! Type parameters are declared in the same order as they appear in Rust. Fields that contain a
+//! bits union must provide their Julia type with `#[jlrs(julia_type = "Union{...}")]`, the
+//! alignment and flag fields of the bits union are skipped.
+
+use std::ffi::c_void;
+
+use crate::{
+    call::Call,
+    convert::into_jlrs_result::IntoJlrsResult,
+    error::{AccessError, JlrsResult, CANNOT_DISPLAY_TYPE},
+    layout::valid_layout::{ValidField, ValidLayout},
+    memory::target::{ExtendedTarget, Target},
+    wrappers::{
+        inline::{bool::Bool, char::Char},
+        ptr::{
+            array::{Array, TypedArray},
+            datatype::{DataType, DataTypeData},
+            function::Function,
+            module::Module,
+            simple_vector::SimpleVector,
+            string::JuliaString,
+            symbol::Symbol,
+            task::Task,
+            type_name::TypeName,
+            type_var::TypeVar,
+            union::Union,
+            union_all::UnionAll,
+            value::Value,
+            Ref,
+            Wrapper,
+        },
+    },
+};
+
+/// Provides the name of the Julia type associated with the implementor as it's used in a struct
+/// definition, e.g. `Core.Int32` for `i32` and `Core.Tuple{Core.Int32, Core.Float64}` for
+/// `Tuple2<i32, f64>`.
+///
+/// Safety: the returned type must have the same layout as the implementor when it's used as a
+/// field type.
+pub unsafe trait JuliaTypeString {
+    /// Returns the name of the associated Julia type.
+    fn julia_type_string() -> String;
+}
+
+/// Provides the definition of a Julia struct with the same layout as the implementor.
+///
+/// This trait should be derived, the definition is generated from the fields of the struct.
+///
+/// Safety: the layout of the defined struct must match the layout of the implementor.
+pub unsafe trait JuliaStruct: JuliaTypeString {
+    /// Returns the definition of the Julia struct.
+    fn julia_definition() -> String;
+
+    /// Evaluate the definition of this struct in `module` and return the type associated with
+    /// `Self`.
+    ///
+    /// The definition is evaluated with `Base.include_string`, redefining a struct is only
+    /// possible if the new definition is identical to the old one. The type is looked up by its
+    /// name in `module`, the module in the path set with `#[jlrs(julia_type = "...")]` is
+    /// ignored. Builtin types are qualified in the definition, e.g. `Core.Int32`, so it can be
+    /// evaluated in a `baremodule`. An error is returned if the definition can't be evaluated,
+    /// or if the layout of the resulting type doesn't match the layout of `Self`.
+    fn define<'target, T>(
+        target: ExtendedTarget<'target, '_, '_, T>,
+        module: Module,
+    ) -> JlrsResult<DataTypeData<'target, T>>
+    where
+        T: Target<'target>,
+        Self: ValidLayout,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            // Safety: the definition only defines a new type, the type itself is evaluated to
+            // look it up.
+            unsafe {
+                let include_string = Module::base(&frame)
+                    .function(&frame, "include_string")?
+                    .wrapper();
+
+                let definition = JuliaString::new(&mut frame, Self::julia_definition());
+                include_string
+                    .call2(&mut frame, module.as_value(), definition.as_value())
+                    .into_jlrs_result()?;
+
+                // The type string is the full path of the type, which can differ from the module
+                // the definition has been evaluated in.
+                let type_string = Self::julia_type_string();
+                let (path, params) =
+                    type_string.split_at(type_string.find('{').unwrap_or(type_string.len()));
+                let name = path.rsplit('.').next().unwrap_or(path);
+                let ty_name = JuliaString::new(&mut frame, format!("{}{}", name, params));
+                let ty = include_string
+                    .call2(&mut frame, module.as_value(), ty_name.as_value())
+                    .into_jlrs_result()?;
+
+                if !Self::valid_layout(ty) {
+                    Err(AccessError::InvalidLayout {
+                        value_type: ty.display_string_or(CANNOT_DISPLAY_TYPE),
+                    })?
+                }
+
+                Ok(ty.cast::<DataType>()?.root(output))
+            }
+        })
+    }
+}
+
+macro_rules! impl_julia_type_string {
+    ($type:ty, $name:expr) => {
+        unsafe impl JuliaTypeString for $type {
+            fn julia_type_string() -> String {
+                String::from($name)
+            }
+        }
+    };
+}
+
+impl_julia_type_string!(bool, "Core.Bool");
+impl_julia_type_string!(char, "Core.Char");
+impl_julia_type_string!(u8, "Core.UInt8");
+impl_julia_type_string!(u16, "Core.UInt16");
+impl_julia_type_string!(u32, "Core.UInt32");
+impl_julia_type_string!(u64, "Core.UInt64");
+impl_julia_type_string!(i8, "Core.Int8");
+impl_julia_type_string!(i16, "Core.Int16");
+impl_julia_type_string!(i32, "Core.Int32");
+impl_julia_type_string!(i64, "Core.Int64");
+impl_julia_type_string!(f32, "Core.Float32");
+impl_julia_type_string!(f64, "Core.Float64");
+impl_julia_type_string!(c_void, "Core.Nothing");
+
+#[cfg(target_pointer_width = "32")]
+impl_julia_type_string!(usize, "Core.UInt32");
+#[cfg(target_pointer_width = "64")]
+impl_julia_type_string!(usize, "Core.UInt64");
+#[cfg(target_pointer_width = "32")]
+impl_julia_type_string!(isize, "Core.Int32");
+#[cfg(target_pointer_width = "64")]
+impl_julia_type_string!(isize, "Core.Int64");
+
+#[cfg(feature = "f16")]
+impl_julia_type_string!(half::f16, "Core.Float16");
+
+impl_julia_type_string!(Bool, "Core.Bool");
+impl_julia_type_string!(Char, "Core.Char");
+impl_julia_type_string!(Value<'_, '_>, "Core.Any");
+impl_julia_type_string!(Module<'_>, "Core.Module");
+impl_julia_type_string!(Symbol<'_>, "Core.Symbol");
+impl_julia_type_string!(DataType<'_>, "Core.DataType");
+impl_julia_type_string!(UnionAll<'_>, "Core.UnionAll");
+impl_julia_type_string!(Union<'_>, "Core.Union");
+impl_julia_type_string!(TypeVar<'_>, "Core.TypeVar");
+impl_julia_type_string!(TypeName<'_>, "Core.TypeName");
+impl_julia_type_string!(SimpleVector<'_>, "Core.SimpleVector");
+impl_julia_type_string!(JuliaString<'_>, "Core.String");
+impl_julia_type_string!(Task<'_>, "Core.Task");
+impl_julia_type_string!(Function<'_, '_>, "Core.Function");
+impl_julia_type_string!(Array<'_, '_>, "Core.Array");
+
+unsafe impl<U: JuliaTypeString> JuliaTypeString for *mut U {
+    fn julia_type_string() -> String {
+        format!("Core.Ptr{{{}}}", U::julia_type_string())
+    }
+}
+
+unsafe impl<U: ValidField + JuliaTypeString> JuliaTypeString for TypedArray<'_, '_, U> {
+    fn julia_type_string() -> String {
+        format!("Core.Array{{{}}}", U::julia_type_string())
+    }
+}
+
+unsafe impl<'scope, 'data, W> JuliaTypeString for Ref<'scope, 'data, W>
+where
+    W: Wrapper<'scope, 'data> + JuliaTypeString,
+{
+    fn julia_type_string() -> String {
+        W::julia_type_string()
+    }
+}
+
+unsafe impl<'scope, 'data, W> JuliaTypeString for Option<Ref<'scope, 'data, W>>
+where
+    W: Wrapper<'scope, 'data> + JuliaTypeString,
+{
+    fn julia_type_string() -> String {
+        W::julia_type_string()
+    }
+}
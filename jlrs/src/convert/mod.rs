@@ -1,8 +1,16 @@
 //! Traits for converting data.
 
+#[cfg(feature = "bignum")]
+pub mod big_num;
+pub mod construct_type;
 pub mod into_jlrs_result;
 pub mod into_julia;
+#[cfg(not(all(target_os = "windows", feature = "lts")))]
+pub mod julia_expr;
+pub mod julia_struct;
 #[cfg(feature = "jlrs-ndarray")]
 pub mod ndarray;
 pub mod to_symbol;
+#[cfg(not(all(target_os = "windows", feature = "lts")))]
+pub mod type_expr;
 pub mod unbox;
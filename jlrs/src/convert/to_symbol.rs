@@ -1,12 +1,18 @@
 //! Convert strings to symbols.
 //!
 //! Many things in Julia are accessed with [`Symbol`]s, the [`ToSymbol`] trait allows for
-//! strings to be used instead.
+//! strings to be used instead. It's also implemented for references to a [`StaticSymbol`],
+//! which avoid converting the same string to a `Symbol` repeatedly.
+//!
+//! [`StaticSymbol`]: crate::wrappers::ptr::symbol::StaticSymbol
 
 use crate::{
     memory::target::Target,
     private::Private,
-    wrappers::ptr::{string::JuliaString, symbol::Symbol},
+    wrappers::ptr::{
+        string::JuliaString,
+        symbol::{StaticSymbol, Symbol},
+    },
 };
 
 /// Trait implemented by types that can be converted to a [`Symbol`].
@@ -24,6 +30,7 @@ pub trait ToSymbol: private::ToSymbolPriv {
 impl<T: AsRef<str>> ToSymbol for T {}
 impl ToSymbol for Symbol<'_> {}
 impl ToSymbol for JuliaString<'_> {}
+impl ToSymbol for &StaticSymbol {}
 
 pub(crate) mod private {
     use std::ptr::NonNull;
@@ -32,7 +39,11 @@ pub(crate) mod private {
 
     use crate::{
         private::Private,
-        wrappers::ptr::{private::WrapperPriv, string::JuliaString, symbol::Symbol},
+        wrappers::ptr::{
+            private::WrapperPriv,
+            string::JuliaString,
+            symbol::{StaticSymbol, Symbol},
+        },
     };
 
     pub trait ToSymbolPriv {
@@ -58,6 +69,13 @@ pub(crate) mod private {
         }
     }
 
+    impl ToSymbolPriv for &StaticSymbol {
+        #[inline]
+        unsafe fn to_symbol_priv<'symbol>(&self, _: Private) -> Symbol<'symbol> {
+            Symbol::wrap_non_null(self.get_unchecked().unwrap_non_null(Private), Private)
+        }
+    }
+
     impl ToSymbolPriv for Symbol<'_> {
         #[inline]
         unsafe fn to_symbol_priv<'symbol>(&self, _: Private) -> Symbol<'symbol> {
@@ -0,0 +1,498 @@
+//! Build Julia types from type expressions.
+//!
+//! Constructing a type like `Dict{Symbol, Vector{Union{Missing, Float64}}}` from Rust requires
+//! looking up every name in the right module and applying the parameters one by one. A
+//! [`TypeExpr`] describes such a type, it can be built with its constructors or parsed from a
+//! string that uses Julia's type syntax:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! # use jlrs::convert::type_expr::TypeExpr;
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         let parsed: TypeExpr = "Dict{Symbol, Vector{Union{Missing, Float64}}}".parse()?;
+//!         let built = TypeExpr::name("Dict").apply([
+//!             TypeExpr::name("Symbol"),
+//!             TypeExpr::name("Vector").apply([TypeExpr::union([
+//!                 TypeExpr::name("Missing"),
+//!                 TypeExpr::name("Float64"),
+//!             ])]),
+//!         ]);
+//!         assert_eq!(parsed, built);
+//!
+//!         let main = Module::main(&frame);
+//!         let ty = parsed.resolve(frame.as_extended_target(), main)?;
+//!         assert!(ty.is::<DataType>());
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+//!
+//! Names are resolved in the module that is provided when the expression is resolved, qualified
+//! names like `Base.Dict` are resolved by looking up the submodules first. Besides types, integers
+//! and symbols can be used as parameters, e.g. `Array{Float64, 2}`.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    iter::Peekable,
+    str::{CharIndices, FromStr},
+    sync::RwLock,
+};
+
+#[cfg(not(feature = "lts"))]
+use jl_sys::jl_vararg_t;
+use smallvec::SmallVec;
+
+use crate::{
+    call::Call,
+    convert::into_jlrs_result::IntoJlrsResult,
+    error::{JlrsError, JlrsResult, TypeError, CANNOT_DISPLAY_TYPE, CANNOT_DISPLAY_VALUE},
+    memory::target::{frame::GcFrame, ExtendedTarget, Target},
+    private::Private,
+    wrappers::ptr::{
+        datatype::DataType,
+        module::Module,
+        private::WrapperPriv,
+        symbol::Symbol,
+        union::Union,
+        union_all::UnionAll,
+        value::{Value, ValueData, MAX_SIZE},
+        Wrapper,
+    },
+};
+
+static TYPE_CACHE: RwLock<Option<HashMap<(usize, String), usize>>> = RwLock::new(None);
+
+/// A Julia type expression.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TypeExpr {
+    /// A name, qualified names like `Base.Dict` are split at the dots.
+    Name(Vec<String>),
+    /// A type with parameters, e.g. `Vector{Float64}`.
+    Apply(Box<TypeExpr>, Vec<TypeExpr>),
+    /// A union of types, e.g. `Union{Missing, Float64}`.
+    Union(Vec<TypeExpr>),
+    /// An integer parameter, e.g. the `2` in `Array{Float64, 2}`. It has the type `Int`, which is
+    /// `Int32` on 32-bit targets.
+    Int(isize),
+    /// A symbol parameter, e.g. `:a`.
+    Symbol(String),
+}
+
+impl TypeExpr {
+    /// A (qualified) name, e.g. `Float64` or `Base.Dict`.
+    pub fn name<N: AsRef<str>>(name: N) -> Self {
+        TypeExpr::Name(name.as_ref().split('.').map(String::from).collect())
+    }
+
+    /// An integer parameter.
+    pub fn int(value: isize) -> Self {
+        TypeExpr::Int(value)
+    }
+
+    /// A symbol parameter.
+    pub fn symbol<N: AsRef<str>>(name: N) -> Self {
+        TypeExpr::Symbol(name.as_ref().into())
+    }
+
+    /// A union of `types`.
+    pub fn union<I: IntoIterator<Item = TypeExpr>>(types: I) -> Self {
+        TypeExpr::Union(types.into_iter().collect())
+    }
+
+    /// Apply `params` to `self`.
+    pub fn apply<I: IntoIterator<Item = TypeExpr>>(self, params: I) -> Self {
+        TypeExpr::Apply(Box::new(self), params.into_iter().collect())
+    }
+
+    /// Parse a type expression that uses Julia's type syntax.
+    pub fn parse<S: AsRef<str>>(expr: S) -> JlrsResult<Self> {
+        let expr = expr.as_ref();
+        let mut parser = Parser {
+            expr,
+            chars: expr.char_indices().peekable(),
+        };
+
+        let parsed = parser.parse_expr()?;
+        parser.skip_whitespace();
+        if let Some((position, _)) = parser.chars.peek().copied() {
+            return Err(parser.error(position, "unexpected trailing characters"));
+        }
+
+        Ok(parsed)
+    }
+
+    /// Resolve the type this expression describes, names are looked up in `module`.
+    ///
+    /// An error is returned if a name doesn't exist, the result is not a type, or if too many
+    /// parameters are applied to a type. If Julia throws an exception while the parameters are
+    /// applied, it's converted to an error.
+    pub fn resolve<'target, T>(
+        &self,
+        target: ExtendedTarget<'target, '_, '_, T>,
+        module: Module,
+    ) -> JlrsResult<ValueData<'target, 'static, T>>
+    where
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let ty = self.resolve_type(&mut frame, module)?;
+            // Safety: the type is rooted until it has been returned
+            unsafe { Ok(output.data_from_ptr(ty.unwrap_non_null(Private), Private)) }
+        })
+    }
+
+    /// Resolve the type this expression describes and cache the result.
+    ///
+    /// This method behaves like [`TypeExpr::resolve`], except that the resolved type is stored in
+    /// a cache and kept alive for the remaining lifetime of the program. The next time the same
+    /// expression is resolved in the same module, the cached type is returned. The module is kept
+    /// alive as well. Cached types are not updated if a name they depend on is redefined. Types
+    /// are only cached if the `Jlrs` module has been loaded, which isn't the case if jlrs is only
+    /// used through `ccall`.
+    pub fn resolve_cached<'target, T>(
+        &self,
+        target: ExtendedTarget<'target, '_, '_, T>,
+        module: Module,
+    ) -> JlrsResult<ValueData<'target, 'static, T>>
+    where
+        T: Target<'target>,
+    {
+        let key = (module.unwrap(Private) as usize, self.to_string());
+        {
+            let cache = TYPE_CACHE.read().expect("Lock poisoned");
+            if let Some(ty) = cache.as_ref().and_then(|cache| cache.get(&key)) {
+                let (output, _) = target.split();
+                // Safety: cached types are rooted in Jlrs.rooted_types
+                unsafe {
+                    let ty = std::ptr::NonNull::new_unchecked(*ty as *mut _);
+                    return Ok(output.data_from_ptr(ty, Private));
+                }
+            }
+        }
+
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let ty = self.resolve_type(&mut frame, module)?;
+
+            // If the Jlrs module hasn't been loaded, e.g. because jlrs is only used through
+            // ccall, the type can't be rooted and isn't cached.
+            let rooted_types = Module::main(&frame)
+                .submodule(&frame, "Jlrs")
+                .and_then(|jlrs| jlrs.wrapper().global(&frame, "rooted_types"));
+
+            if let Ok(rooted_types) = rooted_types {
+                // Safety: pushing values to a Vector{Any} is safe. The module is rooted too
+                // because the cache is keyed on its address.
+                let rooted = unsafe {
+                    Module::base(&frame)
+                        .function(&frame, "push!")?
+                        .wrapper()
+                        .call3(&mut frame, rooted_types.wrapper(), module.as_value(), ty)
+                };
+
+                // Julia must not be called while the lock is held, a thread that's blocked on it
+                // can't reach a safepoint if the GC is triggered. Another thread might have
+                // cached this type in the meantime, that entry is kept.
+                if rooted.is_ok() {
+                    let ty = *TYPE_CACHE
+                        .write()
+                        .expect("Lock poisoned")
+                        .get_or_insert_with(HashMap::new)
+                        .entry(key)
+                        .or_insert(ty.unwrap(Private) as usize);
+
+                    // Safety: cached types are rooted in Jlrs.rooted_types
+                    unsafe {
+                        let ty = std::ptr::NonNull::new_unchecked(ty as *mut _);
+                        return Ok(output.data_from_ptr(ty, Private));
+                    }
+                }
+            }
+
+            // Safety: the type is rooted until it has been returned
+            unsafe { Ok(output.data_from_ptr(ty.unwrap_non_null(Private), Private)) }
+        })
+    }
+
+    fn resolve_type<'target>(
+        &self,
+        frame: &mut GcFrame<'target>,
+        module: Module,
+    ) -> JlrsResult<Value<'target, 'static>> {
+        let ty = self.resolve_value(frame, module)?;
+        if !ty.is_type() {
+            let value = ty.display_string_or(CANNOT_DISPLAY_VALUE);
+            Err(TypeError::NotAType { value })?
+        }
+
+        Ok(ty)
+    }
+
+    fn resolve_value<'target>(
+        &self,
+        frame: &mut GcFrame<'target>,
+        module: Module,
+    ) -> JlrsResult<Value<'target, 'static>> {
+        match self {
+            TypeExpr::Name(path) => {
+                let (name, submodules) = match path.split_last() {
+                    Some(split) => split,
+                    None => Err(TypeError::InvalidTypeExpr {
+                        expr: self.to_string(),
+                        position: 0,
+                        reason: "empty name".into(),
+                    })?,
+                };
+                let mut module = module;
+                for submodule in submodules {
+                    // Safety: modules are globally rooted
+                    module = unsafe { module.submodule(&*frame, submodule.as_str())?.wrapper() };
+                }
+
+                module.global(&mut *frame, name.as_str())
+            }
+            TypeExpr::Apply(base, params) => {
+                let base = base.resolve_value(frame, module)?;
+                let max = max_params(frame, base)?;
+                if params.len() > max {
+                    Err(TypeError::TooManyTypeParameters {
+                        ty: base.display_string_or(CANNOT_DISPLAY_TYPE),
+                        max,
+                        found: params.len(),
+                    })?
+                }
+
+                let mut values: SmallVec<[Value; MAX_SIZE]> = SmallVec::new();
+                for param in params {
+                    values.push(param.resolve_value(frame, module)?);
+                }
+
+                base.apply_type(&mut *frame, values).into_jlrs_result()
+            }
+            TypeExpr::Union(types) => {
+                let mut values: SmallVec<[Value; MAX_SIZE]> = SmallVec::new();
+                for ty in types {
+                    values.push(ty.resolve_type(frame, module)?);
+                }
+
+                Union::new(&mut *frame, values).into_jlrs_result()
+            }
+            TypeExpr::Int(value) => Ok(Value::new(&mut *frame, *value)),
+            TypeExpr::Symbol(name) => Ok(Symbol::new(&*frame, name).as_value()),
+        }
+    }
+}
+
+impl FromStr for TypeExpr {
+    type Err = Box<JlrsError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TypeExpr::parse(s)
+    }
+}
+
+impl Display for TypeExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeExpr::Name(path) => write!(f, "{}", path.join(".")),
+            TypeExpr::Apply(base, params) => {
+                write!(f, "{}{{", base)?;
+                write_list(f, params)?;
+                write!(f, "}}")
+            }
+            TypeExpr::Union(types) => {
+                write!(f, "Union{{")?;
+                write_list(f, types)?;
+                write!(f, "}}")
+            }
+            TypeExpr::Int(value) => write!(f, "{}", value),
+            TypeExpr::Symbol(name) => write!(f, ":{}", name),
+        }
+    }
+}
+
+fn write_list(f: &mut fmt::Formatter<'_>, exprs: &[TypeExpr]) -> fmt::Result {
+    for (i, expr) in exprs.iter().enumerate() {
+        if i != 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", expr)?;
+    }
+
+    Ok(())
+}
+
+// Returns the maximum number of parameters that can be applied to `base`.
+fn max_params(frame: &GcFrame, base: Value) -> JlrsResult<usize> {
+    if let Ok(mut ua) = base.cast::<UnionAll>() {
+        let mut n = 1;
+        while let Ok(body) = ua.body().cast::<UnionAll>() {
+            ua = body;
+            n += 1;
+        }
+
+        Ok(n)
+    } else if let Ok(dt) = base.cast::<DataType>() {
+        // A DataType has no free type parameters, except for `Tuple` and `Union` which accept
+        // any number of parameters.
+        let dt = dt.unwrap(Private);
+        if dt == DataType::anytuple_type(frame).unwrap(Private)
+            || dt == DataType::uniontype_type(frame).unwrap(Private)
+        {
+            Ok(usize::MAX)
+        } else {
+            Ok(0)
+        }
+    } else {
+        #[cfg(not(feature = "lts"))]
+        if base.datatype().unwrap(Private) == DataType::vararg_type(frame).unwrap(Private) {
+            // Since Julia 1.7 `Vararg` is neither a DataType nor a UnionAll, `T` and `N` can be
+            // applied until they have been set.
+            // Safety: the value is a Vararg
+            let vararg = unsafe { base.unwrap(Private).cast::<jl_vararg_t>().as_ref().unwrap() };
+            let n = if vararg.T.is_null() {
+                2
+            } else if vararg.N.is_null() {
+                1
+            } else {
+                0
+            };
+
+            return Ok(n);
+        }
+
+        let value = base.display_string_or(CANNOT_DISPLAY_VALUE);
+        Err(TypeError::NotAType { value })?
+    }
+}
+
+struct Parser<'a> {
+    expr: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn parse_expr(&mut self) -> JlrsResult<TypeExpr> {
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            Some((_, ':')) => {
+                self.chars.next();
+                let name = self.parse_identifier()?;
+                Ok(TypeExpr::Symbol(name))
+            }
+            Some((_, c)) if c == '-' || c.is_ascii_digit() => self.parse_int(),
+            Some(_) => {
+                let mut path = vec![self.parse_identifier()?];
+                while let Some((_, '.')) = self.chars.peek() {
+                    self.chars.next();
+                    path.push(self.parse_identifier()?);
+                }
+
+                self.skip_whitespace();
+                if let Some((_, '{')) = self.chars.peek() {
+                    let params = self.parse_params()?;
+                    if path.len() == 1 && path[0] == "Union" {
+                        Ok(TypeExpr::Union(params))
+                    } else {
+                        Ok(TypeExpr::Apply(Box::new(TypeExpr::Name(path)), params))
+                    }
+                } else {
+                    Ok(TypeExpr::Name(path))
+                }
+            }
+            None => Err(self.error(self.expr.len(), "expected a type")),
+        }
+    }
+
+    fn parse_params(&mut self) -> JlrsResult<Vec<TypeExpr>> {
+        // Skip the opening brace
+        self.chars.next();
+        let mut params = Vec::new();
+
+        self.skip_whitespace();
+        if let Some((_, '}')) = self.chars.peek() {
+            self.chars.next();
+            return Ok(params);
+        }
+
+        loop {
+            params.push(self.parse_expr()?);
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some((_, ',')) => continue,
+                Some((_, '}')) => return Ok(params),
+                Some((position, _)) => return Err(self.error(position, "expected `,` or `}`")),
+                None => return Err(self.error(self.expr.len(), "unclosed `{`")),
+            }
+        }
+    }
+
+    fn parse_identifier(&mut self) -> JlrsResult<String> {
+        let start = match self.chars.peek().copied() {
+            Some((position, c)) if c == '_' || c.is_alphabetic() => position,
+            Some((position, _)) => return Err(self.error(position, "expected a name")),
+            None => return Err(self.error(self.expr.len(), "expected a name")),
+        };
+
+        let mut end = start;
+        while let Some((position, c)) = self.chars.peek().copied() {
+            if c == '_' || c == '!' || c.is_alphanumeric() {
+                end = position + c.len_utf8();
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+
+        Ok(self.expr[start..end].into())
+    }
+
+    fn parse_int(&mut self) -> JlrsResult<TypeExpr> {
+        let (start, _) = self.chars.next().unwrap();
+        let mut end = start + 1;
+        while let Some((position, c)) = self.chars.peek().copied() {
+            if c.is_ascii_digit() {
+                end = position + 1;
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+
+        self.expr[start..end]
+            .parse()
+            .map(TypeExpr::Int)
+            .map_err(|_| self.error(start, "invalid integer"))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some((_, c)) = self.chars.peek() {
+            if c.is_whitespace() {
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn error(&self, position: usize, reason: &str) -> Box<JlrsError> {
+        TypeError::InvalidTypeExpr {
+            expr: self.expr.into(),
+            position,
+            reason: reason.into(),
+        }
+        .into()
+    }
+}
@@ -12,9 +12,9 @@
 //! should be UTF-8 encoded, but to account for the possibility that the contents are invalid the
 //! implementation of `Unbox` returns a `Result<String, Vec<u8>>`.
 //!
-//! Unlike [`IntoJulia`], the `Unbox` trait is not limited to bits-types. The only requirement is
-//! that the layout of the types in both languages match. Types that can be unboxed include those
-//! with pointer fields, type parameters, and bits unions. When wrappers are generated with
+//! Like [`IntoJulia`], the `Unbox` trait is not limited to bits-types. The only requirement is
+//! that the layout of the types in both languages match. Types that can be unboxed include
+//! those with pointer fields, type parameters, and bits unions. When wrappers are generated with
 //! JlrsReflect.jl [`Unbox`] is always derived.
 //!
 //! [`Cast`]: crate::convert::cast::Cast
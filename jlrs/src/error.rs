@@ -4,9 +4,12 @@ use std::error::Error as StdErr;
 
 use thiserror::Error;
 
-use crate::wrappers::ptr::{
-    array::dimensions::Dimensions,
-    value::{Value, ValueRef},
+use crate::{
+    layout::diagnostics::LayoutMismatch,
+    wrappers::ptr::{
+        array::dimensions::Dimensions,
+        value::{Value, ValueRef},
+    },
 };
 
 pub(crate) static CANNOT_DISPLAY_TYPE: &'static str = "<Cannot display type>";
@@ -43,6 +46,8 @@ pub enum RuntimeError {
 pub enum IOError {
     #[error("path does not exist: {path}")]
     NotFound { path: String },
+    #[error("path is not valid UTF-8: {path}")]
+    InvalidPath { path: String },
 }
 
 /// Type errors.
@@ -67,6 +72,40 @@ pub enum TypeError {
 
     #[error("{value_type} is immutable")]
     Immutable { value_type: String },
+    #[error("{ty} is not a concrete type")]
+    NotConcrete { ty: String },
+    #[error("no method can be compiled for the signature {signature}")]
+    NoSpecialization { signature: String },
+    #[error("no method matches the signature {signature}")]
+    NoMatchingMethod { signature: String },
+    #[error("{value} is not a type")]
+    NotAType { value: String },
+    #[error("{ty} has at most {max} type parameters, got {found}")]
+    TooManyTypeParameters {
+        ty: String,
+        max: usize,
+        found: usize,
+    },
+    #[error("invalid type expression `{expr}` at position {position}: {reason}")]
+    InvalidTypeExpr {
+        expr: String,
+        position: usize,
+        reason: String,
+    },
+    #[error("expected {expected} arguments, got {found}")]
+    ArgumentCountMismatch { expected: usize, found: usize },
+    #[error("argument {idx} is a {value_type}, expected a {expected_type}")]
+    ArgumentTypeMismatch {
+        idx: usize,
+        value_type: String,
+        expected_type: String,
+    },
+    #[error("layout of {rust_type} is invalid for {value_type}: {mismatch}")]
+    LayoutMismatch {
+        rust_type: String,
+        value_type: String,
+        mismatch: LayoutMismatch,
+    },
 }
 
 /// Array layout errors.
@@ -0,0 +1,220 @@
+//! Diagnose why a Rust type is not a valid layout for a Julia type.
+//!
+//! When [`ValidLayout::valid_layout`] returns `false` it's not immediately clear what part of
+//! the layout is incompatible. If `ValidLayout` is derived, [`ValidLayout::layout_mismatch`]
+//! compares the layout of the Rust type, i.e. its size, alignment, and the offset, size and type
+//! of every field, with the layout of the Julia type and returns the first difference that has
+//! been found as a [`LayoutMismatch`]. This information is included in the error that is
+//! returned by [`Value::unbox`] and [`FieldAccessor::access`].
+//!
+//! [`ValidLayout::valid_layout`]: crate::layout::valid_layout::ValidLayout::valid_layout
+//! [`ValidLayout::layout_mismatch`]: crate::layout::valid_layout::ValidLayout::layout_mismatch
+//! [`Value::unbox`]: crate::wrappers::ptr::value::Value::unbox
+//! [`FieldAccessor::access`]: crate::wrappers::ptr::value::FieldAccessor::access
+
+use std::fmt;
+
+use crate::{
+    error::CANNOT_DISPLAY_TYPE,
+    memory::target::unrooted::Unrooted,
+    wrappers::ptr::{datatype::DataType, value::Value, Wrapper},
+};
+
+/// The layout of a field of a Rust type.
+#[derive(Clone, Debug)]
+pub struct RustFieldLayout {
+    /// The name of the field.
+    pub name: &'static str,
+    /// The index of the corresponding field in Julia. This can be different from the index of
+    /// the field in Rust because a bits union is represented by three fields in Rust.
+    pub julia_index: usize,
+    /// The offset of the field in bytes.
+    pub offset: usize,
+    /// The size of the field in bytes.
+    pub size: usize,
+    /// The name of the type of the field.
+    pub type_name: &'static str,
+    /// Checks if the field type is compatible with a Julia field type, `None` for bits unions.
+    pub valid_field: Option<fn(Value) -> bool>,
+}
+
+/// The layout of a Rust type.
+#[derive(Clone, Debug)]
+pub struct RustLayout {
+    /// The size of the type in bytes.
+    pub size: usize,
+    /// The alignment of the type in bytes.
+    pub align: usize,
+    /// The layout of the fields that have a corresponding field in Julia.
+    pub fields: Vec<RustFieldLayout>,
+}
+
+impl RustLayout {
+    /// Compare this layout with the layout of the Julia type `ty`. Returns the first difference
+    /// that has been found, or `None` if the layouts are compatible.
+    pub fn compare(&self, ty: Value) -> Option<LayoutMismatch> {
+        let dt = match ty.cast::<DataType>() {
+            Ok(dt) if dt.is_concrete_type() => dt,
+            _ => {
+                return Some(LayoutMismatch::NotConcrete {
+                    ty: ty.display_string_or(CANNOT_DISPLAY_TYPE),
+                })
+            }
+        };
+
+        let n_fields = dt.n_fields() as usize;
+        let n_rust_fields = self
+            .fields
+            .iter()
+            .map(|f| f.julia_index + 1)
+            .max()
+            .unwrap_or(0);
+
+        if n_fields != n_rust_fields {
+            return Some(LayoutMismatch::FieldCount {
+                rust: n_rust_fields,
+                julia: n_fields,
+            });
+        }
+
+        let global = unsafe { Unrooted::new() };
+        for field in self.fields.iter() {
+            let idx = field.julia_index;
+            let julia_type = match dt.field_type(global, idx) {
+                Some(ty) => unsafe { ty.value() },
+                None => continue,
+            };
+
+            if let Some(valid_field) = field.valid_field {
+                if !valid_field(julia_type) {
+                    // Safety: idx is in bounds
+                    let is_pointer = unsafe { dt.is_pointer_field_unchecked(idx) };
+                    return Some(LayoutMismatch::FieldType {
+                        name: field.name,
+                        rust_type: field.type_name,
+                        julia_type: julia_type.display_string_or(CANNOT_DISPLAY_TYPE),
+                        is_pointer,
+                    });
+                }
+            }
+
+            // Safety: idx is in bounds
+            let julia_offset = unsafe { dt.field_offset_unchecked(idx) } as usize;
+            if field.offset != julia_offset {
+                return Some(LayoutMismatch::FieldOffset {
+                    name: field.name,
+                    rust: field.offset,
+                    julia: julia_offset,
+                });
+            }
+
+            // The size of a bits union includes the flag, which is stored in a separate field in
+            // Rust.
+            if field.valid_field.is_some() {
+                // Safety: idx is in bounds
+                let julia_size = unsafe { dt.field_size_unchecked(idx) } as usize;
+                if field.size != julia_size {
+                    return Some(LayoutMismatch::FieldSize {
+                        name: field.name,
+                        rust: field.size,
+                        julia: julia_size,
+                    });
+                }
+            }
+        }
+
+        let julia_size = dt.size() as usize;
+        if self.size != julia_size {
+            return Some(LayoutMismatch::Size {
+                rust: self.size,
+                julia: julia_size,
+            });
+        }
+
+        let julia_align = dt.layout().alignment() as usize;
+        if self.align != julia_align {
+            return Some(LayoutMismatch::Alignment {
+                rust: self.align,
+                julia: julia_align,
+            });
+        }
+
+        None
+    }
+}
+
+/// A difference between the layout of a Rust type and a Julia type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LayoutMismatch {
+    /// The Julia type is not a concrete `DataType`.
+    NotConcrete { ty: String },
+    /// The types have a different number of fields.
+    FieldCount { rust: usize, julia: usize },
+    /// The type of the Rust field is not compatible with the type of the Julia field.
+    FieldType {
+        name: &'static str,
+        rust_type: &'static str,
+        julia_type: String,
+        is_pointer: bool,
+    },
+    /// The field is stored at a different offset.
+    FieldOffset {
+        name: &'static str,
+        rust: usize,
+        julia: usize,
+    },
+    /// The field has a different size.
+    FieldSize {
+        name: &'static str,
+        rust: usize,
+        julia: usize,
+    },
+    /// The types have a different size.
+    Size { rust: usize, julia: usize },
+    /// The types have a different alignment.
+    Alignment { rust: usize, julia: usize },
+}
+
+impl fmt::Display for LayoutMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutMismatch::NotConcrete { ty } => write!(f, "{} is not a concrete DataType", ty),
+            LayoutMismatch::FieldCount { rust, julia } => {
+                write!(f, "expected {} fields, found {}", rust, julia)
+            }
+            LayoutMismatch::FieldType {
+                name,
+                rust_type,
+                julia_type,
+                is_pointer,
+            } => {
+                let storage = if *is_pointer {
+                    "as a pointer"
+                } else {
+                    "inline"
+                };
+                write!(
+                    f,
+                    "field {} has type {} in Rust, but {} (stored {}) in Julia",
+                    name, rust_type, julia_type, storage
+                )
+            }
+            LayoutMismatch::FieldOffset { name, rust, julia } => write!(
+                f,
+                "field {} is stored at offset {} in Rust, but at offset {} in Julia",
+                name, rust, julia
+            ),
+            LayoutMismatch::FieldSize { name, rust, julia } => write!(
+                f,
+                "field {} has size {} in Rust, but size {} in Julia",
+                name, rust, julia
+            ),
+            LayoutMismatch::Size { rust, julia } => {
+                write!(f, "size is {} in Rust, but {} in Julia", rust, julia)
+            }
+            LayoutMismatch::Alignment { rust, julia } => {
+                write!(f, "alignment is {} in Rust, but {} in Julia", rust, julia)
+            }
+        }
+    }
+}
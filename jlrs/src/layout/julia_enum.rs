@@ -0,0 +1,177 @@
+//! Enforce layout requirements of Julia enums.
+//!
+//! An enum defined with `@enum` in Julia is a primitive type whose supertype is
+//! `Base.Enums.Enum{T}`, where `T` is an integer type. Every variant is a global constant in the
+//! module that defines the enum. A Rust enum without data and with the same integer
+//! representation, e.g. `#[repr(i32)]`, has the same layout if every variant has a matching
+//! constant in Julia, and the Julia enum has no other variants.
+//!
+//! The `JuliaEnum` derive macro provided by jlrs-derive uses [`valid_enum_layout`] to implement
+//! `ValidLayout` for such enums. The layout must be validated with [`RegisterEnum::register`]
+//! before the enum can be unboxed. It can also map an enum whose variants carry data to an abstract
+//! Julia type, every variant is converted to and from an instance of a concrete subtype. Such
+//! enums implement [`IntoJuliaVariant`] instead of `IntoJulia`.
+
+use std::{
+    ptr::null_mut,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use jl_sys::jl_datatype_t;
+
+use crate::{
+    call::Call,
+    convert::{into_jlrs_result::IntoJlrsResult, unbox::Unbox},
+    error::{AccessError, JlrsResult, CANNOT_DISPLAY_TYPE},
+    layout::valid_layout::ValidField,
+    memory::target::{frame::GcFrame, Target},
+    private::Private,
+    wrappers::ptr::{
+        datatype::DataType,
+        module::Module,
+        private::WrapperPriv,
+        union_all::UnionAll,
+        value::{Value, ValueData},
+        Wrapper,
+    },
+};
+
+/// Convert an enum whose variants carry data to Julia.
+///
+/// Such an enum is mapped to an abstract Julia type, every variant is converted to an instance
+/// of the concrete subtype associated with that variant. Because there's no single concrete type
+/// associated with the enum it can't implement `IntoJulia`. This trait is implemented by the
+/// `JuliaEnum` derive macro.
+pub trait IntoJuliaVariant: Sized {
+    /// Convert `self` to an instance of the Julia type associated with its variant.
+    fn into_julia_variant<'target, T>(self, target: T) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>;
+}
+
+/// Validate the layout of a Julia enum without data.
+///
+/// Checking if a Julia enum has exactly the same variants as a Rust enum requires calling Julia
+/// functions, which can't be done safely while checking the layout of some value. Instead, the
+/// layout is validated once by calling [`RegisterEnum::register`], until then no Julia type is
+/// compatible with the Rust enum. This trait is implemented by the `JuliaEnum` derive macro for
+/// enums without data.
+pub trait RegisterEnum {
+    /// Validate the layout of the Julia enum associated with this type. An error is returned if
+    /// the layouts don't match.
+    fn register(frame: &mut GcFrame) -> JlrsResult<()>;
+}
+
+/// Cache for the `DataType` of a Julia enum whose layout has been validated.
+///
+/// The `JuliaEnum` derive macro creates a static cache for every enum without data, it's
+/// populated by [`RegisterEnum::register`].
+pub struct EnumLayoutCache(AtomicPtr<jl_datatype_t>);
+
+impl EnumLayoutCache {
+    /// Create a new, empty cache.
+    pub const fn new() -> Self {
+        EnumLayoutCache(AtomicPtr::new(null_mut()))
+    }
+}
+
+/// Returns `true` if `v` is the Julia enum stored in `cache`. No Julia functions are called.
+///
+/// This function is used by jlrs-derive to implement `ValidLayout` for enums, you shouldn't need
+/// to call it directly.
+pub fn valid_enum_layout(cache: &EnumLayoutCache, v: Value) -> bool {
+    let ty = cache.0.load(Ordering::Relaxed);
+    !ty.is_null() && v.unwrap(Private).cast() == ty
+}
+
+/// Checks if `ty` is a Julia enum with the base type `I` whose variants are exactly `variants`,
+/// which contains the name and value of each variant, and stores it in `cache` if it is. An
+/// error is returned otherwise.
+///
+/// This function is used by jlrs-derive to implement `RegisterEnum`, you shouldn't need to call
+/// it directly.
+pub fn register_enum<I>(
+    frame: &mut GcFrame,
+    cache: &EnumLayoutCache,
+    ty: Value,
+    variants: &[(&str, I)],
+) -> JlrsResult<()>
+where
+    I: ValidField + Unbox<Output = I> + Copy + PartialEq,
+{
+    let invalid = || -> JlrsResult<()> {
+        Err(AccessError::InvalidLayout {
+            value_type: ty.display_string_or(CANNOT_DISPLAY_TYPE),
+        }
+        .into())
+    };
+
+    let dt = match ty.cast::<DataType>() {
+        Ok(dt) => dt,
+        Err(_) => return invalid(),
+    };
+
+    if !dt.is_bits() || dt.size() as usize != std::mem::size_of::<I>() {
+        return invalid();
+    }
+
+    frame.scope(|mut frame| {
+        // Safety: only global constants are accessed, the results of the functions that are
+        // called are rooted.
+        unsafe {
+            let enums = Module::base(&frame).submodule(&frame, "Enums")?.wrapper();
+            let enum_base = enums
+                .global(&frame, "Enum")?
+                .value()
+                .cast::<UnionAll>()?
+                .base_type();
+
+            let super_type = dt.super_type();
+            if super_type.type_name().as_value() != enum_base.type_name().as_value() {
+                return invalid();
+            }
+
+            match super_type.parameter(&frame, 0) {
+                Some(param) if I::valid_field(param.value()) => (),
+                _ => return invalid(),
+            }
+
+            let module = dt.type_name().module();
+            for (name, value) in variants.iter().copied() {
+                let variant = match module.global(&frame, name) {
+                    Ok(variant) => variant.value(),
+                    Err(_) => return invalid(),
+                };
+
+                if variant.datatype().as_value() != dt.as_value() {
+                    return invalid();
+                }
+
+                if variant.data_ptr().as_ptr().cast::<I>().read() != value {
+                    return invalid();
+                }
+            }
+
+            let names = enums
+                .function(&frame, "namemap")?
+                .wrapper()
+                .call1(&mut frame, dt.as_value())
+                .into_jlrs_result()?;
+
+            let n = Module::base(&frame)
+                .function(&frame, "length")?
+                .wrapper()
+                .call1(&mut frame, names)
+                .into_jlrs_result()?
+                .unbox::<isize>()?;
+
+            if n as usize != variants.len() {
+                return invalid();
+            }
+
+            // The type is a global constant so it's never freed.
+            cache.0.store(dt.unwrap(Private), Ordering::Relaxed);
+            Ok(())
+        }
+    })
+}
@@ -1,7 +1,9 @@
 //! Traits for checking layout compatibility and enforcing layout requirements.
 
 pub mod bits_union;
+pub mod diagnostics;
 pub mod field_index;
 pub mod inline_layout;
+pub mod julia_enum;
 pub mod typecheck;
 pub mod valid_layout;
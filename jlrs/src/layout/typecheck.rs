@@ -31,6 +31,7 @@ use jl_sys::{
 
 use crate::{
     convert::into_julia::IntoJulia,
+    layout::diagnostics::LayoutMismatch,
     memory::target::unrooted::Unrooted,
     private::Private,
     wrappers::ptr::{
@@ -55,6 +56,12 @@ use crate::{
 pub unsafe trait Typecheck {
     /// Returns whether the property implied by `Self` holds true.
     fn typecheck(t: DataType) -> bool;
+
+    /// Returns the first difference between the layout of the implementor and the layout of
+    /// `t`, if the implementor has a known layout. The default implementation returns `None`.
+    fn layout_mismatch(_t: DataType) -> Option<LayoutMismatch> {
+        None
+    }
 }
 
 #[doc(hidden)]
@@ -12,6 +12,7 @@ use std::ffi::c_void;
 
 use crate::{
     convert::into_julia::IntoJulia,
+    layout::diagnostics::LayoutMismatch,
     wrappers::ptr::{datatype::DataType, value::Value},
 };
 
@@ -30,6 +31,13 @@ pub unsafe trait ValidLayout {
     /// argument is a `Value` to account for the fact that a field type can be a `Union`,
     /// `UnionAll` or `Union{}`.
     fn valid_layout(ty: Value) -> bool;
+
+    /// Returns the first difference between the layout of the implementor and the layout of
+    /// `ty`. Returns `None` if no difference has been found or if the layout of the implementor
+    /// is unknown, it's only known if this trait has been derived.
+    fn layout_mismatch(_ty: Value) -> Option<LayoutMismatch> {
+        None
+    }
 }
 
 #[doc(hidden)]
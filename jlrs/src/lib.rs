@@ -135,6 +135,26 @@
 //!
 //!   Adds support for working with Julia's `Float16` type from Rust using half's `f16` type.
 //!
+//! - `complex`
+//!
+//!   Adds support for working with Julia's `Complex` type from Rust using num-complex's `Complex`
+//!   type, including arrays of complex numbers.
+//!
+//! - `rational`
+//!
+//!   Adds the `Rational` inline wrapper for Julia's `Rational` type, which can be converted from
+//!   and to num-rational's `Ratio` type.
+//!
+//! - `bignum`
+//!
+//!   Adds conversions between Julia's `BigInt` and num-bigint's `BigInt`, and between Julia's
+//!   `BigFloat` and its decimal representation.
+//!
+//! - `dates`
+//!
+//!   Adds wrappers for `Date`, `DateTime`, `Time` and periods from the `Dates` standard library,
+//!   which can be converted from and to chrono's types.
+//!
 //! - `ccall`
 //!
 //!   Julia's `ccall` interface can be used to call functions written in Rust from Julia. No
@@ -152,6 +172,26 @@
 //!
 //!   This feature lets you plot data using the Pyplot package and Gtk 3 from Rust.
 //!
+//! - `reflection`
+//!
+//!   Enables the `reflection` module, which can be used to list the contents of a module and
+//!   the methods and docstrings of functions.
+//!
+//! - `logging`
+//!
+//!   Enables the `logging` module, which can be used to capture Julia's log messages and the
+//!   output written to `stdout` and `stderr`.
+//!
+//! - `jlrs-log`
+//!
+//!   Enables the `logging` feature and lets you forward Julia's log messages to the `log`
+//!   crate.
+//!
+//! - `jlrs-tracing`
+//!
+//!   Enables the `logging` feature and lets you forward Julia's log messages to the `tracing`
+//!   crate.
+//!
 //! - `i686`
 //!
 //!   Link with a 32-bit build of Julia on Linux.
@@ -633,7 +673,8 @@
 //!
 //! In order to map a struct in Rust to one in Julia you can derive [`Unbox`], [`Typecheck`] and
 //! [`ValidLayout`]. If the struct in Julia is immutable [`ValidField`] can also be derived,
-//! if it has no type parameters [`IntoJulia`].
+//! if it has no type parameters [`IntoJulia`]. Arrays can only be created from Rust data if the
+//! element type is a bits type, which is expressed by deriving `IsBits`.
 //!
 //! You normally shouldn't need to implement these structs or traits manually. The JlrsReflect
 //! package can generate correct Rust struct and automatically derive the supported traits for
@@ -641,6 +682,23 @@
 //! reason for this restriction is that the layout of such fields can be very different in a way
 //! that can't be easily represented.
 //!
+//! Rust enums can be mapped to Julia by deriving `JuliaEnum`. An enum without data that has an
+//! integer representation like `#[repr(i32)]` is mapped to an enum defined with `@enum`, every
+//! variant must match a variant of the Julia enum by name and value. This is checked by calling
+//! `RegisterEnum::register`, which must be done before the enum can be unboxed. The name of a
+//! variant can be changed with `#[jlrs(rename = "name")]`. An enum whose variants carry data is mapped to an
+//! abstract type, the concrete subtype of every variant must be set with
+//! `#[jlrs(julia_type = "Main.MyModule.MyVariant")]`. Such an enum implements
+//! `IntoJuliaVariant` instead of `IntoJulia`.
+//!
+//! If a type is defined in Rust first, `JuliaStruct` can be derived to generate the definition
+//! of a Julia struct with a matching layout. This definition can be evaluated in a module with
+//! [`JuliaStruct::define`].
+//!
+//! Structs with named fields can be converted to and from a `NamedTuple` with the same field
+//! names by deriving `IntoNamedTuple` and `FromNamedTuple`, which is useful for passing keyword
+//! arguments and options.
+//!
 //! These custom types can also be used when you call Rust from Julia with `ccall`.
 //!
 //! [their User Guide]: https://rust-lang.github.io/rust-bindgen/requirements.html
@@ -657,6 +715,7 @@
 //! [`CCall`]: crate::ccall::CCall
 //! [`CCall::uv_async_send`]: crate::ccall::CCall::uv_async_send
 //! [`Unrooted`]: crate::memory::target::unrooted::Unrooted
+//! [`JuliaStruct::define`]: crate::convert::julia_struct::JuliaStruct::define
 //! [`GcFrame`]: crate::memory::target::frame::GcFrame
 //! [`Module`]: crate::wrappers::ptr::module::Module
 //! [`Function`]: crate::wrappers::ptr::function::Function
@@ -694,7 +753,13 @@
 //! [`jlrs::prelude`]: crate::prelude
 #![forbid(rustdoc::broken_intra_doc_links)]
 
-#[cfg(any(feature = "sync-rt", feature = "async-rt", feature = "pyplot"))]
+#[cfg(any(
+    feature = "sync-rt",
+    feature = "async-rt",
+    feature = "pyplot",
+    feature = "logging",
+    feature = "reflection"
+))]
 macro_rules! init_fn {
     ($name:ident, $include:ident, $file:expr) => {
         pub(crate) static $include: &'static str = include_str!($file);
@@ -728,12 +793,16 @@ pub mod convert;
 pub mod error;
 pub mod info;
 pub mod layout;
+#[cfg(feature = "logging")]
+pub mod logging;
 pub mod memory;
 #[cfg(feature = "prelude")]
 pub mod prelude;
 pub(crate) mod private;
 #[cfg(feature = "pyplot")]
 pub mod pyplot;
+#[cfg(feature = "reflection")]
+pub mod reflection;
 #[cfg(any(feature = "sync-rt", feature = "async-rt"))]
 pub mod runtime;
 pub mod safety;
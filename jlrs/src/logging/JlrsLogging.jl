@@ -0,0 +1,70 @@
+module JlrsLogging
+import Base.CoreLogging: AbstractLogger, LogLevel, handle_message, shouldlog, min_enabled_level, catch_exceptions, global_logger
+
+struct RustIO <: IO
+    callback::Ptr{Cvoid}
+end
+
+function Base.unsafe_write(io::RustIO, p::Ptr{UInt8}, n::UInt)::Int
+    ccall(io.callback, Cvoid, (Ptr{UInt8}, Csize_t), p, n)
+    n
+end
+
+function Base.write(io::RustIO, b::UInt8)::Int
+    r = Ref(b)
+    GC.@preserve r unsafe_write(io, Base.unsafe_convert(Ptr{UInt8}, r), UInt(1))
+end
+
+Base.isopen(::RustIO) = true
+Base.flush(::RustIO) = nothing
+Base.displaysize(::RustIO) = (24, 80)
+
+struct RustLogger <: AbstractLogger
+    callback::Ptr{Cvoid}
+    min_level::LogLevel
+end
+
+shouldlog(::RustLogger, level, _module, group, id) = true
+min_enabled_level(logger::RustLogger) = logger.min_level
+catch_exceptions(::RustLogger) = true
+
+tostring(x) = x === nothing ? "" : string(x)
+
+function handle_message(logger::RustLogger, level::LogLevel, message, _module, group, id, filepath, line; kwargs...)
+    keys = String[string(k) for (k, _) in kwargs]
+    values = String[sprint(show, v) for (_, v) in kwargs]
+    line = line === nothing ? 0 : Int(line)
+    ccall(logger.callback, Cvoid, (Int32, Any, Any, Any, Any, Int, Any, Any),
+        level.level, tostring(message), tostring(_module), tostring(group), tostring(filepath), line, keys, values)
+    nothing
+end
+
+const original_stdout = Ref{IO}(stdout)
+const original_stderr = Ref{IO}(stderr)
+const original_logger = Ref{AbstractLogger}(global_logger())
+
+function setstdout(io::IO)::Nothing
+    Core.eval(Base, Expr(:(=), :stdout, io))
+    nothing
+end
+
+function setstderr(io::IO)::Nothing
+    Core.eval(Base, Expr(:(=), :stderr, io))
+    nothing
+end
+
+redirect_stdout(callback::Ptr{Cvoid}) = setstdout(RustIO(callback))
+redirect_stderr(callback::Ptr{Cvoid}) = setstderr(RustIO(callback))
+restore_stdout() = setstdout(original_stdout[])
+restore_stderr() = setstderr(original_stderr[])
+
+function redirect_logger(callback::Ptr{Cvoid}, min_level::Int32)::Nothing
+    global_logger(RustLogger(callback, LogLevel(min_level)))
+    nothing
+end
+
+function restore_logger()::Nothing
+    global_logger(original_logger[])
+    nothing
+end
+end
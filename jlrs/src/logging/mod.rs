@@ -0,0 +1,460 @@
+//! Redirect Julia's output and log messages to Rust.
+//!
+//! By default everything Julia prints to `stdout` and `stderr`, and every message logged with
+//! one of the logging macros like `@info`, ends up in the terminal. The functions in this module
+//! can be used to redirect this output to Rust instead. Output written to `Base.stdout` and
+//! `Base.stderr` can be redirected to a callback that receives the written bytes, while the
+//! global logger of `Base.CoreLogging` can be replaced with a logger that calls a callback with
+//! a [`LogRecord`] for every message.
+//!
+//! If the `jlrs-log` feature is enabled, this output can also be forwarded to the `log` crate
+//! with [`redirect_to_log`], if the `jlrs-tracing` feature is enabled it can be forwarded to
+//! the `tracing` crate with [`redirect_to_tracing`].
+//!
+//! Only output that is written to the `stdout` and `stderr` globals of the `Base` module is
+//! captured, data that is written directly to the underlying file descriptors by C code is not.
+//! Similarly, only messages handled by the global logger are captured, tasks that use a
+//! different logger with `with_logger` are unaffected.
+//!
+//! The callbacks are called from the thread that writes the output or logs the message, they
+//! must not call into Julia.
+
+#[cfg(any(feature = "jlrs-log", feature = "jlrs-tracing"))]
+use std::sync::Mutex;
+use std::{
+    ffi::c_void,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::RwLock,
+};
+
+use crate::{
+    call::Call,
+    convert::into_jlrs_result::IntoJlrsResult,
+    error::JlrsResult,
+    memory::target::frame::GcFrame,
+    wrappers::ptr::{array::Array, module::Module, string::JuliaString, value::Value},
+};
+
+init_fn!(init_jlrs_logging, JLRS_LOGGING_JL, "JlrsLogging.jl");
+
+type OutputCallback = Box<dyn Fn(&[u8]) + Send + Sync + 'static>;
+type LogCallback = Box<dyn Fn(&LogRecord) + Send + Sync + 'static>;
+
+static STDOUT_CALLBACK: RwLock<Option<OutputCallback>> = RwLock::new(None);
+static STDERR_CALLBACK: RwLock<Option<OutputCallback>> = RwLock::new(None);
+static LOG_CALLBACK: RwLock<Option<LogCallback>> = RwLock::new(None);
+
+/// The severity of a log message.
+///
+/// Julia uses an integer to represent the level of a message, the predefined levels are
+/// `Debug` (-1000), `Info` (0), `Warn` (1000) and `Error` (2000). Custom levels are mapped to
+/// the nearest predefined level below it, levels below `Debug` are mapped to `Trace`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Convert the integer representation of a Julia `LogLevel` to a `LogLevel`.
+    pub fn from_julia(level: i32) -> Self {
+        match level {
+            l if l < -1000 => LogLevel::Trace,
+            l if l < 0 => LogLevel::Debug,
+            l if l < 1000 => LogLevel::Info,
+            l if l < 2000 => LogLevel::Warn,
+            _ => LogLevel::Error,
+        }
+    }
+
+    /// Convert this level to the integer representation of a Julia `LogLevel`. `Trace` is
+    /// mapped to `BelowMinLevel`.
+    pub fn to_julia(self) -> i32 {
+        match self {
+            LogLevel::Trace => -1000001,
+            LogLevel::Debug => -1000,
+            LogLevel::Info => 0,
+            LogLevel::Warn => 1000,
+            LogLevel::Error => 2000,
+        }
+    }
+}
+
+/// A message that has been logged in Julia.
+#[derive(Debug)]
+pub struct LogRecord<'a> {
+    /// The level of the message.
+    pub level: LogLevel,
+    /// The integer representation of the Julia `LogLevel` of the message.
+    pub raw_level: i32,
+    /// The message.
+    pub message: &'a str,
+    /// The module the message was logged from.
+    pub module: &'a str,
+    /// The group of the message, by default this is the name of the file without extension.
+    pub group: &'a str,
+    /// The file the message was logged from.
+    pub file: &'a str,
+    /// The line the message was logged from.
+    pub line: usize,
+    /// The key-value pairs that were provided, the values are converted to strings with `show`.
+    pub key_values: Vec<(&'a str, &'a str)>,
+}
+
+/// This function must be called before this module can be used. The Julia code used by this
+/// module is evaluated if it hasn't been evaluated yet.
+pub fn init<'frame>(frame: &mut GcFrame<'frame>) {
+    if Module::main(&frame)
+        .submodule(&frame, "JlrsLogging")
+        .is_ok()
+    {
+        return;
+    }
+
+    unsafe { init_jlrs_logging(frame) };
+}
+
+/// Redirect everything that's written to `Base.stdout` to `callback`.
+///
+/// Safety: `callback` must not call into Julia.
+pub unsafe fn redirect_stdout<'frame, F>(frame: &mut GcFrame<'frame>, callback: F) -> JlrsResult<()>
+where
+    F: Fn(&[u8]) + Send + Sync + 'static,
+{
+    *STDOUT_CALLBACK.write().expect("Lock poisoned") = Some(Box::new(callback));
+    let func = write_stdout as unsafe extern "C" fn(*const u8, usize);
+    call_logging_fn(frame, "redirect_stdout", Some(func as *mut c_void))
+}
+
+/// Redirect everything that's written to `Base.stderr` to `callback`.
+///
+/// Safety: `callback` must not call into Julia.
+pub unsafe fn redirect_stderr<'frame, F>(frame: &mut GcFrame<'frame>, callback: F) -> JlrsResult<()>
+where
+    F: Fn(&[u8]) + Send + Sync + 'static,
+{
+    *STDERR_CALLBACK.write().expect("Lock poisoned") = Some(Box::new(callback));
+    let func = write_stderr as unsafe extern "C" fn(*const u8, usize);
+    call_logging_fn(frame, "redirect_stderr", Some(func as *mut c_void))
+}
+
+/// Replace the global logger with a logger that calls `callback` for every message with a
+/// level of at least `min_level`.
+///
+/// Safety: `callback` must not call into Julia.
+pub unsafe fn redirect_logger<'frame, F>(
+    frame: &mut GcFrame<'frame>,
+    min_level: LogLevel,
+    callback: F,
+) -> JlrsResult<()>
+where
+    F: Fn(&LogRecord) + Send + Sync + 'static,
+{
+    *LOG_CALLBACK.write().expect("Lock poisoned") = Some(Box::new(callback));
+
+    frame.scope(|mut frame| {
+        let func = handle_message as HandleMessage;
+        let func = Value::new(&mut frame, func as *mut c_void);
+        let min_level = Value::new(&mut frame, min_level.to_julia());
+
+        Module::main(&frame)
+            .submodule(&frame, "JlrsLogging")?
+            .wrapper()
+            .function(&frame, "redirect_logger")?
+            .wrapper()
+            .call2(&mut frame, func, min_level)
+            .into_jlrs_result()?;
+
+        Ok(())
+    })
+}
+
+/// Restore the original `Base.stdout`.
+pub unsafe fn restore_stdout<'frame>(frame: &mut GcFrame<'frame>) -> JlrsResult<()> {
+    call_logging_fn(frame, "restore_stdout", None)?;
+    *STDOUT_CALLBACK.write().expect("Lock poisoned") = None;
+    Ok(())
+}
+
+/// Restore the original `Base.stderr`.
+pub unsafe fn restore_stderr<'frame>(frame: &mut GcFrame<'frame>) -> JlrsResult<()> {
+    call_logging_fn(frame, "restore_stderr", None)?;
+    *STDERR_CALLBACK.write().expect("Lock poisoned") = None;
+    Ok(())
+}
+
+/// Restore the original global logger.
+pub unsafe fn restore_logger<'frame>(frame: &mut GcFrame<'frame>) -> JlrsResult<()> {
+    call_logging_fn(frame, "restore_logger", None)?;
+    *LOG_CALLBACK.write().expect("Lock poisoned") = None;
+    Ok(())
+}
+
+/// Forward `stdout`, `stderr` and all messages with a level of at least `min_level` to the
+/// `log` crate.
+///
+/// Output written to `stdout` and `stderr` is logged line by line at the `Info` and `Warn`
+/// level respectively, with the targets `julia::stdout` and `julia::stderr`. Log messages use
+/// the name of the module they were logged from as their target, key-value pairs are forwarded
+/// as the record's key-values.
+#[cfg(feature = "jlrs-log")]
+pub unsafe fn redirect_to_log<'frame>(
+    frame: &mut GcFrame<'frame>,
+    min_level: LogLevel,
+) -> JlrsResult<()> {
+    static STDOUT_BUFFER: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+    static STDERR_BUFFER: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+    redirect_stdout(frame, |data| {
+        for_each_line(
+            &STDOUT_BUFFER,
+            data,
+            |line| log::info!(target: "julia::stdout", "{}", line),
+        )
+    })?;
+
+    redirect_stderr(frame, |data| {
+        for_each_line(
+            &STDERR_BUFFER,
+            data,
+            |line| log::warn!(target: "julia::stderr", "{}", line),
+        )
+    })?;
+
+    redirect_logger(frame, min_level, |record| {
+        let level = match record.level {
+            LogLevel::Trace => log::Level::Trace,
+            LogLevel::Debug => log::Level::Debug,
+            LogLevel::Info => log::Level::Info,
+            LogLevel::Warn => log::Level::Warn,
+            LogLevel::Error => log::Level::Error,
+        };
+
+        if level > log::max_level() {
+            return;
+        }
+
+        let key_values = &record.key_values[..];
+        log::logger().log(
+            &log::Record::builder()
+                .level(level)
+                .target(record.module)
+                .module_path(Some(record.module))
+                .file(Some(record.file))
+                .line(Some(record.line as u32))
+                .key_values(&key_values)
+                .args(format_args!("{}", record.message))
+                .build(),
+        );
+    })
+}
+
+/// Forward `stdout`, `stderr` and all messages with a level of at least `min_level` to the
+/// `tracing` crate.
+///
+/// Output written to `stdout` and `stderr` is emitted line by line as events at the `INFO` and
+/// `WARN` level respectively, with the targets `julia::stdout` and `julia::stderr`. Log messages
+/// are emitted as events with the target `julia`, the module, file, line and key-value pairs are
+/// recorded as fields.
+#[cfg(feature = "jlrs-tracing")]
+pub unsafe fn redirect_to_tracing<'frame>(
+    frame: &mut GcFrame<'frame>,
+    min_level: LogLevel,
+) -> JlrsResult<()> {
+    static STDOUT_BUFFER: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+    static STDERR_BUFFER: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+    redirect_stdout(frame, |data| {
+        for_each_line(
+            &STDOUT_BUFFER,
+            data,
+            |line| tracing::info!(target: "julia::stdout", "{}", line),
+        )
+    })?;
+
+    redirect_stderr(frame, |data| {
+        for_each_line(
+            &STDERR_BUFFER,
+            data,
+            |line| tracing::warn!(target: "julia::stderr", "{}", line),
+        )
+    })?;
+
+    redirect_logger(frame, min_level, |record| {
+        let key_values = KeyValues(&record.key_values);
+
+        macro_rules! emit {
+            ($level:expr) => {
+                tracing::event!(
+                    target: "julia",
+                    $level,
+                    module = record.module,
+                    file = record.file,
+                    line = record.line,
+                    group = record.group,
+                    key_values = %key_values,
+                    "{}",
+                    record.message
+                )
+            };
+        }
+
+        match record.level {
+            LogLevel::Trace => emit!(tracing::Level::TRACE),
+            LogLevel::Debug => emit!(tracing::Level::DEBUG),
+            LogLevel::Info => emit!(tracing::Level::INFO),
+            LogLevel::Warn => emit!(tracing::Level::WARN),
+            LogLevel::Error => emit!(tracing::Level::ERROR),
+        }
+    })
+}
+
+type HandleMessage = unsafe extern "C" fn(
+    i32,
+    Value<'static, 'static>,
+    Value<'static, 'static>,
+    Value<'static, 'static>,
+    Value<'static, 'static>,
+    isize,
+    Value<'static, 'static>,
+    Value<'static, 'static>,
+);
+
+unsafe fn call_logging_fn<'frame>(
+    frame: &mut GcFrame<'frame>,
+    name: &str,
+    arg: Option<*mut c_void>,
+) -> JlrsResult<()> {
+    frame.scope(|mut frame| {
+        let func = Module::main(&frame)
+            .submodule(&frame, "JlrsLogging")?
+            .wrapper()
+            .function(&frame, name)?
+            .wrapper();
+
+        match arg {
+            Some(arg) => {
+                let arg = Value::new(&mut frame, arg);
+                func.call1(&mut frame, arg).into_jlrs_result()?;
+            }
+            None => {
+                func.call0(&mut frame).into_jlrs_result()?;
+            }
+        }
+
+        Ok(())
+    })
+}
+
+unsafe extern "C" fn write_stdout(data: *const u8, len: usize) {
+    write_output(&STDOUT_CALLBACK, data, len)
+}
+
+unsafe extern "C" fn write_stderr(data: *const u8, len: usize) {
+    write_output(&STDERR_CALLBACK, data, len)
+}
+
+unsafe fn write_output(callback: &RwLock<Option<OutputCallback>>, data: *const u8, len: usize) {
+    let data = if len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(data, len)
+    };
+
+    if let Ok(callback) = callback.read() {
+        if let Some(callback) = callback.as_ref() {
+            // Unwinding into Julia must be avoided
+            catch_unwind(AssertUnwindSafe(|| callback(data))).ok();
+        }
+    }
+}
+
+unsafe extern "C" fn handle_message(
+    level: i32,
+    message: Value<'static, 'static>,
+    module: Value<'static, 'static>,
+    group: Value<'static, 'static>,
+    file: Value<'static, 'static>,
+    line: isize,
+    keys: Value<'static, 'static>,
+    values: Value<'static, 'static>,
+) {
+    // Safety: these values are rooted by the caller and are only used until this function returns.
+    let keys = string_vector(keys);
+    let values = string_vector(values);
+
+    let record = LogRecord {
+        level: LogLevel::from_julia(level),
+        raw_level: level,
+        message: as_str(message),
+        module: as_str(module),
+        group: as_str(group),
+        file: as_str(file),
+        line: line.max(0) as usize,
+        key_values: keys.into_iter().zip(values).collect(),
+    };
+
+    if let Ok(callback) = LOG_CALLBACK.read() {
+        if let Some(callback) = callback.as_ref() {
+            // Unwinding into Julia must be avoided
+            catch_unwind(AssertUnwindSafe(|| callback(&record))).ok();
+        }
+    }
+}
+
+unsafe fn as_str<'a>(value: Value<'a, 'static>) -> &'a str {
+    value
+        .cast::<JuliaString>()
+        .and_then(|s| s.as_str())
+        .unwrap_or("<invalid string>")
+}
+
+unsafe fn string_vector<'a>(value: Value<'a, 'static>) -> Vec<&'a str> {
+    let arr = match value.cast::<Array>() {
+        Ok(arr) => arr,
+        Err(_) => return Vec::new(),
+    };
+
+    match arr.value_data() {
+        Ok(data) => data
+            .as_slice()
+            .iter()
+            .map(|s| match s {
+                Some(s) => as_str(s.value()),
+                None => "",
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+#[cfg(any(feature = "jlrs-log", feature = "jlrs-tracing"))]
+fn for_each_line<F: Fn(&str)>(buffer: &Mutex<Vec<u8>>, data: &[u8], f: F) {
+    let mut buffer = match buffer.lock() {
+        Ok(buffer) => buffer,
+        Err(_) => return,
+    };
+
+    buffer.extend_from_slice(data);
+    while let Some(idx) = buffer.iter().position(|b| *b == b'\n') {
+        let line: Vec<u8> = buffer.drain(..=idx).collect();
+        f(String::from_utf8_lossy(&line[..idx]).trim_end_matches('\r'));
+    }
+}
+
+#[cfg(feature = "jlrs-tracing")]
+struct KeyValues<'a, 'b>(&'a [(&'b str, &'b str)]);
+
+#[cfg(feature = "jlrs-tracing")]
+impl std::fmt::Display for KeyValues<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (key, value) in self.0 {
+            write!(f, " {}={}", key, value)?;
+        }
+
+        Ok(())
+    }
+}
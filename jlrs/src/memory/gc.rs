@@ -1,7 +1,26 @@
 //! Manage the garbage collector.
+//!
+//! Besides the methods of the [`Gc`] trait, which can be used to control the GC, this module
+//! provides access to statistics about the GC with [`Gc::gc_stats`] and lets you register
+//! callbacks that are called when a collection starts and ends with [`Gc::add_gc_callback`].
+//!
+//! Memory that is allocated by Rust but kept alive by Julia data is invisible to the GC unless
+//! it's reported with [`report_external_alloc`] and [`report_external_free`].
 
-use std::ffi::c_void;
+use std::{
+    ffi::c_void,
+    os::raw::c_int,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+        Mutex,
+    },
+    time::Duration,
+};
 
+#[cfg(any(feature = "beta", feature = "nightly"))]
+use jl_sys::jl_gc_set_max_memory;
 use jl_sys::{
     jl_gc_collect,
     jl_gc_collection_t,
@@ -10,17 +29,29 @@ use jl_sys::{
     jl_gc_mark_queue_obj,
     jl_gc_mark_queue_objarray,
     jl_gc_safepoint,
+    jl_gc_set_cb_post_gc,
+    jl_gc_set_cb_pre_gc,
     jl_gc_wb,
+    jlrs_gc_count_allocd,
+    jlrs_gc_count_freed,
 };
 
-use super::{target::Target, PTls};
+use super::{
+    target::{unrooted::Unrooted, Target},
+    PTls,
+};
 #[cfg(feature = "sync-rt")]
 use crate::runtime::sync_rt::Julia;
-#[cfg(not(feature = "lts"))]
-use crate::{call::Call, wrappers::ptr::module::Module};
 use crate::{
+    call::Call,
+    error::{JlrsError, JlrsResult, CANNOT_DISPLAY_VALUE},
     private::Private,
-    wrappers::ptr::{private::WrapperPriv, value::Value},
+    wrappers::ptr::{
+        module::{base_function, Module},
+        private::WrapperPriv,
+        value::{Value, ValueRef},
+        Wrapper,
+    },
 };
 
 /// The different collection modes.
@@ -31,11 +62,75 @@ pub enum GcCollection {
     Incremental = 2,
 }
 
+/// A GC event that is passed to the callbacks registered with [`Gc::add_gc_callback`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GcEvent {
+    /// A collection is about to start.
+    Start {
+        /// `true` if this is a full collection.
+        full: bool,
+    },
+    /// A collection has ended.
+    End {
+        /// `true` if this was a full collection.
+        full: bool,
+    },
+}
+
+/// A handle to a callback registered with [`Gc::add_gc_callback`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct GcCallbackHandle(usize);
+
+/// Statistics about the GC, as returned by `Base.gc_num()`.
+///
+/// The allocation counters are reset after every collection, the other counters are cumulative.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct GcStats {
+    /// The number of bytes allocated since the last collection.
+    pub allocd: i64,
+    /// The number of bytes freed since the last collection.
+    pub freed: i64,
+    /// The number of calls to `malloc` since the last collection.
+    pub malloc: i64,
+    /// The number of calls to `realloc` since the last collection.
+    pub realloc: i64,
+    /// The number of allocations in the pool allocator since the last collection.
+    pub poolalloc: i64,
+    /// The number of big allocations since the last collection.
+    pub bigalloc: i64,
+    /// The number of calls to `free` since the last collection.
+    pub freecall: i64,
+    /// The total number of bytes allocated before the last collection.
+    pub total_allocd: i64,
+    /// The total number of bytes allocated, including the bytes allocated since the last
+    /// collection.
+    pub total_bytes: i64,
+    /// The number of bytes that were live after the last collection.
+    pub live_bytes: i64,
+    /// The total number of collections.
+    pub collections: i64,
+    /// The number of full collections.
+    pub full_collections: i64,
+    /// The total time spent in the GC.
+    pub total_time: Duration,
+    /// The longest pause caused by a collection. This is always zero if the `lts` feature is
+    /// enabled.
+    pub max_pause: Duration,
+}
+
+impl GcStats {
+    /// Returns the number of incremental collections.
+    pub fn incremental_collections(&self) -> i64 {
+        self.collections - self.full_collections
+    }
+}
+
 /// Manage the GC.
 ///
 /// This trait provides several methods that can be used to enable or disable the GC, force a
-/// collection, insert a safepoint, and to enable and disable GC logging. It's implemented for
-/// [`Julia`] and all implementations of [`Target`]
+/// collection, insert a safepoint, to enable and disable GC logging, read statistics about the
+/// GC, and to register callbacks for GC events. It's implemented for [`Julia`] and all
+/// implementations of [`Target`]
 pub trait Gc: private::GcPriv {
     /// Enable or disable the GC.
     fn enable_gc(&self, on: bool) -> bool {
@@ -99,6 +194,114 @@ pub trait Gc: private::GcPriv {
             jl_gc_safepoint();
         }
     }
+
+    /// Returns the current statistics of the GC.
+    fn gc_stats(&self) -> JlrsResult<GcStats> {
+        let global = unsafe { Unrooted::new() };
+
+        // Safety: these functions only read the statistics. GC_Num is an isbits type, its fields
+        // are read before any other function is called.
+        unsafe {
+            let live_bytes = base_function(&global, "gc_live_bytes")?
+                .call0(&global)
+                .map_err(exception)?
+                .value()
+                .unbox::<i64>()?;
+
+            let num = base_function(&global, "gc_num")?
+                .call0(&global)
+                .map_err(exception)?
+                .value();
+
+            // max_pause is not available in Julia 1.6
+            #[cfg(feature = "lts")]
+            let max_pause = 0;
+            #[cfg(not(feature = "lts"))]
+            let max_pause = int_field(num, "max_pause")?;
+
+            let mut stats = GcStats {
+                allocd: int_field(num, "allocd")?,
+                freed: int_field(num, "freed")?,
+                malloc: int_field(num, "malloc")?,
+                realloc: int_field(num, "realloc")?,
+                poolalloc: int_field(num, "poolalloc")?,
+                bigalloc: int_field(num, "bigalloc")?,
+                freecall: int_field(num, "freecall")?,
+                total_allocd: int_field(num, "total_allocd")?,
+                total_bytes: 0,
+                live_bytes,
+                collections: int_field(num, "pause")?,
+                full_collections: int_field(num, "full_sweep")?,
+                total_time: Duration::from_nanos(int_field(num, "total_time")? as u64),
+                max_pause: Duration::from_nanos(max_pause as u64),
+            };
+
+            stats.total_bytes = base_function(&global, "gc_total_bytes")?
+                .call1(&global, num)
+                .map_err(exception)?
+                .value()
+                .unbox::<i64>()?;
+
+            Ok(stats)
+        }
+    }
+
+    /// Set a soft limit on the amount of memory Julia uses, the GC collects more aggressively
+    /// when this limit is approached. This is equivalent to starting Julia with
+    /// `--heap-size-hint`.
+    ///
+    /// This method is only available if the `beta` or `nightly` feature is enabled, older
+    /// versions of Julia don't support it.
+    #[cfg(any(feature = "beta", feature = "nightly"))]
+    fn set_gc_memory_limit(&self, bytes: u64) {
+        // Safety: this function only updates the limit.
+        unsafe { jl_gc_set_max_memory(bytes) }
+    }
+
+    /// Register a callback that is called whenever a collection starts or ends. The returned
+    /// handle can be used to remove the callback again with [`Gc::remove_gc_callback`].
+    ///
+    /// The callback is called by the thread that triggered the collection while all other
+    /// threads are paused. It must not call into Julia, allocate Julia data, or add or remove GC
+    /// callbacks. Panics are caught and ignored.
+    fn add_gc_callback<F>(&self, callback: F) -> GcCallbackHandle
+    where
+        F: Fn(GcEvent) + Send + Sync + 'static,
+        Self: Sized,
+    {
+        let id = NEXT_GC_CALLBACK_ID.fetch_add(1, Ordering::Relaxed);
+        let mut callbacks = GC_CALLBACKS.lock().expect("GC callbacks poisoned");
+
+        if callbacks.is_empty() {
+            // Safety: the callbacks are valid function pointers that don't call into Julia.
+            unsafe {
+                jl_gc_set_cb_pre_gc(Some(pre_gc_callback), 1);
+                jl_gc_set_cb_post_gc(Some(post_gc_callback), 1);
+            }
+        }
+
+        callbacks.push((id, Arc::new(callback)));
+        GcCallbackHandle(id)
+    }
+
+    /// Remove a callback that was registered with [`Gc::add_gc_callback`]. Returns `false` if
+    /// the callback has already been removed.
+    fn remove_gc_callback(&self, handle: GcCallbackHandle) -> bool {
+        let mut callbacks = GC_CALLBACKS.lock().expect("GC callbacks poisoned");
+        let n_callbacks = callbacks.len();
+        callbacks.retain(|(id, _)| *id != handle.0);
+        let removed = callbacks.len() != n_callbacks;
+
+        if removed && callbacks.is_empty() {
+            // Safety: the callbacks were registered when the first callback was added.
+            unsafe {
+                jl_gc_set_cb_pre_gc(Some(pre_gc_callback), 0);
+                jl_gc_set_cb_post_gc(Some(post_gc_callback), 0);
+            }
+        }
+
+        removed
+    }
 }
 
 /// Mark `obj`, returns `true` if `obj` points to young data.
@@ -150,6 +353,83 @@ pub unsafe fn write_barrier<T>(data: &mut T, child: Value) {
     jl_gc_wb(data as *mut _ as *mut _, child.unwrap(Private))
 }
 
+/// Report that `bytes` bytes of memory have been allocated outside of Julia's GC, and are kept
+/// alive by Julia data.
+///
+/// The GC only takes memory it has allocated itself into account when deciding whether it
+/// should collect garbage. If Julia data owns a large amount of memory that has been allocated
+/// by Rust, e.g. a foreign value that contains a `Vec`, collections might not happen often enough
+/// under memory pressure. Reporting this memory avoids this problem. Every call to this function
+/// should eventually be matched by a call to [`report_external_free`] with the same size.
+///
+/// [`ForeignType::external_size`] can be implemented instead if the memory is owned by a foreign
+/// value. Arrays created with [`Array::from_vec`] account for their data automatically.
+///
+/// Only the allocation counters of the current thread are updated, the reported memory is taken
+/// into account the next time Julia allocates data and checks whether it should collect garbage.
+///
+/// Safety: this function must be called from a thread known to Julia.
+///
+/// [`ForeignType::external_size`]: crate::wrappers::foreign::ForeignType::external_size
+/// [`Array::from_vec`]: crate::wrappers::ptr::array::Array::from_vec
+pub unsafe fn report_external_alloc(bytes: usize) {
+    jlrs_gc_count_allocd(bytes)
+}
+
+/// Report that `bytes` bytes of memory previously reported with [`report_external_alloc`] have
+/// been freed.
+///
+/// This function only updates the counters of the current thread, it can be called while the GC
+/// is running, e.g. from the sweep function of a foreign type.
+///
+/// Safety: this function must be called from a thread known to Julia, or by the GC.
+pub unsafe fn report_external_free(bytes: usize) {
+    jlrs_gc_count_freed(bytes)
+}
+
+// Converts an exception thrown by a function in Base to an error.
+fn exception(exc: ValueRef) -> Box<JlrsError> {
+    // Safety: the exception is only used to create the error message.
+    let msg = unsafe { exc.value().error_string_or(CANNOT_DISPLAY_VALUE) };
+    Box::new(JlrsError::exception(msg))
+}
+
+// Reads the integer field `name` of a `GC_Num`, the types of its fields depend on the version of
+// Julia.
+fn int_field(num: Value, name: &str) -> JlrsResult<i64> {
+    let field = || num.field_accessor().field(name);
+    if let Ok(n) = field()?.access::<i64>() {
+        Ok(n)
+    } else if let Ok(n) = field()?.access::<u64>() {
+        Ok(n as i64)
+    } else {
+        Ok(field()?.access::<i32>()? as i64)
+    }
+}
+
+type GcCallback = Arc<dyn Fn(GcEvent) + Send + Sync>;
+
+static GC_CALLBACKS: Mutex<Vec<(usize, GcCallback)>> = Mutex::new(Vec::new());
+static NEXT_GC_CALLBACK_ID: AtomicUsize = AtomicUsize::new(0);
+
+unsafe extern "C" fn pre_gc_callback(full: c_int) {
+    call_gc_callbacks(GcEvent::Start { full: full != 0 })
+}
+
+unsafe extern "C" fn post_gc_callback(full: c_int) {
+    call_gc_callbacks(GcEvent::End { full: full != 0 })
+}
+
+fn call_gc_callbacks(event: GcEvent) {
+    // The lock is only held by threads in a GC-unsafe state, a collection can't start until
+    // they've released it so this never blocks.
+    if let Ok(callbacks) = GC_CALLBACKS.lock() {
+        for (_, callback) in callbacks.iter() {
+            let _ = catch_unwind(AssertUnwindSafe(|| callback(event)));
+        }
+    }
+}
+
 #[cfg(feature = "sync-rt")]
 impl Gc for Julia<'_> {}
 impl<'frame, T: Target<'frame>> Gc for T {}
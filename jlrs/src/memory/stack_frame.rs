@@ -10,7 +10,7 @@ use std::{
 #[cfg(not(feature = "lts"))]
 use jl_sys::{jl_get_current_task, jl_task_t};
 
-use super::context::stack::Stack;
+use super::{context::stack::Stack, target::frame::GcFrame};
 use crate::{
     private::Private,
     wrappers::ptr::{private::WrapperPriv, value::Value},
@@ -159,3 +159,21 @@ impl<'scope, 'inner, const N: usize> JlrsStackFrame<'scope, 'inner, N> {
         false
     }
 }
+
+// Pushes a new frame to the GC stack of the current task, calls `func` with a `GcFrame` that
+// roots its data in that frame, and pops the frame before returning. Unlike `CCall::new` this
+// can be used more than once during a `ccall`, and from any thread that is known to Julia.
+//
+// Safety: the current thread must be known to Julia.
+pub(crate) unsafe fn with_base_frame<T, F>(func: F) -> T
+where
+    for<'scope> F: FnOnce(GcFrame<'scope>) -> T,
+{
+    let mut raw = StackFrame::new();
+    let mut pinned = raw.pin();
+    let stack = pinned.stack_frame().sync_stack();
+    let (owner, frame) = GcFrame::base(stack);
+    let ret = func(frame);
+    std::mem::drop(owner);
+    ret
+}
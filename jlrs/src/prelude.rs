@@ -8,7 +8,7 @@ pub use jlrs_derive::*;
 #[cfg(feature = "ccall")]
 pub use crate::ccall::CCall;
 #[cfg(feature = "pyplot")]
-pub use crate::pyplot::{AccessPlotsModule, PyPlot};
+pub use crate::pyplot::{headless::HeadlessPlot, AccessPlotsModule, PyPlot};
 #[cfg(feature = "async-std-rt")]
 pub use crate::runtime::async_rt::async_std_rt::*;
 #[cfg(feature = "tokio-rt")]
@@ -0,0 +1,36 @@
+module JlrsHeadlessPlot
+# Render with GR without opening a window
+ENV["GKSwstype"] = "nul"
+
+using Plots
+gr()
+
+default(show=false)
+
+const formats = Dict(
+    :png => MIME("image/png"),
+    :svg => MIME("image/svg+xml"),
+    :pdf => MIME("application/pdf"),
+)
+
+headlessplot(plotfn::Function, args...; kwargs...)::Plots.Plot = plotfn(args...; kwargs...)
+
+function updateplot(plt::Plots.Plot, plotfn::Function, args...; kwargs...)::Plots.Plot
+    newplot = plotfn(plt, args...; kwargs...)
+    newplot isa Plots.Plot ? newplot : plt
+end
+
+function saveplot(plt::Plots.Plot, path::String, format::Symbol)::Nothing
+    open(path, "w") do io
+        show(io, formats[format], plt)
+    end
+
+    nothing
+end
+
+function plotbytes(plt::Plots.Plot, format::Symbol)::Vector{UInt8}
+    io = IOBuffer()
+    show(io, formats[format], plt)
+    take!(io)
+end
+end
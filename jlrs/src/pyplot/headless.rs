@@ -0,0 +1,305 @@
+//! Render plots without a GUI.
+//!
+//! [`PyPlot`] needs a window to show its plots in, which isn't available on a headless server.
+//! The [`HeadlessPlot`] in this module renders plots created with Plots.jl to PNG, SVG or PDF
+//! with the GR backend instead, the result can be written to a file or returned as bytes.
+//!
+//! In order to use this module Plots.jl must have been installed. The environment variable
+//! `GKSwstype` is set to `nul` when this module is initialized, which prevents GR from opening
+//! a window. Because the GR backend is activated globally, headless and interactive plots should
+//! not be mixed.
+//!
+//! Example:
+//!
+//! ```no_run
+//! # use jlrs::prelude::*;
+//! # use jlrs::pyplot::headless::{HeadlessPlot, PlotFormat};
+//! # use jlrs::util::test::JULIA;
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| unsafe {
+//!         HeadlessPlot::init(&mut frame);
+//!
+//!         let plot_fn = Module::plots(&frame).function(&frame, "plot")?.wrapper();
+//!         let data = Array::from_vec(frame.as_extended_target(), vec![1.0f64, 4.0, 9.0], 3)?
+//!             .into_jlrs_result()?;
+//!         let title = JuliaString::new(&mut frame, "Squares");
+//!         let keywords = named_tuple!(frame.as_extended_target(), "title" => title.as_value());
+//!
+//!         let plot = HeadlessPlot::new_with_keywords(
+//!             &mut frame,
+//!             plot_fn,
+//!             [data.as_value()],
+//!             keywords,
+//!         )?;
+//!
+//!         plot.save(&mut frame, "squares.svg", PlotFormat::Svg)?;
+//!         let png = plot.to_bytes(&mut frame, PlotFormat::Png)?;
+//!         assert!(png.starts_with(b"\x89PNG"));
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+//!
+//! [`PyPlot`]: crate::pyplot::PyPlot
+
+use std::path::Path;
+
+use smallvec::SmallVec;
+
+use crate::{
+    call::{Call, ProvideKeywords},
+    convert::into_jlrs_result::IntoJlrsResult,
+    error::{IOError, JlrsResult},
+    memory::target::frame::GcFrame,
+    wrappers::ptr::{
+        array::Array,
+        function::Function,
+        module::Module,
+        string::JuliaString,
+        symbol::Symbol,
+        value::{Value, MAX_SIZE},
+        Wrapper,
+    },
+};
+
+init_fn!(
+    init_jlrs_headless_plot,
+    JLRS_HEADLESS_PLOT_JL,
+    "JlrsHeadlessPlot.jl"
+);
+
+/// The formats a [`HeadlessPlot`] can be rendered to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlotFormat {
+    /// A PNG image, `image/png`.
+    Png,
+    /// An SVG image, `image/svg+xml`.
+    Svg,
+    /// A PDF document, `application/pdf`.
+    Pdf,
+}
+
+impl PlotFormat {
+    /// Returns the file extension associated with this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            PlotFormat::Png => "png",
+            PlotFormat::Svg => "svg",
+            PlotFormat::Pdf => "pdf",
+        }
+    }
+
+    /// Returns the MIME type associated with this format.
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            PlotFormat::Png => "image/png",
+            PlotFormat::Svg => "image/svg+xml",
+            PlotFormat::Pdf => "application/pdf",
+        }
+    }
+}
+
+/// A plot that is rendered without a GUI.
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct HeadlessPlot<'scope>(Value<'scope, 'static>);
+
+impl<'scope> HeadlessPlot<'scope> {
+    /// This metod must be called before this module can be used.
+    pub fn init<'frame>(frame: &mut GcFrame<'frame>) {
+        if Module::main(&frame)
+            .submodule(&frame, "JlrsHeadlessPlot")
+            .is_ok()
+        {
+            return;
+        }
+        unsafe { init_jlrs_headless_plot(frame) };
+    }
+
+    /// Create a new plot by calling `plot_fn(args...)`. `plot_fn` must be a plotting function
+    /// from the Plots.jl package, such as `plot` or `hexbin`.
+    pub unsafe fn new<'value, V>(
+        frame: &mut GcFrame<'scope>,
+        plot_fn: Function<'_, 'static>,
+        args: V,
+    ) -> JlrsResult<Self>
+    where
+        V: AsRef<[Value<'value, 'static>]>,
+    {
+        let args = args.as_ref();
+        let mut vals: SmallVec<[Value; MAX_SIZE]> = SmallVec::with_capacity(1 + args.len());
+        vals.push(plot_fn.as_value());
+
+        for arg in args.iter().copied() {
+            vals.push(arg);
+        }
+
+        let plt = headless_function(frame, "headlessplot")?
+            .call(frame, vals)
+            .into_jlrs_result()?;
+
+        Ok(HeadlessPlot(plt))
+    }
+
+    /// Create a new plot by calling `plot_fn(args...; keywords...)`. `plot_fn` must be a plotting
+    /// function from the Plots.jl package, such as `plot` or `hexbin`.
+    pub unsafe fn new_with_keywords<'value, V>(
+        frame: &mut GcFrame<'scope>,
+        plot_fn: Function<'_, 'static>,
+        args: V,
+        keywords: Value<'_, 'static>,
+    ) -> JlrsResult<Self>
+    where
+        V: AsRef<[Value<'value, 'static>]>,
+    {
+        let args = args.as_ref();
+        let mut vals: SmallVec<[Value; MAX_SIZE]> = SmallVec::with_capacity(1 + args.len());
+        vals.push(plot_fn.as_value());
+
+        for arg in args.iter().copied() {
+            vals.push(arg);
+        }
+
+        let plt = headless_function(frame, "headlessplot")?
+            .provide_keywords(keywords)?
+            .call(frame, vals)
+            .into_jlrs_result()?;
+
+        Ok(HeadlessPlot(plt))
+    }
+
+    /// Update this plot by calling `plot_fn(<plot associated with self>, args...)`. If `plot_fn`
+    /// returns a new plot, like `plot` does, that plot is returned. Otherwise the plot is
+    /// assumed to have been mutated, like `plot!` does, and `self` is returned.
+    pub unsafe fn update<'value, V>(
+        self,
+        frame: &mut GcFrame<'scope>,
+        plot_fn: Function<'_, 'static>,
+        args: V,
+    ) -> JlrsResult<Self>
+    where
+        V: AsRef<[Value<'value, 'static>]>,
+    {
+        let args = args.as_ref();
+        let mut vals: SmallVec<[Value; MAX_SIZE]> = SmallVec::with_capacity(2 + args.len());
+        vals.push(self.0);
+        vals.push(plot_fn.as_value());
+
+        for arg in args.iter().copied() {
+            vals.push(arg);
+        }
+
+        let plt = headless_function(frame, "updateplot")?
+            .call(frame, vals)
+            .into_jlrs_result()?;
+
+        Ok(HeadlessPlot(plt))
+    }
+
+    /// Update this plot by calling `plot_fn(<plot associated with self>, args...; keywords...)`.
+    /// If `plot_fn` returns a new plot, like `plot` does, that plot is returned. Otherwise the
+    /// plot is assumed to have been mutated, like `plot!` does, and `self` is returned.
+    pub unsafe fn update_with_keywords<'value, V>(
+        self,
+        frame: &mut GcFrame<'scope>,
+        plot_fn: Function<'_, 'static>,
+        args: V,
+        keywords: Value<'_, 'static>,
+    ) -> JlrsResult<Self>
+    where
+        V: AsRef<[Value<'value, 'static>]>,
+    {
+        let args = args.as_ref();
+        let mut vals: SmallVec<[Value; MAX_SIZE]> = SmallVec::with_capacity(2 + args.len());
+        vals.push(self.0);
+        vals.push(plot_fn.as_value());
+
+        for arg in args.iter().copied() {
+            vals.push(arg);
+        }
+
+        let plt = headless_function(frame, "updateplot")?
+            .provide_keywords(keywords)?
+            .call(frame, vals)
+            .into_jlrs_result()?;
+
+        Ok(HeadlessPlot(plt))
+    }
+
+    /// Render this plot as `format` and write it to the file at `path`. The file is created if
+    /// it doesn't exist and truncated if it does.
+    pub fn save<'frame, P>(
+        self,
+        frame: &mut GcFrame<'frame>,
+        path: P,
+        format: PlotFormat,
+    ) -> JlrsResult<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let path = match path.to_str() {
+            Some(path) => path,
+            None => Err(IOError::InvalidPath {
+                path: path.to_string_lossy().into(),
+            })?,
+        };
+
+        frame.scope(|mut frame| unsafe {
+            let path = JuliaString::new(&mut frame, path);
+            let format = Symbol::new(&frame, format.extension());
+
+            headless_function(&mut frame, "saveplot")?
+                .call3(&mut frame, self.0, path.as_value(), format.as_value())
+                .into_jlrs_result()?;
+
+            Ok(())
+        })
+    }
+
+    /// Render this plot as `format` and return the rendered data.
+    pub fn to_bytes<'frame>(
+        self,
+        frame: &mut GcFrame<'frame>,
+        format: PlotFormat,
+    ) -> JlrsResult<Vec<u8>> {
+        frame.scope(|mut frame| unsafe {
+            let format = Symbol::new(&frame, format.extension());
+
+            let bytes = headless_function(&mut frame, "plotbytes")?
+                .call2(&mut frame, self.0, format.as_value())
+                .into_jlrs_result()?
+                .cast::<Array>()?
+                .copy_inline_data::<u8>()?;
+
+            Ok(bytes.splat().0.into_vec())
+        })
+    }
+
+    /// Returns the Julia value of this plot.
+    pub fn as_value(self) -> Value<'scope, 'static> {
+        self.0
+    }
+}
+
+fn headless_function<'frame>(
+    frame: &mut GcFrame<'frame>,
+    name: &str,
+) -> JlrsResult<Function<'frame, 'static>> {
+    // Safety: the function is a constant in JlrsHeadlessPlot, it's never freed.
+    unsafe {
+        Ok(Module::main(&*frame)
+            .submodule(&*frame, "JlrsHeadlessPlot")?
+            .wrapper()
+            .function(&*frame, name)?
+            .wrapper())
+    }
+}
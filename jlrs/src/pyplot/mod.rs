@@ -5,6 +5,8 @@
 //! to `Gtk3Agg`.
 //!
 //! When multiple figures are open, only the most recently opened one is updated automatically.
+//!
+//! Plots can be rendered to a file or bytes without a GUI with the [`headless`] module.
 
 use smallvec::SmallVec;
 
@@ -23,6 +25,8 @@ use crate::{
     },
 };
 
+pub mod headless;
+
 init_fn!(init_jlrs_py_plot, JLRS_PY_PLOT_JL, "JlrsPyPlot.jl");
 
 /// A handle to a plotting window.
@@ -291,11 +295,15 @@ impl<'scope> PyPlot<'scope> {
 /// This trait is, and can only be, implemented by [`Module`]. It adds the method `Module::plots`
 /// that provides access to the contents of the `Plots` package.
 pub trait AccessPlotsModule: private::AccessPlotsModulePriv {
-    /// Returns the `Plots` module.
+    /// Returns the `Plots` module. Either [`PyPlot::init`] or [`HeadlessPlot::init`] must have
+    /// been called.
+    ///
+    /// [`HeadlessPlot::init`]: crate::pyplot::headless::HeadlessPlot::init
     fn plots<'global, T: Target<'global>>(target: &T) -> Module<'global> {
         unsafe {
-            Module::main(target)
-                .submodule(target, "JlrsPyPlot")
+            let main = Module::main(target);
+            main.submodule(target, "JlrsPyPlot")
+                .or_else(|_| main.submodule(target, "JlrsHeadlessPlot"))
                 .unwrap()
                 .wrapper()
                 .submodule(target, "Plots")
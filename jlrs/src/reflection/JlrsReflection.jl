@@ -0,0 +1,87 @@
+module JlrsReflection
+
+struct ModuleInfo
+    name::String
+    exported::Vector{String}
+    functions::Vector{String}
+    types::Vector{String}
+    submodules::Vector{String}
+    constants::Vector{String}
+end
+
+# Mutable so a Vector{MethodInfo} stores pointers rather than the structs themselves
+mutable struct MethodInfo
+    name::String
+    module_name::String
+    file::String
+    line::Int
+    signature::String
+    arg_names::Vector{String}
+    arg_types::Vector{String}
+    kwarg_names::Vector{String}
+    is_vararg::Bool
+end
+
+function moduleinfo(mod::Module, all::Bool)::ModuleInfo
+    exported = String[string(name) for name in names(mod)]
+    functions = String[]
+    types = String[]
+    submodules = String[]
+    constants = String[]
+
+    for name in names(mod; all = all)
+        isdefined(mod, name) || continue
+        s = string(name)
+        startswith(s, '#') && continue
+
+        value = getfield(mod, name)
+        if value isa Module
+            value === mod || push!(submodules, s)
+        elseif value isa Type
+            push!(types, s)
+        elseif value isa Function
+            push!(functions, s)
+        elseif isconst(mod, name)
+            push!(constants, s)
+        end
+    end
+
+    ModuleInfo(string(nameof(mod)), exported, functions, types, submodules, constants)
+end
+
+function methodinfo(m::Method)::MethodInfo
+    # The first slot is the function itself
+    slots = Base.method_argnames(m)
+    arg_names = String[name === Symbol("#unused#") ? "" : string(name) for name in slots[2:end]]
+
+    sig = Base.unwrap_unionall(m.sig)
+    arg_types = String[string(ty) for ty in sig.parameters[2:end]]
+
+    kwarg_names = String[string(name) for name in Base.kwarg_decl(m)]
+
+    MethodInfo(
+        string(m.name),
+        string(m.module),
+        string(m.file),
+        Int(m.line),
+        string(m.sig),
+        arg_names,
+        arg_types,
+        kwarg_names,
+        m.isva,
+    )
+end
+
+methodinfos(@nospecialize(f))::Vector{MethodInfo} = MethodInfo[methodinfo(m) for m in methods(f)]
+
+function docstring(mod::Module, name::Symbol)::Union{Nothing,String}
+    binding = Base.Docs.Binding(mod, name)
+    for m in Base.Docs.modules
+        if haskey(Base.Docs.meta(m), binding)
+            return string(Base.Docs.doc(binding))
+        end
+    end
+
+    nothing
+end
+end
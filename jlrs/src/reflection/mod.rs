@@ -0,0 +1,270 @@
+//! Inspect the contents of modules and the methods and documentation of functions.
+//!
+//! Julia's reflection capabilities, e.g. `names`, `methods` and `@doc`, are available through
+//! the functions in this module. Rather than walking internal types like `MethodTable` and
+//! `Method`, which requires enabling the `internal-types` feature and depends on the version of
+//! Julia that is used, the data is collected in Julia and returned as plain Rust structs:
+//!
+//!  - [`module_info`] returns a [`ModuleInfo`] with the exported names of a module and the
+//!    names of the functions, types, submodules and constants it contains.
+//!  - [`methods`] returns a [`MethodInfo`] for every method of a function, which contains the
+//!    name and type of every argument, the names of the keyword arguments, and the file and line
+//!    where the method has been defined.
+//!  - [`docs`] returns the docstring of a global.
+//!
+//! Before these functions can be used, [`init`] must have been called.
+//!
+//! Example:
+//!
+//! ```no_run
+//! # use jlrs::prelude::*;
+//! # use jlrs::reflection;
+//! # use jlrs::util::test::JULIA;
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         reflection::init(&mut frame);
+//!
+//!         let base = Module::base(&frame);
+//!         let info = reflection::module_info(&mut frame, base, false)?;
+//!         assert!(info.functions.iter().any(|f| f == "println"));
+//!
+//!         let println = base.function(&frame, "println")?.wrapper();
+//!         for method in reflection::methods(&mut frame, println.as_value())? {
+//!             println!("{}:{} {:?}", method.file, method.line, method.arg_names);
+//!         }
+//!
+//!         let docs = reflection::docs(&mut frame, base, "println")?;
+//!         assert!(docs.is_some());
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+
+use crate::{
+    call::Call,
+    convert::{into_jlrs_result::IntoJlrsResult, to_symbol::ToSymbol},
+    error::{AccessError, JlrsResult},
+    memory::target::frame::GcFrame,
+    wrappers::{
+        inline::bool::Bool,
+        ptr::{
+            array::Array,
+            function::Function,
+            module::Module,
+            string::JuliaString,
+            value::Value,
+            Wrapper,
+        },
+    },
+};
+
+init_fn!(
+    init_jlrs_reflection,
+    JLRS_REFLECTION_JL,
+    "JlrsReflection.jl"
+);
+
+/// The contents of a module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleInfo {
+    /// The name of the module.
+    pub name: String,
+    /// The names exported by the module.
+    pub exported: Vec<String>,
+    /// The names of the functions defined in the module.
+    pub functions: Vec<String>,
+    /// The names of the types defined in the module.
+    pub types: Vec<String>,
+    /// The names of the submodules of the module.
+    pub submodules: Vec<String>,
+    /// The names of the remaining constants defined in the module.
+    pub constants: Vec<String>,
+}
+
+/// A method of a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodInfo {
+    /// The name of the function.
+    pub name: String,
+    /// The name of the module the method has been defined in.
+    pub module: String,
+    /// The file the method has been defined in.
+    pub file: String,
+    /// The line the method has been defined on.
+    pub line: usize,
+    /// The signature of the method, e.g. `Tuple{typeof(f), Int64, Any}`.
+    pub signature: String,
+    /// The names of the positional arguments, these names are taken from the method's
+    /// `slot_syms`. Unnamed arguments have an empty name.
+    pub arg_names: Vec<String>,
+    /// The types of the positional arguments.
+    pub arg_types: Vec<String>,
+    /// The names of the keyword arguments.
+    pub kwarg_names: Vec<String>,
+    /// If `true`, the last positional argument is a vararg.
+    pub is_vararg: bool,
+}
+
+/// This function must be called before this module can be used. The Julia code used by this
+/// module is evaluated if it hasn't been evaluated yet.
+pub fn init<'frame>(frame: &mut GcFrame<'frame>) {
+    if Module::main(&frame)
+        .submodule(&frame, "JlrsReflection")
+        .is_ok()
+    {
+        return;
+    }
+
+    unsafe { init_jlrs_reflection(frame) };
+}
+
+/// Returns the contents of `module`. If `all` is `false` only exported names and the names of
+/// globals that have been defined in `module` are considered, otherwise non-exported and
+/// imported names are included too. Compiler-generated names are always skipped.
+pub fn module_info<'frame>(
+    frame: &mut GcFrame<'frame>,
+    module: Module,
+    all: bool,
+) -> JlrsResult<ModuleInfo> {
+    frame.scope(|mut frame| {
+        let all = Value::new(&mut frame, all);
+        // Safety: moduleinfo only reads the contents of the module.
+        let info = unsafe {
+            reflection_fn(&frame, "moduleinfo")?
+                .call2(&mut frame, module.as_value(), all)
+                .into_jlrs_result()?
+        };
+
+        Ok(ModuleInfo {
+            name: string_field(info, "name")?,
+            exported: string_vector_field(info, "exported")?,
+            functions: string_vector_field(info, "functions")?,
+            types: string_vector_field(info, "types")?,
+            submodules: string_vector_field(info, "submodules")?,
+            constants: string_vector_field(info, "constants")?,
+        })
+    })
+}
+
+/// Returns information about every method of `func`. Any callable value can be used, e.g. a
+/// function or a type.
+pub fn methods<'frame>(frame: &mut GcFrame<'frame>, func: Value) -> JlrsResult<Vec<MethodInfo>> {
+    frame.scope(|mut frame| {
+        // Safety: methodinfos only reads the method table of func.
+        let infos = unsafe {
+            reflection_fn(&frame, "methodinfos")?
+                .call1(&mut frame, func)
+                .into_jlrs_result()?
+                .cast::<Array>()?
+        };
+
+        // Safety: the array is rooted and not mutated while its contents are accessed.
+        unsafe {
+            infos
+                .value_data()?
+                .as_slice()
+                .iter()
+                .map(|info| {
+                    let info = info.ok_or(AccessError::UndefRef)?.value();
+                    Ok(MethodInfo {
+                        name: string_field(info, "name")?,
+                        module: string_field(info, "module_name")?,
+                        file: string_field(info, "file")?,
+                        line: info
+                            .field_accessor()
+                            .field("line")?
+                            .access::<isize>()?
+                            .max(0) as usize,
+                        signature: string_field(info, "signature")?,
+                        arg_names: string_vector_field(info, "arg_names")?,
+                        arg_types: string_vector_field(info, "arg_types")?,
+                        kwarg_names: string_vector_field(info, "kwarg_names")?,
+                        is_vararg: info
+                            .field_accessor()
+                            .field("is_vararg")?
+                            .access::<Bool>()?
+                            .as_bool(),
+                    })
+                })
+                .collect()
+        }
+    })
+}
+
+/// Returns the docstring of the global `name` in `module`, or `None` if it hasn't been
+/// documented.
+pub fn docs<'frame, N: ToSymbol>(
+    frame: &mut GcFrame<'frame>,
+    module: Module,
+    name: N,
+) -> JlrsResult<Option<String>> {
+    frame.scope(|mut frame| {
+        let name = name.to_symbol(&frame);
+        // Safety: docstring only reads the documentation of the binding.
+        let docs = unsafe {
+            reflection_fn(&frame, "docstring")?
+                .call2(&mut frame, module.as_value(), name.as_value())
+                .into_jlrs_result()?
+        };
+
+        if docs == Value::nothing(&frame) {
+            return Ok(None);
+        }
+
+        Ok(Some(docs.cast::<JuliaString>()?.as_str()?.into()))
+    })
+}
+
+fn reflection_fn<'frame>(
+    frame: &GcFrame<'frame>,
+    name: &str,
+) -> JlrsResult<Function<'frame, 'static>> {
+    // Safety: the module and its functions are globally rooted.
+    unsafe {
+        Ok(Module::main(frame)
+            .submodule(frame, "JlrsReflection")?
+            .wrapper()
+            .function(frame, name)?
+            .wrapper())
+    }
+}
+
+fn string_field(value: Value, name: &str) -> JlrsResult<String> {
+    // Safety: the field is only accessed while value is rooted.
+    unsafe {
+        let field = value
+            .get_field_ref(name)?
+            .ok_or(AccessError::UndefRef)?
+            .value();
+
+        Ok(field.cast::<JuliaString>()?.as_str()?.into())
+    }
+}
+
+fn string_vector_field(value: Value, name: &str) -> JlrsResult<Vec<String>> {
+    // Safety: the field is only accessed while value is rooted.
+    unsafe {
+        let arr = value
+            .get_field_ref(name)?
+            .ok_or(AccessError::UndefRef)?
+            .value()
+            .cast::<Array>()?;
+
+        arr.value_data()?
+            .as_slice()
+            .iter()
+            .map(|s| {
+                let s = s.ok_or(AccessError::UndefRef)?.value();
+                Ok(s.cast::<JuliaString>()?.as_str()?.into())
+            })
+            .collect()
+    }
+}
@@ -1,6 +1,8 @@
 module Jlrs
 
 const color = Ref{Bool}(false)
+const rooted_types = Any[]
+const rooted_functions = Any[]
 
 function valuestring(@nospecialize(value::Any))::String
     io = IOBuffer()
@@ -13,4 +15,43 @@ function errorstring(@nospecialize(value::Any))::String
     showerror(IOContext(io, :color => color[], :compact => true, :limit => true), value)
     String(take!(io))
 end
+
+collectany(@nospecialize(itr))::Vector{Any} = Any[x for x in itr]
+
+bigintstring(x::BigInt)::String = string(x; base = 16)
+parsebigint(s::String)::BigInt = parse(BigInt, s; base = 16)
+bigfloatstring(x::BigFloat)::String = string(x)
+parsebigfloat(s::String, precision::UInt)::BigFloat = BigFloat(s; precision = precision)
+
+writebytes(io::IOBuffer, ptr::Ptr{UInt8}, n::UInt)::Nothing = (unsafe_write(io, ptr, n); nothing)
+takestring!(io::IOBuffer)::String = String(take!(io))
+
+struct RustFunctionError <: Exception
+    msg::String
+end
+
+Base.showerror(io::IO, e::RustFunctionError) = print(io, "RustFunctionError: ", e.msg)
+
+const rustfunctionptr = Ref{Ptr{Cvoid}}(C_NULL)
+
+function callrustfunction(@nospecialize(f), args::Core.SimpleVector)
+    failed = Ref{Bool}(false)
+    res = ccall(rustfunctionptr[], Any, (Any, Any, Ref{Bool}), f, args, failed)
+    failed[] && throw(RustFunctionError(res::String))
+    res
+end
+
+# ccall requires the signature to be known when the function is defined, so the wrapper is
+# generated with eval.
+function cfunctionwrapper(ptr::Ptr{Cvoid}, rettype::Type, argtypes::Type...)
+    args = [Symbol(:arg, i) for i in 1:length(argtypes)]
+    typedargs = [:($(arg)::$(ty)) for (arg, ty) in zip(args, argtypes)]
+    @eval ($(typedargs...),) -> ccall($ptr, $rettype, ($(argtypes...),), $(args...))
+end
+
+function initrustfunction(ty::DataType, ptr::Ptr{Cvoid})::Nothing
+    rustfunctionptr[] = ptr
+    @eval (f::$ty)(args...) = callrustfunction(f, Core.svec(args...))
+    nothing
+end
 end
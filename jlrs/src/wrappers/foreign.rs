@@ -14,6 +14,9 @@
 /// implemented. Whenever Julia data in an instance of a foreign type is mutated,
 /// [`InlineLayout::write_barrier`] must be called if the foreign data is owned by Julia.
 ///
+/// If a foreign type owns heap-allocated data, e.g. a `Vec`, [`ForeignType::external_size`]
+/// should be implemented so the GC is aware of this memory.
+///
 /// It's recommended that `ForeignType` is only implemented for types that are thread-safe, and
 /// if a foreign type needs to be mutable this should be achieved through interior mutability.
 use std::{
@@ -34,7 +37,12 @@ use super::ptr::{datatype::DataTypeData, private::WrapperPriv, value::ValueData}
 use crate::{
     convert::{into_julia::IntoJulia, unbox::Unbox},
     layout::valid_layout::ValidLayout,
-    memory::{get_tls, target::Target, PTls},
+    memory::{
+        gc::{report_external_alloc, report_external_free},
+        get_tls,
+        target::Target,
+        PTls,
+    },
     private::Private,
     wrappers::ptr::{datatype::DataType, module::Module, symbol::Symbol, value::Value},
 };
@@ -77,6 +85,17 @@ pub unsafe trait ForeignType: Sized + 'static {
     fn mark(_ptls: PTls, _data: &Self) -> usize {
         0
     }
+
+    /// The number of bytes of heap-allocated memory owned by this value.
+    ///
+    /// The GC is unaware of memory that has been allocated by Rust. When a foreign value is
+    /// converted to Julia data, the size returned by this method is reported to the GC as if it
+    /// has been allocated by Julia, and it's reported as freed when the value is swept. This lets
+    /// the GC take this memory into account when it decides to collect garbage. The returned
+    /// value must not change while the value is owned by Julia.
+    fn external_size(&self) -> usize {
+        0
+    }
 }
 
 /// Create a new foreign type `U`. This method must be called before `U` can be used as a foreign
@@ -142,7 +161,9 @@ unsafe fn do_sweep<T>(data: &mut ForeignValue<T>)
 where
     T: ForeignType,
 {
+    let external_size = data.data.assume_init_ref().external_size();
     data.data.assume_init_drop();
+    report_external_free(external_size);
 }
 
 unsafe impl<F: ForeignType> IntoJulia for F {
@@ -159,6 +180,10 @@ unsafe impl<F: ForeignType> IntoJulia for F {
         T: Target<'scope>,
     {
         unsafe {
+            // Report the external memory first so it's taken into account if allocating the
+            // value triggers a collection.
+            report_external_alloc(self.external_size());
+
             let ptls = get_tls();
             let sz = std::mem::size_of::<Self>();
             let ty = FOREIGN_TYPES.find::<F>().expect("Doesn't exist");
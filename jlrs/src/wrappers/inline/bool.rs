@@ -2,15 +2,28 @@
 //!
 //! In Rust it's unsound to create an invalid `bool`, while a `Bool` in Julia can be an arbitrary
 //! `i8` in some rare cases. Rather than treating all `Bool`s as `i8` or `bool`s jlrs provides
-use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    ptr::NonNull,
+};
 
-use jl_sys::{jl_bool_type, jl_unbox_int8};
+use jl_sys::{jl_bool_type, jl_box_bool, jl_unbox_int8};
 
 /// a wrapper for this type.
-use crate::{convert::unbox::Unbox, impl_julia_typecheck, impl_valid_layout};
 use crate::{
+    convert::{
+        into_julia::{IntoJulia, IsBits},
+        unbox::Unbox,
+    },
+    impl_julia_typecheck,
+    impl_valid_layout,
+    memory::target::Target,
     private::Private,
-    wrappers::ptr::{private::WrapperPriv, value::Value},
+    wrappers::ptr::{
+        datatype::{DataType, DataTypeData},
+        private::WrapperPriv,
+        value::{Value, ValueData},
+    },
 };
 
 /// A Julia `Bool`.
@@ -77,3 +90,24 @@ unsafe impl Unbox for bool {
         Bool(jl_unbox_int8(value.unwrap(Private).cast()))
     }
 }
+
+unsafe impl IntoJulia for Bool {
+    fn julia_type<'scope, T>(target: T) -> DataTypeData<'scope, T>
+    where
+        T: Target<'scope>,
+    {
+        let dt = DataType::bool_type(&target);
+        unsafe { target.data_from_ptr(dt.unwrap_non_null(Private), Private) }
+    }
+
+    #[inline(always)]
+    fn into_julia<'scope, T>(self, target: T) -> ValueData<'scope, 'static, T>
+    where
+        T: Target<'scope>,
+    {
+        unsafe { target.data_from_ptr(NonNull::new_unchecked(jl_box_bool(self.0)), Private) }
+    }
+}
+
+// Safety: Bool is a bits type
+unsafe impl IsBits for Bool {}
@@ -3,16 +3,27 @@
 //! In Rust it's unsafe to create an invalid `char`, while a `Char` in Julia can be an arbitrary
 //! `u32` in some rare cases. Rather than treating all `Char`s as `u32` or `char`s, jlrs provides
 //! a wrapper for this type.
-use std::fmt::{Debug, Formatter, Result as FmtResult, Write};
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult, Write},
+    ptr::NonNull,
+};
 
-use jl_sys::{jl_char_type, jl_unbox_uint32};
+use jl_sys::{jl_box_char, jl_char_type, jl_unbox_uint32};
 
 use crate::{
-    convert::unbox::Unbox,
+    convert::{
+        into_julia::{IntoJulia, IsBits},
+        unbox::Unbox,
+    },
     impl_julia_typecheck,
     impl_valid_layout,
+    memory::target::Target,
     private::Private,
-    wrappers::ptr::{private::WrapperPriv, value::Value},
+    wrappers::ptr::{
+        datatype::{DataType, DataTypeData},
+        private::WrapperPriv,
+        value::{Value, ValueData},
+    },
 };
 
 /// A Julia `Char`.
@@ -75,3 +86,24 @@ unsafe impl Unbox for char {
         Char(jl_unbox_uint32(value.unwrap(Private).cast()))
     }
 }
+
+unsafe impl IntoJulia for Char {
+    fn julia_type<'scope, T>(target: T) -> DataTypeData<'scope, T>
+    where
+        T: Target<'scope>,
+    {
+        let dt = DataType::char_type(&target);
+        unsafe { target.data_from_ptr(dt.unwrap_non_null(Private), Private) }
+    }
+
+    #[inline(always)]
+    fn into_julia<'scope, T>(self, target: T) -> ValueData<'scope, 'static, T>
+    where
+        T: Target<'scope>,
+    {
+        unsafe { target.data_from_ptr(NonNull::new_unchecked(jl_box_char(self.0)), Private) }
+    }
+}
+
+// Safety: Char is a bits type
+unsafe impl IsBits for Char {}
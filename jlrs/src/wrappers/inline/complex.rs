@@ -0,0 +1,113 @@
+//! Support for `Complex` numbers.
+//!
+//! This module is only available if the `complex` feature is enabled. The layout of
+//! `num_complex::Complex<T>` matches the layout of `Complex{T}`, so it can be used directly as
+//! Julia data: it can be converted to Julia with [`Value::new`], unboxed, used as a field type,
+//! and used as the element type of an array. For example, `Complex<f64>` is associated with
+//! `ComplexF64`.
+//!
+//! [`Value::new`]: crate::wrappers::ptr::value::Value::new
+
+use num_complex::Complex;
+
+use super::numeric::{apply_base_type, is_base_type_with_fields, JuliaReal};
+use crate::{
+    convert::{
+        construct_type::ConstructType,
+        into_julia::{IntoJulia, IsBits},
+        julia_struct::JuliaTypeString,
+        unbox::Unbox,
+    },
+    layout::{
+        typecheck::Typecheck,
+        valid_layout::{ValidField, ValidLayout},
+    },
+    memory::target::{ExtendedTarget, Target},
+    private::Private,
+    wrappers::ptr::{
+        datatype::{DataType, DataTypeData},
+        value::{Value, ValueData},
+        Wrapper,
+    },
+};
+
+unsafe impl<U> IntoJulia for Complex<U>
+where
+    U: JuliaReal,
+{
+    fn julia_type<'scope, T>(target: T) -> DataTypeData<'scope, T>
+    where
+        T: Target<'scope>,
+    {
+        // Safety: the element type is a global constant
+        let elem_ty = unsafe { U::julia_type(&target).wrapper() };
+        apply_base_type(target, "Complex", elem_ty)
+    }
+}
+
+// Safety: Complex{T} is a bits type if T is a bits type
+unsafe impl<U: JuliaReal> IsBits for Complex<U> {}
+
+unsafe impl<U> ConstructType for Complex<U>
+where
+    U: JuliaReal,
+{
+    fn construct_type<'target, 'current, 'borrow, T>(
+        target: ExtendedTarget<'target, 'current, 'borrow, T>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        let (output, _) = target.split();
+        // Safety: the type is cached by Julia
+        unsafe {
+            let ty = Self::julia_type(&output).ptr();
+            output.data_from_ptr(ty.cast(), Private)
+        }
+    }
+}
+
+unsafe impl<U> JuliaTypeString for Complex<U>
+where
+    U: JuliaTypeString,
+{
+    fn julia_type_string() -> String {
+        format!("Base.Complex{{{}}}", U::julia_type_string())
+    }
+}
+
+unsafe impl<U> ValidLayout for Complex<U>
+where
+    U: ValidField,
+{
+    fn valid_layout(v: Value) -> bool {
+        <Self as ValidField>::valid_field(v)
+    }
+
+    const IS_REF: bool = false;
+}
+
+unsafe impl<U> ValidField for Complex<U>
+where
+    U: ValidField,
+{
+    fn valid_field(v: Value) -> bool {
+        is_base_type_with_fields::<U>(v, "Complex")
+    }
+}
+
+unsafe impl<U> Unbox for Complex<U>
+where
+    U: ValidField + Clone,
+{
+    type Output = Self;
+}
+
+unsafe impl<U> Typecheck for Complex<U>
+where
+    U: ValidField,
+{
+    fn typecheck(t: DataType) -> bool {
+        <Self as ValidLayout>::valid_layout(t.as_value())
+    }
+}
@@ -0,0 +1,509 @@
+//! Wrappers for `Date`, `DateTime`, `Time` and periods from the `Dates` standard library.
+//!
+//! This module is only available if the `dates` feature is enabled. Every type in this module
+//! has the same layout as the Julia type with the same name, it contains the `Int64` that is
+//! returned by `Dates.value`. They can be converted to Julia with [`Value::new`], unboxed, used
+//! as field types, and used as the element type of an array. For example, a `Vec<DateTime>` can
+//! be converted to a `Vector{DateTime}` with [`Array::from_vec`] without converting each element.
+//!
+//! The types from `Dates` are looked up when [`load_dates`] is called, or the first time one
+//! of these types is converted to Julia. `Dates` is found through `Base.require`, so this works
+//! whether or not jlrs has been initialized through `Julia::init` or `CCall`. Checking the layout
+//! of some value or type never calls into Julia, the types from `Dates` are identified by their
+//! name and module.
+//!
+//! These types can be converted from and to the types from chrono. Julia's dates use the
+//! proleptic Gregorian calendar like chrono's do, but they support a much larger range, so
+//! converting a [`Date`], [`DateTime`] or [`Time`] to chrono can fail. The corresponding chrono
+//! types can also be unboxed directly, they unbox to `None` if the value is out of range.
+//! Converting a chrono type to a [`DateTime`] truncates it to millisecond precision.
+//!
+//! Example:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! use chrono::NaiveDate;
+//! use jlrs::wrappers::inline::dates::{load_dates, Date};
+//!
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         unsafe { load_dates(&mut frame)? };
+//!
+//!         let date = NaiveDate::from_ymd_opt(2022, 10, 18).unwrap();
+//!         let value = Value::new(&mut frame, Date::from(date));
+//!         assert_eq!(value.unbox::<NaiveDate>()?, Some(date));
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+//!
+//! [`Value::new`]: crate::wrappers::ptr::value::Value::new
+//! [`Array::from_vec`]: crate::wrappers::ptr::array::Array::from_vec
+
+use std::{
+    convert::TryFrom,
+    ptr::{null_mut, NonNull},
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use jl_sys::jl_datatype_t;
+
+use crate::{
+    call::Call,
+    convert::{
+        construct_type::ConstructType,
+        into_julia::{IntoJulia, IsBits},
+        to_symbol::ToSymbol,
+        unbox::Unbox,
+    },
+    error::{JlrsError, JlrsResult, CANNOT_DISPLAY_VALUE},
+    layout::{
+        typecheck::Typecheck,
+        valid_layout::{ValidField, ValidLayout},
+    },
+    memory::target::{frame::GcFrame, unrooted::Unrooted, ExtendedTarget, Target},
+    private::Private,
+    wrappers::ptr::{
+        datatype::{DataType, DataTypeData},
+        module::{base_function, Module},
+        private::WrapperPriv,
+        value::{Value, ValueData},
+        Wrapper,
+    },
+};
+
+const MILLISECONDS_PER_DAY: i64 = 86_400_000;
+const NANOSECONDS_PER_SECOND: i64 = 1_000_000_000;
+
+/// Load the `Dates` module if it hasn't been loaded yet. If Julia throws an exception it's
+/// caught and returned as an error.
+///
+/// Calling this function is optional, the types in this module load `Dates` the first time
+/// they're converted to Julia. Because that conversion can't fail, the process is aborted if
+/// `Dates` can't be loaded at that point. Call this function first to handle that error instead.
+///
+/// Safety: loading `Dates` executes its initialization code.
+pub unsafe fn load_dates(_frame: &mut GcFrame) -> JlrsResult<()> {
+    let dates = dates_module()?;
+    Date::cache_datatype(dates)?;
+    DateTime::cache_datatype(dates)?;
+    Time::cache_datatype(dates)?;
+    Year::cache_datatype(dates)?;
+    Month::cache_datatype(dates)?;
+    Week::cache_datatype(dates)?;
+    Day::cache_datatype(dates)?;
+    Hour::cache_datatype(dates)?;
+    Minute::cache_datatype(dates)?;
+    Second::cache_datatype(dates)?;
+    Millisecond::cache_datatype(dates)?;
+    Microsecond::cache_datatype(dates)?;
+    Nanosecond::cache_datatype(dates)?;
+
+    Ok(())
+}
+
+// Returns the `Dates` module, it's loaded with `Base.require(Main, :Dates)` if necessary. The
+// Jlrs module isn't used, and the module is rooted by `Base.loaded_modules`.
+unsafe fn dates_module() -> JlrsResult<Module<'static>> {
+    let global = Unrooted::new();
+    let main = Module::main(&global).as_value();
+    let name = "Dates".to_symbol(&global).as_value();
+    let dates = base_function(&global, "require")?
+        .call2(&global, main, name)
+        .map_err(|e| {
+            let msg = e.value().error_string_or(CANNOT_DISPLAY_VALUE);
+            Box::new(JlrsError::exception(msg))
+        })?
+        .value()
+        .cast::<Module>()?;
+
+    Ok(dates)
+}
+
+// Stores `Dates.$name` in `cache`. The type is a constant in Dates so it's never freed.
+unsafe fn cache_dates_type(
+    dates: Module,
+    cache: &AtomicPtr<jl_datatype_t>,
+    name: &str,
+) -> JlrsResult<NonNull<jl_datatype_t>> {
+    let global = Unrooted::new();
+    let ty = dates
+        .global(&global, name)?
+        .value()
+        .cast::<DataType>()?
+        .unwrap_non_null(Private);
+
+    cache.store(ty.as_ptr(), Ordering::Relaxed);
+    Ok(ty)
+}
+
+// Returns `true` if `v` is the type `Dates.$name`. No Julia functions are called, the type is
+// identified by its name and the name of the root module it has been defined in.
+fn is_dates_type(cache: &AtomicPtr<jl_datatype_t>, v: Value, name: &str) -> bool {
+    if let Some(ty) = NonNull::new(cache.load(Ordering::Relaxed)) {
+        return v.unwrap_non_null(Private) == ty.cast();
+    }
+
+    let dt = match v.cast::<DataType>() {
+        Ok(dt) => dt,
+        Err(_) => return false,
+    };
+
+    if dt.name() != name {
+        return false;
+    }
+
+    let module = dt.type_name().module();
+    module.parent() == module && module.name().as_str().map_or(false, |n| n == "Dates")
+}
+
+macro_rules! impl_dates_type {
+    ($(#[$meta:meta])* $name:ident, $value_doc:literal) => {
+        $(#[$meta])*
+        #[repr(C)]
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name {
+            #[doc = $value_doc]
+            pub value: i64,
+        }
+
+        impl $name {
+            /// Create a new instance from its value.
+            pub const fn new(value: i64) -> Self {
+                $name { value }
+            }
+
+            fn cache() -> &'static AtomicPtr<jl_datatype_t> {
+                static TYPE: AtomicPtr<jl_datatype_t> = AtomicPtr::new(null_mut());
+                &TYPE
+            }
+
+            unsafe fn cache_datatype(dates: Module) -> JlrsResult<()> {
+                cache_dates_type(dates, Self::cache(), stringify!($name))?;
+                Ok(())
+            }
+
+            // Returns the cached type, `Dates` is loaded if this type hasn't been cached yet.
+            // This is called from trait methods that can't fail and that might be called from
+            // a function called by Julia, so the process is aborted rather than unwinding if
+            // `Dates` can't be loaded.
+            fn loaded_datatype() -> NonNull<jl_datatype_t> {
+                if let Some(ty) = NonNull::new(Self::cache().load(Ordering::Relaxed)) {
+                    return ty;
+                }
+
+                // Safety: the types are only converted to Julia from a thread that can call
+                // into Julia.
+                let ty = unsafe {
+                    dates_module()
+                        .and_then(|dates| cache_dates_type(dates, Self::cache(), stringify!($name)))
+                };
+
+                match ty {
+                    Ok(ty) => ty,
+                    Err(e) => {
+                        eprintln!("Cannot load Dates.{}: {}", stringify!($name), e);
+                        std::process::abort()
+                    }
+                }
+            }
+        }
+
+        // Safety: the layout matches the layout of the Julia type. Converting this type to Julia
+        // loads Dates if it hasn't been loaded yet.
+        unsafe impl IntoJulia for $name {
+            fn julia_type<'scope, T>(target: T) -> DataTypeData<'scope, T>
+            where
+                T: Target<'scope>,
+            {
+                // Safety: the type is a constant in Dates
+                unsafe { target.data_from_ptr(Self::loaded_datatype(), Private) }
+            }
+        }
+
+        // Safety: all types in this module are bits types
+        unsafe impl IsBits for $name {}
+
+        unsafe impl ConstructType for $name {
+            fn construct_type<'target, 'current, 'borrow, T>(
+                target: ExtendedTarget<'target, 'current, 'borrow, T>,
+            ) -> ValueData<'target, 'static, T>
+            where
+                T: Target<'target>,
+            {
+                let (output, _) = target.split();
+                // Safety: the type is a constant in Dates
+                unsafe { output.data_from_ptr(Self::loaded_datatype().cast(), Private) }
+            }
+        }
+
+        unsafe impl ValidLayout for $name {
+            fn valid_layout(v: Value) -> bool {
+                is_dates_type(Self::cache(), v, stringify!($name))
+            }
+
+            const IS_REF: bool = false;
+        }
+
+        unsafe impl ValidField for $name {
+            fn valid_field(v: Value) -> bool {
+                <Self as ValidLayout>::valid_layout(v)
+            }
+        }
+
+        unsafe impl Unbox for $name {
+            type Output = Self;
+        }
+
+        unsafe impl Typecheck for $name {
+            fn typecheck(t: DataType) -> bool {
+                <Self as ValidLayout>::valid_layout(t.as_value())
+            }
+        }
+    };
+}
+
+impl_dates_type!(
+    /// The layout of `Dates.Date`.
+    Date,
+    "The number of days since 0000-12-31, 0001-01-01 is day 1."
+);
+
+impl_dates_type!(
+    /// The layout of `Dates.DateTime`.
+    DateTime,
+    "The number of milliseconds since 0000-12-31T00:00:00."
+);
+
+impl_dates_type!(
+    /// The layout of `Dates.Time`.
+    Time,
+    "The number of nanoseconds since midnight."
+);
+
+impl_dates_type!(
+    /// The layout of `Dates.Year`.
+    Year,
+    "The number of years."
+);
+
+impl_dates_type!(
+    /// The layout of `Dates.Month`.
+    Month,
+    "The number of months."
+);
+
+impl_dates_type!(
+    /// The layout of `Dates.Week`.
+    Week,
+    "The number of weeks."
+);
+
+impl_dates_type!(
+    /// The layout of `Dates.Day`.
+    Day,
+    "The number of days."
+);
+
+impl_dates_type!(
+    /// The layout of `Dates.Hour`.
+    Hour,
+    "The number of hours."
+);
+
+impl_dates_type!(
+    /// The layout of `Dates.Minute`.
+    Minute,
+    "The number of minutes."
+);
+
+impl_dates_type!(
+    /// The layout of `Dates.Second`.
+    Second,
+    "The number of seconds."
+);
+
+impl_dates_type!(
+    /// The layout of `Dates.Millisecond`.
+    Millisecond,
+    "The number of milliseconds."
+);
+
+impl_dates_type!(
+    /// The layout of `Dates.Microsecond`.
+    Microsecond,
+    "The number of microseconds."
+);
+
+impl_dates_type!(
+    /// The layout of `Dates.Nanosecond`.
+    Nanosecond,
+    "The number of nanoseconds."
+);
+
+/// Error returned when a date, time or period can't be represented by chrono.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OutOfRangeError;
+
+impl std::fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("value is out of range")
+    }
+}
+
+impl std::error::Error for OutOfRangeError {}
+
+impl From<NaiveDate> for Date {
+    fn from(date: NaiveDate) -> Self {
+        Date::new(date.num_days_from_ce() as i64)
+    }
+}
+
+impl TryFrom<Date> for NaiveDate {
+    type Error = OutOfRangeError;
+
+    fn try_from(date: Date) -> Result<Self, Self::Error> {
+        i32::try_from(date.value)
+            .ok()
+            .and_then(NaiveDate::from_num_days_from_ce_opt)
+            .ok_or(OutOfRangeError)
+    }
+}
+
+impl From<NaiveDateTime> for DateTime {
+    fn from(datetime: NaiveDateTime) -> Self {
+        let days = datetime.date().num_days_from_ce() as i64;
+        let time = datetime.time();
+        let millis =
+            time.num_seconds_from_midnight() as i64 * 1000 + time.nanosecond() as i64 / 1_000_000;
+
+        DateTime::new(days * MILLISECONDS_PER_DAY + millis)
+    }
+}
+
+impl From<chrono::DateTime<Utc>> for DateTime {
+    fn from(datetime: chrono::DateTime<Utc>) -> Self {
+        datetime.naive_utc().into()
+    }
+}
+
+impl TryFrom<DateTime> for NaiveDateTime {
+    type Error = OutOfRangeError;
+
+    fn try_from(datetime: DateTime) -> Result<Self, Self::Error> {
+        let days = datetime.value.div_euclid(MILLISECONDS_PER_DAY);
+        let millis = datetime.value.rem_euclid(MILLISECONDS_PER_DAY);
+
+        let date = NaiveDate::try_from(Date::new(days))?;
+        let time = NaiveTime::from_num_seconds_from_midnight_opt(
+            (millis / 1000) as u32,
+            (millis % 1000) as u32 * 1_000_000,
+        )
+        .ok_or(OutOfRangeError)?;
+
+        Ok(NaiveDateTime::new(date, time))
+    }
+}
+
+impl TryFrom<DateTime> for chrono::DateTime<Utc> {
+    type Error = OutOfRangeError;
+
+    fn try_from(datetime: DateTime) -> Result<Self, Self::Error> {
+        let naive = NaiveDateTime::try_from(datetime)?;
+        Ok(chrono::DateTime::from_naive_utc_and_offset(naive, Utc))
+    }
+}
+
+impl From<NaiveTime> for Time {
+    fn from(time: NaiveTime) -> Self {
+        let secs = time.num_seconds_from_midnight() as i64;
+        Time::new(secs * NANOSECONDS_PER_SECOND + time.nanosecond() as i64)
+    }
+}
+
+impl TryFrom<Time> for NaiveTime {
+    type Error = OutOfRangeError;
+
+    fn try_from(time: Time) -> Result<Self, Self::Error> {
+        if time.value < 0 {
+            Err(OutOfRangeError)?
+        }
+
+        let secs =
+            u32::try_from(time.value / NANOSECONDS_PER_SECOND).map_err(|_| OutOfRangeError)?;
+        let nanos = (time.value % NANOSECONDS_PER_SECOND) as u32;
+        NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos).ok_or(OutOfRangeError)
+    }
+}
+
+macro_rules! impl_try_into_duration {
+    ($($name:ident => $ctor:ident),+) => {
+        $(
+            impl TryFrom<$name> for Duration {
+                type Error = OutOfRangeError;
+
+                fn try_from(period: $name) -> Result<Self, Self::Error> {
+                    Duration::$ctor(period.value).ok_or(OutOfRangeError)
+                }
+            }
+        )+
+    };
+}
+
+impl_try_into_duration!(
+    Week => try_weeks,
+    Day => try_days,
+    Hour => try_hours,
+    Minute => try_minutes,
+    Second => try_seconds,
+    Millisecond => try_milliseconds
+);
+
+impl From<Microsecond> for Duration {
+    fn from(period: Microsecond) -> Self {
+        Duration::microseconds(period.value)
+    }
+}
+
+impl From<Nanosecond> for Duration {
+    fn from(period: Nanosecond) -> Self {
+        Duration::nanoseconds(period.value)
+    }
+}
+
+macro_rules! impl_unbox_chrono {
+    ($($chrono_ty:ty => $layout:ident),+) => {
+        $(
+            unsafe impl Unbox for $chrono_ty {
+                type Output = Option<Self>;
+
+                unsafe fn unbox(value: Value) -> Self::Output {
+                    <$chrono_ty>::try_from($layout::unbox(value)).ok()
+                }
+            }
+
+            unsafe impl Typecheck for $chrono_ty {
+                fn typecheck(t: DataType) -> bool {
+                    $layout::typecheck(t)
+                }
+            }
+        )+
+    };
+}
+
+impl_unbox_chrono!(
+    NaiveDate => Date,
+    NaiveDateTime => DateTime,
+    chrono::DateTime<Utc> => DateTime,
+    NaiveTime => Time
+);
@@ -4,7 +4,10 @@ use half::f16;
 use jl_sys::jl_float16_type;
 
 use crate::{
-    convert::{into_julia::IntoJulia, unbox::Unbox},
+    convert::{
+        into_julia::{IntoJulia, IsBits},
+        unbox::Unbox,
+    },
     impl_julia_typecheck,
     impl_valid_layout,
     memory::target::Target,
@@ -22,6 +25,9 @@ unsafe impl Unbox for f16 {
     type Output = Self;
 }
 
+// Safety: Float16 is a bits type
+unsafe impl IsBits for f16 {}
+
 unsafe impl IntoJulia for f16 {
     fn julia_type<'scope, T>(target: T) -> DataTypeData<'scope, T>
     where
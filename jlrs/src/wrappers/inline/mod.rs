@@ -2,10 +2,20 @@
 
 pub mod bool;
 pub mod char;
+#[cfg(feature = "complex")]
+pub mod complex;
+#[cfg(feature = "dates")]
+pub mod dates;
 #[cfg(feature = "f16")]
 pub mod f16;
 pub mod nothing;
+#[cfg(any(feature = "complex", feature = "rational"))]
+pub mod numeric;
+pub mod pointer;
+#[cfg(feature = "rational")]
+pub mod rational;
 #[cfg(feature = "internal-types")]
 pub mod ssa_value;
+pub mod sub_string;
 pub mod tuple;
 pub mod union;
@@ -3,7 +3,10 @@
 use jl_sys::jl_nothing_type;
 
 use crate::{
-    convert::{into_julia::IntoJulia, unbox::Unbox},
+    convert::{
+        into_julia::{IntoJulia, IsBits},
+        unbox::Unbox,
+    },
     impl_julia_typecheck,
     impl_valid_layout,
     memory::target::Target,
@@ -25,6 +28,9 @@ unsafe impl Unbox for Nothing {
     type Output = Self;
 }
 
+// Safety: Nothing is a bits type
+unsafe impl IsBits for Nothing {}
+
 unsafe impl IntoJulia for Nothing {
     fn julia_type<'scope, T>(target: T) -> DataTypeData<'scope, T>
     where
@@ -0,0 +1,109 @@
+//! Marker traits for the type parameters of `Complex` and `Rational`.
+//!
+//! `Rational{T}` requires that `T <: Integer` and `Complex{T}` that `T <: Real`. These traits
+//! are implemented for the Rust types that are mapped to such Julia types, they're used to
+//! reject types like `Rational<f64>` and `Complex<Complex<f64>>` at compile time.
+
+use std::ptr::NonNull;
+
+use jl_sys::jl_apply_type;
+
+use crate::{
+    convert::into_julia::IsBits,
+    layout::valid_layout::ValidField,
+    memory::target::{unrooted::Unrooted, Target},
+    private::Private,
+    wrappers::ptr::{
+        datatype::{DataType, DataTypeData},
+        module::Module,
+        private::WrapperPriv,
+        union_all::UnionAll,
+        value::Value,
+    },
+};
+
+/// Implemented by types that are mapped to a subtype of `Real`.
+///
+/// Safety: the type returned by `IntoJulia::julia_type` must be a subtype of `Real`.
+pub unsafe trait JuliaReal: IsBits + ValidField {}
+
+/// Implemented by types that are mapped to a subtype of `Integer`.
+///
+/// Safety: the type returned by `IntoJulia::julia_type` must be a subtype of `Integer`.
+pub unsafe trait JuliaInteger: JuliaReal {}
+
+macro_rules! impl_numeric {
+    (integer: $($ty:ty),+) => {
+        $(
+            unsafe impl JuliaReal for $ty {}
+            unsafe impl JuliaInteger for $ty {}
+        )+
+    };
+    (real: $($ty:ty),+) => {
+        $(
+            unsafe impl JuliaReal for $ty {}
+        )+
+    };
+}
+
+impl_numeric!(integer: bool, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_numeric!(real: f32, f64);
+
+#[cfg(feature = "f16")]
+impl_numeric!(real: half::f16);
+
+#[cfg(feature = "rational")]
+unsafe impl<U: JuliaInteger> JuliaReal for super::rational::Rational<U> {}
+
+// Returns `Base.$name{$param}`, `param` must be a valid type parameter of `Base.$name`.
+pub(crate) fn apply_base_type<'target, T>(
+    target: T,
+    name: &str,
+    param: DataType,
+) -> DataTypeData<'target, T>
+where
+    T: Target<'target>,
+{
+    // Safety: the type is a constant in Base. The parameter is concrete and a valid parameter
+    // of the type, so the applied type is concrete too and cached by Julia.
+    unsafe {
+        let global = Unrooted::new();
+        let base = Module::base(&global)
+            .global(&global, name)
+            .expect("Type doesn't exist")
+            .value();
+
+        let params = &mut [param.unwrap(Private).cast()];
+        let ty = jl_apply_type(base.unwrap(Private), params.as_mut_ptr(), 1);
+        target.data_from_ptr(NonNull::new_unchecked(ty.cast()), Private)
+    }
+}
+
+// Returns `true` if `ty` is an instance of `Base.$name`, and all its fields are valid `U`s.
+pub(crate) fn is_base_type_with_fields<U: ValidField>(ty: Value, name: &str) -> bool {
+    let dt = match ty.cast::<DataType>() {
+        Ok(dt) => dt,
+        Err(_) => return false,
+    };
+
+    // Safety: the type is a constant in Base, field types are globally rooted.
+    unsafe {
+        let global = Unrooted::new();
+        let base = match Module::base(&global).global(&global, name) {
+            Ok(base) => base.value(),
+            Err(_) => return false,
+        };
+
+        match base.cast::<UnionAll>() {
+            Ok(ua) if ua.base_type().type_name() == dt.type_name() => (),
+            _ => return false,
+        }
+
+        dt.field_types(global)
+            .wrapper()
+            .data()
+            .as_slice()
+            .iter()
+            .all(|ty| ty.map_or(false, |ty| U::valid_field(ty.wrapper())))
+    }
+}
@@ -0,0 +1,181 @@
+//! Wrapper for `Ptr`.
+//!
+//! A `Ptr{T}` can also be represented as `*mut T`, but that's only possible if `T` implements
+//! [`IntoJulia`]. [`Ptr`] has the same layout and only requires that `T` implements
+//! [`ValidField`], so it can be used as a field type or unboxed for any pointee type that has a
+//! valid layout, including pointers to pointers and pointers to Julia data.
+//!
+//! [`IntoJulia`]: crate::convert::into_julia::IntoJulia
+
+use std::{fmt, hash::Hash};
+
+use crate::{
+    convert::{
+        construct_type::ConstructType,
+        into_julia::{IntoJulia, IsBits},
+        unbox::Unbox,
+    },
+    layout::{
+        typecheck::Typecheck,
+        valid_layout::{ValidField, ValidLayout},
+    },
+    memory::target::{unrooted::Unrooted, ExtendedTarget, Target},
+    wrappers::ptr::{
+        datatype::{DataType, DataTypeData},
+        type_name::TypeName,
+        value::{Value, ValueData},
+    },
+};
+
+/// A typed pointer, the layout of `Ptr{T}`.
+#[repr(transparent)]
+pub struct Ptr<T> {
+    ptr: *mut T,
+}
+
+impl<T> Ptr<T> {
+    /// Wrap a raw pointer.
+    pub const fn new(ptr: *mut T) -> Self {
+        Ptr { ptr }
+    }
+
+    /// Create a null pointer, `C_NULL` in Julia.
+    pub const fn null() -> Self {
+        Ptr::new(std::ptr::null_mut())
+    }
+
+    /// Returns `true` if this pointer is null.
+    pub fn is_null(self) -> bool {
+        self.ptr.is_null()
+    }
+
+    /// Returns the raw pointer.
+    pub const fn as_ptr(self) -> *mut T {
+        self.ptr
+    }
+
+    /// Cast this pointer to a pointer of another type.
+    pub const fn cast<U>(self) -> Ptr<U> {
+        Ptr::new(self.ptr.cast())
+    }
+
+    /// Read the value this pointer points to.
+    ///
+    /// Safety: the pointer must be valid for reads and point to an initialized `T`.
+    pub unsafe fn read(self) -> T {
+        self.ptr.read()
+    }
+
+    /// Overwrite the value this pointer points to without dropping the old value.
+    ///
+    /// Safety: the pointer must be valid for writes. If `T` contains references to Julia data,
+    /// the pointee must not be owned by Julia.
+    pub unsafe fn write(self, value: T) {
+        self.ptr.write(value)
+    }
+}
+
+impl<T> Clone for Ptr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Ptr<T> {}
+
+impl<T> PartialEq for Ptr<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl<T> Eq for Ptr<T> {}
+
+impl<T> Hash for Ptr<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.ptr.hash(state)
+    }
+}
+
+impl<T> fmt::Debug for Ptr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Ptr").field(&self.ptr).finish()
+    }
+}
+
+impl<T> From<*mut T> for Ptr<T> {
+    fn from(ptr: *mut T) -> Self {
+        Ptr::new(ptr)
+    }
+}
+
+impl<T> From<Ptr<T>> for *mut T {
+    fn from(ptr: Ptr<T>) -> Self {
+        ptr.ptr
+    }
+}
+
+// Safety: Ptr<U> and *mut U have the same layout
+unsafe impl<U: IntoJulia> IntoJulia for Ptr<U> {
+    fn julia_type<'scope, T>(target: T) -> DataTypeData<'scope, T>
+    where
+        T: Target<'scope>,
+    {
+        <*mut U as IntoJulia>::julia_type(target)
+    }
+}
+
+// Safety: Ptr{T} is a bits type
+unsafe impl<U: IntoJulia> IsBits for Ptr<U> {}
+
+unsafe impl<U: ConstructType> ConstructType for Ptr<U> {
+    fn construct_type<'target, 'current, 'borrow, T>(
+        target: ExtendedTarget<'target, 'current, 'borrow, T>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        <*mut U as ConstructType>::construct_type(target)
+    }
+}
+
+unsafe impl<U: ValidField> ValidLayout for Ptr<U> {
+    fn valid_layout(v: Value) -> bool {
+        if let Ok(dt) = v.cast::<DataType>() {
+            Self::typecheck(dt)
+        } else {
+            false
+        }
+    }
+
+    const IS_REF: bool = false;
+}
+
+unsafe impl<U: ValidField> ValidField for Ptr<U> {
+    fn valid_field(v: Value) -> bool {
+        <Self as ValidLayout>::valid_layout(v)
+    }
+}
+
+unsafe impl<U: ValidField> Unbox for Ptr<U> {
+    type Output = Self;
+}
+
+unsafe impl<U: ValidField> Typecheck for Ptr<U> {
+    fn typecheck(t: DataType) -> bool {
+        // Safety: the type name of Ptr is globally rooted, the parameters of t are rooted by t
+        unsafe {
+            let global = Unrooted::new();
+            if t.type_name() != TypeName::of_pointer(&global) {
+                return false;
+            }
+
+            let params = t.parameters();
+            let params = params.data().as_slice();
+            match params[0] {
+                Some(param) => U::valid_field(param.value()),
+                None => false,
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Wrapper for `Rational`.
+//!
+//! This module is only available if the `rational` feature is enabled. Unlike
+//! `num_complex::Complex`, the layout of `num_rational::Ratio<T>` is unspecified, so a
+//! [`Rational`] is used as the layout of `Rational{T}` instead. It can be converted from and to a
+//! `Ratio<T>`. A `Ratio<T>` can also be unboxed directly.
+
+use num_rational::Ratio;
+
+use super::numeric::{apply_base_type, is_base_type_with_fields, JuliaInteger};
+use crate::{
+    convert::{
+        construct_type::ConstructType,
+        into_julia::{IntoJulia, IsBits},
+        julia_struct::JuliaTypeString,
+        unbox::Unbox,
+    },
+    layout::{
+        typecheck::Typecheck,
+        valid_layout::{ValidField, ValidLayout},
+    },
+    memory::target::{ExtendedTarget, Target},
+    private::Private,
+    wrappers::ptr::{
+        datatype::{DataType, DataTypeData},
+        value::{Value, ValueData},
+        Wrapper,
+    },
+};
+
+/// The layout of `Rational{T}`.
+///
+/// Julia expects rationals to be normalized: the numerator and denominator are coprime, and the
+/// denominator is nonnegative. This is guaranteed when it's converted from a `Ratio<T>` that has
+/// been created with `Ratio::new`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rational<T> {
+    /// The numerator.
+    pub num: T,
+    /// The denominator.
+    pub den: T,
+}
+
+impl<T: Clone> From<Ratio<T>> for Rational<T> {
+    fn from(ratio: Ratio<T>) -> Self {
+        Rational {
+            num: ratio.numer().clone(),
+            den: ratio.denom().clone(),
+        }
+    }
+}
+
+impl<T> From<Rational<T>> for Ratio<T> {
+    fn from(rational: Rational<T>) -> Self {
+        // Julia normalizes rationals, so they don't need to be reduced again.
+        Ratio::new_raw(rational.num, rational.den)
+    }
+}
+
+unsafe impl<U> IntoJulia for Rational<U>
+where
+    U: JuliaInteger,
+{
+    fn julia_type<'scope, T>(target: T) -> DataTypeData<'scope, T>
+    where
+        T: Target<'scope>,
+    {
+        // Safety: the element type is a global constant
+        let elem_ty = unsafe { U::julia_type(&target).wrapper() };
+        apply_base_type(target, "Rational", elem_ty)
+    }
+}
+
+// Safety: Rational{T} is a bits type if T is a bits type
+unsafe impl<U: JuliaInteger> IsBits for Rational<U> {}
+
+unsafe impl<U> ConstructType for Rational<U>
+where
+    U: JuliaInteger,
+{
+    fn construct_type<'target, 'current, 'borrow, T>(
+        target: ExtendedTarget<'target, 'current, 'borrow, T>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        let (output, _) = target.split();
+        // Safety: the type is cached by Julia
+        unsafe {
+            let ty = Self::julia_type(&output).ptr();
+            output.data_from_ptr(ty.cast(), Private)
+        }
+    }
+}
+
+unsafe impl<U> JuliaTypeString for Rational<U>
+where
+    U: JuliaTypeString,
+{
+    fn julia_type_string() -> String {
+        format!("Base.Rational{{{}}}", U::julia_type_string())
+    }
+}
+
+unsafe impl<U> ValidLayout for Rational<U>
+where
+    U: ValidField,
+{
+    fn valid_layout(v: Value) -> bool {
+        <Self as ValidField>::valid_field(v)
+    }
+
+    const IS_REF: bool = false;
+}
+
+unsafe impl<U> ValidField for Rational<U>
+where
+    U: ValidField,
+{
+    fn valid_field(v: Value) -> bool {
+        is_base_type_with_fields::<U>(v, "Rational")
+    }
+}
+
+unsafe impl<U> Unbox for Rational<U>
+where
+    U: ValidField + Clone,
+{
+    type Output = Self;
+}
+
+unsafe impl<U> Typecheck for Rational<U>
+where
+    U: ValidField,
+{
+    fn typecheck(t: DataType) -> bool {
+        <Self as ValidLayout>::valid_layout(t.as_value())
+    }
+}
+
+unsafe impl<U> Unbox for Ratio<U>
+where
+    U: ValidField + Clone,
+{
+    type Output = Self;
+
+    unsafe fn unbox(value: Value) -> Self::Output {
+        Rational::<U>::unbox(value).into()
+    }
+}
+
+unsafe impl<U> Typecheck for Ratio<U>
+where
+    U: ValidField,
+{
+    fn typecheck(t: DataType) -> bool {
+        Rational::<U>::typecheck(t)
+    }
+}
@@ -0,0 +1,137 @@
+//! Wrapper for `SubString{String}`.
+//!
+//! A `SubString` is returned by functions like `split` and by regex matches, it references a
+//! range of code units of its parent string. [`SubString`] has the same layout as
+//! `SubString{String}` and can be used to access this range as a `&str` without copying it, it
+//! can also be used as a field type and as the element type of an array. A `String` or a
+//! `SubString{String}` can be accessed through the same interface with [`StringView`].
+//!
+//! [`StringView`]: crate::wrappers::ptr::string::StringView
+
+use std::str;
+
+use jl_sys::jl_string_type;
+
+use crate::{
+    convert::unbox::Unbox,
+    error::{JlrsError, JlrsResult},
+    layout::{
+        typecheck::Typecheck,
+        valid_layout::{ValidField, ValidLayout},
+    },
+    memory::target::unrooted::Unrooted,
+    private::Private,
+    wrappers::ptr::{
+        datatype::DataType,
+        module::Module,
+        private::WrapperPriv,
+        string::StringRef,
+        union_all::UnionAll,
+        value::Value,
+    },
+};
+
+/// The layout of `SubString{String}`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct SubString<'scope> {
+    /// The parent string.
+    pub string: Option<StringRef<'scope>>,
+    /// The offset of the first code unit in the parent string, in bytes.
+    pub offset: isize,
+    /// The length of the substring, in bytes.
+    pub ncodeunits: isize,
+}
+
+impl<'scope> SubString<'scope> {
+    /// Returns the length of the substring in bytes.
+    pub fn len(self) -> usize {
+        self.ncodeunits as usize
+    }
+
+    /// Returns `true` if the substring is empty.
+    pub fn is_empty(self) -> bool {
+        self.ncodeunits == 0
+    }
+
+    /// Returns the substring as a slice of bytes, this slice borrows from the parent string.
+    ///
+    /// Safety: the parent string is not rooted by this `SubString`, it must be reachable from a
+    /// root until the slice is no longer used, e.g. because the `SubString` has been read from
+    /// rooted Julia data. The range of code units must be in bounds, which is guaranteed if this
+    /// `SubString` has been created by Julia.
+    pub unsafe fn as_bytes(self) -> &'scope [u8] {
+        let parent = self
+            .string
+            .expect("SubString has no parent string")
+            .wrapper()
+            .as_bytes();
+        let start = self.offset as usize;
+        &parent[start..start + self.len()]
+    }
+
+    /// Returns the substring as a string slice, or an error if it the substring contains
+    /// invalid characters.
+    ///
+    /// Safety: the same requirements as [`SubString::as_bytes`] apply.
+    pub unsafe fn as_str(self) -> JlrsResult<&'scope str> {
+        Ok(str::from_utf8(self.as_bytes()).map_err(JlrsError::other)?)
+    }
+
+    /// Returns the substring as a string slice without checking if it's properly encoded.
+    ///
+    /// Safety: the same requirements as [`SubString::as_bytes`] apply, and the substring must be
+    /// properly encoded.
+    pub unsafe fn as_str_unchecked(self) -> &'scope str {
+        str::from_utf8_unchecked(self.as_bytes())
+    }
+}
+
+unsafe impl ValidLayout for SubString<'_> {
+    fn valid_layout(v: Value) -> bool {
+        if let Ok(dt) = v.cast::<DataType>() {
+            Self::typecheck(dt)
+        } else {
+            false
+        }
+    }
+
+    const IS_REF: bool = false;
+}
+
+unsafe impl ValidField for SubString<'_> {
+    fn valid_field(v: Value) -> bool {
+        <Self as ValidLayout>::valid_layout(v)
+    }
+}
+
+unsafe impl Unbox for SubString<'_> {
+    type Output = Self;
+}
+
+// Safety: the type name is compared with the type name of Base.SubString, and the type parameter
+// must be String.
+unsafe impl Typecheck for SubString<'_> {
+    fn typecheck(t: DataType) -> bool {
+        // Safety: SubString is a constant in Base, String is globally rooted.
+        unsafe {
+            let global = Unrooted::new();
+            let is_sub_string = match Module::base(&global).global(&global, "SubString") {
+                Ok(ty) => match ty.value().cast::<UnionAll>() {
+                    Ok(ua) => ua.base_type().type_name() == t.type_name(),
+                    Err(_) => false,
+                },
+                Err(_) => false,
+            };
+
+            if !is_sub_string {
+                return false;
+            }
+
+            match t.parameters().data().as_slice()[0] {
+                Some(param) => param.value().unwrap(Private) == jl_string_type.cast(),
+                None => false,
+            }
+        }
+    }
+}
@@ -25,18 +25,34 @@
 //! # }
 //! ```
 //!
+//! Native Rust tuples with up to 32 elements can be unboxed and typechecked, and converted to
+//! these types with `From`. They don't implement `IntoJulia`, a native tuple must be converted
+//! first, e.g. `Value::new(&mut frame, Tuple2::from((2i32, true)))`. Arrays are mapped to
+//! homogeneous tuples, `[T; N]` corresponds to `NTuple{N, T}`.
+//!
 //! Additionally, [`Tuple` ] can be used to create a tuple from an arbitrary number of `Value`s.
 
-use jl_sys::jl_tuple_typename;
+use std::ptr::NonNull;
+
+use jl_sys::{jl_anytuple_type, jl_apply_tuple_type_v, jl_tuple_typename};
 
 #[cfg(not(all(target_os = "windows", feature = "lts")))]
 use crate::wrappers::ptr::value::ValueResult;
 use crate::{
-    layout::typecheck::Typecheck,
+    convert::{
+        construct_type::{apply_to_constant, ConstructType},
+        into_julia::{IntoJulia, IsBits},
+        julia_struct::JuliaTypeString,
+        unbox::Unbox,
+    },
+    layout::{
+        typecheck::Typecheck,
+        valid_layout::{ValidField, ValidLayout},
+    },
     memory::target::{ExtendedTarget, Target},
     private::Private,
     wrappers::ptr::{
-        datatype::DataType,
+        datatype::{DataType, DataTypeData},
         private::WrapperPriv as _,
         value::{Value, ValueData, MAX_SIZE},
         Wrapper as _,
@@ -185,6 +201,42 @@ macro_rules! impl_tuple {
             }
         }
 
+        // Safety: a tuple whose elements are bits types is a bits type
+        unsafe impl<$($types),+> $crate::convert::into_julia::IsBits for $name<$($types),+>
+        where
+            $($types: $crate::convert::into_julia::IsBits + ::std::fmt::Debug + Clone),+
+        {}
+
+        unsafe impl<$($types),+> $crate::convert::construct_type::ConstructType for $name<$($types),+>
+        where
+            $($types: $crate::convert::construct_type::ConstructType + Clone + ::std::fmt::Debug),+
+        {
+            fn construct_type<'target, 'current, 'borrow, T>(
+                target: $crate::memory::target::ExtendedTarget<'target, 'current, 'borrow, T>,
+            ) -> $crate::wrappers::ptr::value::ValueData<'target, 'static, T>
+            where
+                T: $crate::memory::target::Target<'target>,
+            {
+                unsafe {
+                    $crate::convert::construct_type::apply_to_constant(
+                        target,
+                        ::jl_sys::jl_anytuple_type.cast(),
+                        |frame| [$(<$types as $crate::convert::construct_type::ConstructType>::construct_type(frame.as_extended_target())),+],
+                    )
+                }
+            }
+        }
+
+        unsafe impl<$($types),+> $crate::convert::julia_struct::JuliaTypeString for $name<$($types),+>
+        where
+            $($types: $crate::convert::julia_struct::JuliaTypeString + Clone + ::std::fmt::Debug),+
+        {
+            fn julia_type_string() -> String {
+                let params: &[String] = &[$(<$types as $crate::convert::julia_struct::JuliaTypeString>::julia_type_string()),+];
+                format!("Core.Tuple{{{}}}", params.join(", "))
+            }
+        }
+
         unsafe impl<$($types),+> $crate::layout::valid_layout::ValidLayout for $name<$($types),+>
         where
             $($types: $crate::layout::valid_layout::ValidField + Clone + ::std::fmt::Debug),+
@@ -260,6 +312,62 @@ macro_rules! impl_tuple {
                 <Self as $crate::layout::valid_layout::ValidLayout>::valid_layout(t.as_value())
             }
         }
+
+        impl<$($types),+> From<($($types,)+)> for $name<$($types),+>
+        where
+            $($types: Clone + ::std::fmt::Debug),+
+        {
+            #[allow(non_snake_case)]
+            fn from(($($types,)+): ($($types,)+)) -> Self {
+                $name($($types),+)
+            }
+        }
+
+        impl<$($types),+> From<$name<$($types),+>> for ($($types,)+)
+        where
+            $($types: Clone + ::std::fmt::Debug),+
+        {
+            #[allow(non_snake_case)]
+            fn from($name($($types),+): $name<$($types),+>) -> Self {
+                ($($types,)+)
+            }
+        }
+
+        unsafe impl<$($types),+> $crate::convert::construct_type::ConstructType for ($($types,)+)
+        where
+            $($types: $crate::convert::construct_type::ConstructType + Clone + ::std::fmt::Debug),+
+        {
+            fn construct_type<'target, 'current, 'borrow, T>(
+                target: $crate::memory::target::ExtendedTarget<'target, 'current, 'borrow, T>,
+            ) -> $crate::wrappers::ptr::value::ValueData<'target, 'static, T>
+            where
+                T: $crate::memory::target::Target<'target>,
+            {
+                <$name<$($types),+> as $crate::convert::construct_type::ConstructType>::construct_type(target)
+            }
+        }
+
+        // Safety: the data is unboxed as the equivalent tuple struct, which has the correct
+        // layout.
+        unsafe impl<$($types),+> $crate::convert::unbox::Unbox for ($($types,)+)
+        where
+            $($types: $crate::layout::valid_layout::ValidField + Clone + ::std::fmt::Debug),+
+        {
+            type Output = Self;
+
+            unsafe fn unbox(value: $crate::wrappers::ptr::value::Value) -> Self::Output {
+                <$name<$($types),+> as $crate::convert::unbox::Unbox>::unbox(value).into()
+            }
+        }
+
+        unsafe impl<$($types),+> $crate::layout::typecheck::Typecheck for ($($types,)+)
+        where
+            $($types: $crate::layout::valid_layout::ValidField + Clone + ::std::fmt::Debug),+
+        {
+            fn typecheck(t: $crate::wrappers::ptr::datatype::DataType) -> bool {
+                <$name<$($types),+> as $crate::layout::typecheck::Typecheck>::typecheck(t)
+            }
+        }
     };
     ($name:ident) => {
         #[repr(C)]
@@ -291,6 +399,30 @@ macro_rules! impl_tuple {
             }
         }
 
+        // Safety: the empty tuple is a bits type
+        unsafe impl $crate::convert::into_julia::IsBits for $name {}
+
+        unsafe impl $crate::convert::construct_type::ConstructType for $name {
+            fn construct_type<'target, 'current, 'borrow, T>(
+                target: $crate::memory::target::ExtendedTarget<'target, 'current, 'borrow, T>,
+            ) -> $crate::wrappers::ptr::value::ValueData<'target, 'static, T>
+            where
+                T: $crate::memory::target::Target<'target>,
+            {
+                let (output, _) = target.split();
+                unsafe {
+                    let ptr = $crate::wrappers::ptr::datatype::DataType::emptytuple_type(&output).unwrap_non_null($crate::private::Private);
+                    output.data_from_ptr(ptr.cast(), $crate::private::Private)
+                }
+            }
+        }
+
+        unsafe impl $crate::convert::julia_struct::JuliaTypeString for $name {
+            fn julia_type_string() -> String {
+                String::from("Core.Tuple{}")
+            }
+        }
+
         unsafe impl $crate::layout::valid_layout::ValidLayout for $name {
             fn valid_layout(v: $crate::wrappers::ptr::value::Value) -> bool {
                 if let Ok(dt) = v.cast::<$crate::wrappers::ptr::datatype::DataType>() {
@@ -407,3 +539,110 @@ impl_tuple!(
     Tuple32, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19,
     T20, T21, T22, T23, T24, T25, T26, T27, T28, T29, T30, T31, T32
 );
+
+// Safety: [T; N] and NTuple{N, T} have the same layout
+unsafe impl<U, const N: usize> IntoJulia for [U; N]
+where
+    U: IntoJulia + Copy,
+{
+    fn julia_type<'scope, T>(target: T) -> DataTypeData<'scope, T>
+    where
+        T: Target<'scope>,
+    {
+        // Safety: the element type is concrete, so the tuple type is concrete, too. Concrete
+        // tuple types are cached.
+        unsafe {
+            let elem_ty = U::julia_type(&target);
+            let types = &mut [elem_ty; N];
+            let ty = jl_apply_tuple_type_v(types.as_mut_ptr().cast(), N);
+            target.data_from_ptr(NonNull::new_unchecked(ty), Private)
+        }
+    }
+}
+
+// Safety: NTuple{N, T} is a bits type if T is a bits type
+unsafe impl<U, const N: usize> IsBits for [U; N] where U: IsBits + Copy {}
+
+unsafe impl<U, const N: usize> ConstructType for [U; N]
+where
+    U: ConstructType,
+{
+    fn construct_type<'target, 'current, 'borrow, T>(
+        target: ExtendedTarget<'target, 'current, 'borrow, T>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        unsafe {
+            apply_to_constant(target, jl_anytuple_type.cast(), |frame| {
+                [U::construct_type(frame.as_extended_target()); N]
+            })
+        }
+    }
+}
+
+unsafe impl<U, const N: usize> JuliaTypeString for [U; N]
+where
+    U: JuliaTypeString,
+{
+    fn julia_type_string() -> String {
+        format!("Core.NTuple{{{}, {}}}", N, U::julia_type_string())
+    }
+}
+
+unsafe impl<U, const N: usize> ValidLayout for [U; N]
+where
+    U: ValidField,
+{
+    fn valid_layout(v: Value) -> bool {
+        <Self as ValidField>::valid_field(v)
+    }
+
+    const IS_REF: bool = false;
+}
+
+unsafe impl<U, const N: usize> ValidField for [U; N]
+where
+    U: ValidField,
+{
+    fn valid_field(v: Value) -> bool {
+        if let Ok(dt) = v.cast::<DataType>() {
+            if !dt.is::<Tuple>() {
+                return false;
+            }
+
+            // Safety: the field types of a DataType are globally rooted
+            unsafe {
+                let global = v.unrooted_target();
+                let fieldtypes = dt.field_types(global).wrapper();
+                if fieldtypes.len() != N {
+                    return false;
+                }
+
+                return fieldtypes
+                    .data()
+                    .as_slice()
+                    .iter()
+                    .all(|ty| ty.map_or(false, |ty| U::valid_field(ty.wrapper())));
+            }
+        }
+
+        false
+    }
+}
+
+unsafe impl<U, const N: usize> Unbox for [U; N]
+where
+    U: ValidField + Clone,
+{
+    type Output = Self;
+}
+
+unsafe impl<U, const N: usize> Typecheck for [U; N]
+where
+    U: ValidField,
+{
+    fn typecheck(t: DataType) -> bool {
+        <Self as ValidLayout>::valid_layout(t.as_value())
+    }
+}
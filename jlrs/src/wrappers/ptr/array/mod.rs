@@ -5,9 +5,9 @@
 //! used if the element type implements [`ValidField`].
 //!
 //! Several methods are available to create new arrays. [`Array::new`] lets you create a new array
-//! for any type that implements [`IntoJulia`], while [`Array::new_for`] can be used to create a
+//! for any type that implements [`IsBits`], while [`Array::new_for`] can be used to create a
 //! new array for arbitrary types. These methods allocate a new array, it's also possible to use
-//! data from Rust directly if it implements `IntoJulia`. [`Array::from_vec`] and can be used to
+//! data from Rust directly if it implements `IsBits`. [`Array::from_vec`] and can be used to
 //! move the data from Rust to Julia, while [`Array::from_slice`] can be used to mutably borrow
 //! data from Rust as a Julia array.
 //!
@@ -73,7 +73,10 @@ use self::data::accessor::{
 };
 use super::{union_all::UnionAll, value::ValueRef, Ref};
 use crate::{
-    convert::into_julia::IntoJulia,
+    convert::{
+        construct_type::ConstructType,
+        into_julia::{IntoJulia, IsBits},
+    },
     error::{AccessError, ArrayLayoutError, InstantiationError, JlrsResult, CANNOT_DISPLAY_TYPE},
     layout::{
         typecheck::Typecheck,
@@ -84,24 +87,29 @@ use crate::{
         target::{frame::GcFrame, private::TargetPriv, unrooted::Unrooted, ExtendedTarget, Target},
     },
     private::Private,
-    wrappers::ptr::{
-        array::{
-            data::copied::CopiedArray,
-            dimensions::{ArrayDimensions, Dims},
+    wrappers::{
+        inline::sub_string::SubString,
+        ptr::{
+            array::{
+                data::copied::CopiedArray,
+                dimensions::{ArrayDimensions, Dims},
+            },
+            datatype::DataType,
+            private::WrapperPriv,
+            string::{JuliaString, StringView},
+            type_name::TypeName,
+            union::Union,
+            value::Value,
+            Wrapper,
+            WrapperRef,
         },
-        datatype::DataType,
-        private::WrapperPriv,
-        type_name::TypeName,
-        union::Union,
-        value::Value,
-        Wrapper,
-        WrapperRef,
     },
 };
 
 cfg_if! {
     if #[cfg(not(all(target_os = "windows", feature = "lts")))] {
         use crate::{catch::{catch_exceptions_with_slots, catch_exceptions}};
+        use crate::convert::into_jlrs_result::IntoJlrsResult;
         use std::mem::MaybeUninit;
     }
 }
@@ -126,7 +134,9 @@ pub mod tracked;
 /// compatible.
 ///
 /// If the data isn't inlined, e.g. because it's mutable, each element is stored as a [`Value`].
-/// This data can be accessed using [`Array::value_data`] and [`Array::value_data_mut`].
+/// This data can be accessed using [`Array::value_data`] and [`Array::value_data_mut`]. Arrays
+/// of strings and arrays of arrays can be converted from Rust with [`Array::from_strings`] and
+/// [`Array::from_vecs`], and back to Rust with [`Array::to_strings`] and [`Array::to_vecs`].
 #[derive(Copy, Clone)]
 #[repr(transparent)]
 pub struct Array<'scope, 'data>(
@@ -138,7 +148,7 @@ pub struct Array<'scope, 'data>(
 impl<'data> Array<'_, 'data> {
     /// Allocate a new n-dimensional Julia array of dimensions `dims` for data of type `T`.
     ///
-    /// This method can only be used in combination with types that implement `IntoJulia`. If you
+    /// This method can only be used in combination with types that implement `IsBits`. If you
     /// want to create an array for a type that doesn't implement this trait you must use
     /// [`Array::new_for`].
     ///
@@ -151,7 +161,7 @@ impl<'data> Array<'_, 'data> {
         dims: D,
     ) -> ArrayResult<'target, 'static, S>
     where
-        T: IntoJulia,
+        T: IsBits,
         D: Dims,
         S: Target<'target>,
     {
@@ -221,7 +231,7 @@ impl<'data> Array<'_, 'data> {
         dims: D,
     ) -> ArrayData<'target, 'static, S>
     where
-        T: IntoJulia,
+        T: IsBits,
         D: Dims,
         S: Target<'target>,
     {
@@ -378,9 +388,9 @@ impl<'data> Array<'_, 'data> {
 
     /// Create a new n-dimensional Julia array of dimensions `dims` that borrows data from Rust.
     ///
-    /// This method can only be used in combination with types that implement `IntoJulia`. Because
+    /// This method can only be used in combination with types that implement `IsBits`. Because
     /// the data is borrowed from Rust, operations that can change the size of the array (e.g.
-    /// `push!`) will fail.
+    /// `push!`) will fail. An error is returned if the Julia type of `T` is not a bits type.
     ///
     /// If the array size is too large, Julia will throw an error. This error is caught and
     /// returned.
@@ -391,7 +401,7 @@ impl<'data> Array<'_, 'data> {
         dims: D,
     ) -> JlrsResult<ArrayResult<'target, 'data, S>>
     where
-        T: IntoJulia,
+        T: IsBits,
         D: Dims,
         S: Target<'target>,
     {
@@ -404,6 +414,7 @@ impl<'data> Array<'_, 'data> {
 
         let (output, frame) = target.split();
         frame.scope(|mut frame| {
+            ensure_bits_type::<T>(&frame)?;
             let elty_ptr = T::julia_type(&frame).ptr().cast();
 
             // Safety: The array type is rooted until the array has been constructed, all C API
@@ -461,9 +472,9 @@ impl<'data> Array<'_, 'data> {
 
     /// Create a new n-dimensional Julia array of dimensions `dims` that borrows data from Rust.
     ///
-    /// This method can only be used in combination with types that implement `IntoJulia`. Because
+    /// This method can only be used in combination with types that implement `IsBits`. Because
     /// the data is borrowed from Rust, operations that can change the size of the array (e.g.
-    /// `push!`) will fail.
+    /// `push!`) will fail. An error is returned if the Julia type of `T` is not a bits type.
     ///
     /// Safety: If the array size is too large, Julia will throw an error. This error is not
     /// caught, which is UB from a `ccall`ed function.
@@ -473,7 +484,7 @@ impl<'data> Array<'_, 'data> {
         dims: D,
     ) -> JlrsResult<ArrayData<'target, 'data, S>>
     where
-        T: IntoJulia,
+        T: IsBits,
         D: Dims,
         S: Target<'target>,
     {
@@ -486,6 +497,7 @@ impl<'data> Array<'_, 'data> {
 
         let (output, frame) = target.split();
         frame.scope(|mut frame| {
+            ensure_bits_type::<T>(&frame)?;
             let array_type = jl_apply_array_type(
                 T::julia_type(&frame).ptr().cast().as_ptr(),
                 dims.n_dimensions(),
@@ -525,9 +537,11 @@ impl<'data> Array<'_, 'data> {
     /// Create a new n-dimensional Julia array of dimensions `dims` that takes ownership of Rust
     /// data.
     ///
-    /// This method can only be used in combination with types that implement `IntoJulia`. Because
+    /// This method can only be used in combination with types that implement `IsBits`. Because
     /// the data is allocated by Rust, operations that can change the size of the array (e.g.
-    /// `push!`) will fail.
+    /// `push!`) will fail. The size of the data is reported to the GC when the array is created,
+    /// and reported as freed when the array is. An error is returned if the Julia type of `T` is
+    /// not a bits type.
     ///
     /// If the array size is too large, Julia will throw an error. This error is caught and
     /// returned.
@@ -538,7 +552,7 @@ impl<'data> Array<'_, 'data> {
         dims: D,
     ) -> JlrsResult<ArrayResult<'target, 'static, S>>
     where
-        T: IntoJulia,
+        T: IsBits,
         D: Dims,
         S: Target<'target>,
     {
@@ -551,6 +565,7 @@ impl<'data> Array<'_, 'data> {
 
         let (output, scope) = target.split();
         scope.scope(|mut frame| {
+            ensure_bits_type::<T>(&frame)?;
             let elty_ptr = T::julia_type(&frame).ptr().cast();
             let data = Box::leak(data.into_boxed_slice());
 
@@ -616,9 +631,11 @@ impl<'data> Array<'_, 'data> {
     /// Create a new n-dimensional Julia array of dimensions `dims` that takes ownership of Rust
     /// data.
     ///
-    /// This method can only be used in combination with types that implement `IntoJulia`. Because
+    /// This method can only be used in combination with types that implement `IsBits`. Because
     /// the data is allocated by Rust, operations that can change the size of the array (e.g.
-    /// `push!`) will fail.
+    /// `push!`) will fail. The size of the data is reported to the GC when the array is created,
+    /// and reported as freed when the array is. An error is returned if the Julia type of `T` is
+    /// not a bits type.
     ///
     /// Safety: If the array size is too large, Julia will throw an error. This error is not
     /// caught, which is UB from a `ccall`ed function.
@@ -628,7 +645,7 @@ impl<'data> Array<'_, 'data> {
         dims: D,
     ) -> JlrsResult<ArrayData<'target, 'static, S>>
     where
-        T: IntoJulia,
+        T: IsBits,
         D: Dims,
         S: Target<'target>,
     {
@@ -641,6 +658,7 @@ impl<'data> Array<'_, 'data> {
 
         let (output, scope) = target.split();
         scope.scope(|mut frame| {
+            ensure_bits_type::<T>(&frame)?;
             let array_type = jl_apply_array_type(
                 T::julia_type(&frame).ptr().cast().as_ptr(),
                 dims.n_dimensions(),
@@ -697,6 +715,134 @@ impl<'data> Array<'_, 'data> {
         }
     }
 
+    /// Convert the strings in `strings` to a new `Vector{String}`.
+    ///
+    /// The iterator must report its length correctly.
+    #[cfg(not(all(target_os = "windows", feature = "lts")))]
+    pub fn from_strings<'target, I, S, T>(
+        target: ExtendedTarget<'target, '_, '_, T>,
+        strings: I,
+    ) -> JlrsResult<ArrayData<'target, 'static, T>>
+    where
+        I: IntoIterator<Item = S>,
+        I::IntoIter: ExactSizeIterator,
+        S: AsRef<str>,
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let strings = strings.into_iter();
+            let ty = DataType::string_type(&frame).as_value();
+            let arr =
+                Array::new_for(frame.as_extended_target(), strings.len(), ty).into_jlrs_result()?;
+
+            // Safety: every element is a String.
+            unsafe {
+                fill_ptr_array(&mut frame, arr, strings, |frame, s| {
+                    Ok(JuliaString::new(frame, s).as_value())
+                })?;
+            }
+
+            Ok(arr.root(output))
+        })
+    }
+
+    /// Convert the vectors in `vecs` to a new `Vector{Vector{U}}`. The vectors are converted
+    /// with [`Array::from_vec`], so their contents aren't copied.
+    ///
+    /// The iterator must report its length correctly.
+    #[cfg(not(all(target_os = "windows", feature = "lts")))]
+    pub fn from_vecs<'target, I, U, T>(
+        target: ExtendedTarget<'target, '_, '_, T>,
+        vecs: I,
+    ) -> JlrsResult<ArrayData<'target, 'static, T>>
+    where
+        I: IntoIterator<Item = Vec<U>>,
+        I::IntoIter: ExactSizeIterator,
+        U: IsBits,
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            ensure_bits_type::<U>(&frame)?;
+            let vecs = vecs.into_iter();
+
+            // Safety: the element type is rooted until the array has been allocated.
+            let ty: Value = unsafe {
+                let elty = U::julia_type(&frame).wrapper();
+                let ty = jl_apply_array_type(elty.unwrap(Private).cast(), 1);
+                frame
+                    .as_mut()
+                    .data_from_ptr(NonNull::new_unchecked(ty), Private)
+            };
+
+            let arr =
+                Array::new_for(frame.as_extended_target(), vecs.len(), ty).into_jlrs_result()?;
+
+            // Safety: every element is a Vector{U}.
+            unsafe {
+                fill_ptr_array(&mut frame, arr, vecs, |frame, v| {
+                    let len = v.len();
+                    Ok(Array::from_vec(frame.as_extended_target(), v, len)?
+                        .into_jlrs_result()?
+                        .as_value())
+                })?;
+            }
+
+            Ok(arr.root(output))
+        })
+    }
+
+    /// Convert the contents of an array of strings to a `Vec<String>`. The elements must be
+    /// `String`s or `SubString{String}`s.
+    ///
+    /// Returns `AccessError::UndefRef` if an element is undefined, or an error if an element has
+    /// another type or isn't properly encoded.
+    ///
+    /// Safety: the array must not be mutated while its contents are converted.
+    pub unsafe fn to_strings(self) -> JlrsResult<Vec<String>> {
+        if self.contains_inline::<SubString>() {
+            let data = self.inline_data::<SubString>()?;
+            return data
+                .as_slice()
+                .iter()
+                .map(|s| Ok(s.as_str()?.into()))
+                .collect();
+        }
+
+        let data = self.value_data()?;
+        data.as_slice()
+            .iter()
+            .map(|elem| {
+                let elem = elem.ok_or(AccessError::UndefRef)?.value();
+                Ok(StringView::new(elem)?.as_str()?.into())
+            })
+            .collect()
+    }
+
+    /// Convert the contents of an array of arrays to a `Vec<Vec<U>>`. The data of each array is
+    /// copied with [`Array::copy_inline_data`], arrays with multiple dimensions are flattened
+    /// in column-major order.
+    ///
+    /// Returns `AccessError::UndefRef` if an element is undefined, or an error if an element
+    /// isn't an array or if `U` isn't a valid layout for its elements.
+    ///
+    /// Safety: the arrays must not be mutated while their contents are copied.
+    pub unsafe fn to_vecs<U>(self) -> JlrsResult<Vec<Vec<U>>>
+    where
+        U: 'static + ValidField,
+    {
+        let data = self.value_data()?;
+        data.as_slice()
+            .iter()
+            .map(|elem| {
+                let arr = elem.ok_or(AccessError::UndefRef)?.value().cast::<Array>()?;
+                let (data, _) = arr.copy_inline_data::<U>()?.splat();
+                Ok(data.into_vec())
+            })
+            .collect()
+    }
+
     #[inline(always)]
     pub(crate) fn data_ptr(self) -> *mut c_void {
         // Safety: the pointer points to valid data.
@@ -1400,12 +1546,12 @@ impl<'scope, 'data, T> Copy for TypedArray<'scope, 'data, T> where T: ValidField
 
 impl<'data, T> TypedArray<'_, 'data, T>
 where
-    T: ValidField + IntoJulia,
+    T: ValidField + ConstructType,
 {
     /// Allocate a new n-dimensional Julia array of dimensions `dims` for data of type `T`.
     ///
-    /// This method can only be used in combination with types that implement `IntoJulia`. If you
-    /// want to create an array for a type that doesn't implement this trait you must use
+    /// This method can only be used in combination with types that implement `ConstructType`. If
+    /// you want to create an array for a type that doesn't implement this trait you must use
     /// [`Array::new_for`].
     ///
     /// If the array size is too large, Julia will throw an error. This error is caught and
@@ -1423,9 +1569,12 @@ where
             let (output, frame) = target.split();
             frame
                 .scope(|mut frame| {
+                    let ty = T::construct_type(frame.as_extended_target());
+                    debug_assert!(T::valid_field(ty), "ConstructType is implemented incorrectly");
+
                     let global = frame.unrooted();
                     let target = frame.extended_target(global);
-                    let x = Array::new::<T, _, _>(target, dims);
+                    let x = Array::new_for(target, dims, ty);
 
                     let res = match x {
                         Ok(arr) => Ok(arr
@@ -1458,10 +1607,13 @@ where
         let (output, frame) = target.split();
         frame
             .scope(|mut frame| {
+                let ty = T::construct_type(frame.as_extended_target());
+                debug_assert!(T::valid_field(ty), "ConstructType is implemented incorrectly");
+
                 let inner_output = frame.unrooted();
                 let target = frame.extended_target(inner_output);
 
-                let res = Array::new_unchecked::<T, _, _>(target, dims)
+                let res = Array::new_for_unchecked(target, dims, ty)
                     .wrapper()
                     .as_typed_unchecked::<T>();
 
@@ -1469,12 +1621,17 @@ where
             })
             .unwrap()
     }
+}
 
+impl<'data, T> TypedArray<'_, 'data, T>
+where
+    T: ValidField + IsBits,
+{
     /// Create a new n-dimensional Julia array of dimensions `dims` that borrows data from Rust.
     ///
-    /// This method can only be used in combination with types that implement `IntoJulia`. Because
+    /// This method can only be used in combination with types that implement `IsBits`. Because
     /// the data is borrowed from Rust, operations that can change the size of the array (e.g.
-    /// `push!`) will fail.
+    /// `push!`) will fail. An error is returned if the Julia type of `T` is not a bits type.
     ///
     /// If the array size is too large, Julia will throw an error. This error is caught and
     /// returned.
@@ -1485,7 +1642,7 @@ where
         dims: D,
     ) -> JlrsResult<TypedArrayResult<'target, 'data, S, T>>
     where
-        T: IntoJulia,
+        T: IsBits,
         D: Dims,
         S: Target<'target>,
     {
@@ -1510,9 +1667,9 @@ where
 
     /// Create a new n-dimensional Julia array of dimensions `dims` that borrows data from Rust.
     ///
-    /// This method can only be used in combination with types that implement `IntoJulia`. Because
+    /// This method can only be used in combination with types that implement `IsBits`. Because
     /// the data is borrowed from Rust, operations that can change the size of the array (e.g.
-    /// `push!`) will fail.
+    /// `push!`) will fail. An error is returned if the Julia type of `T` is not a bits type.
     ///
     /// Safety: If the array size is too large, Julia will throw an error. This error is not
     /// caught, which is UB from a `ccall`ed function.
@@ -1522,7 +1679,7 @@ where
         dims: D,
     ) -> JlrsResult<TypedArrayData<'target, 'data, S, T>>
     where
-        T: IntoJulia,
+        T: IsBits,
         D: Dims,
         S: Target<'target>,
     {
@@ -1542,9 +1699,11 @@ where
     /// Create a new n-dimensional Julia array of dimensions `dims` that takes ownership of Rust
     /// data.
     ///
-    /// This method can only be used in combination with types that implement `IntoJulia`. Because
+    /// This method can only be used in combination with types that implement `IsBits`. Because
     /// the data is allocated by Rust, operations that can change the size of the array (e.g.
-    /// `push!`) will fail.
+    /// `push!`) will fail. The size of the data is reported to the GC when the array is created,
+    /// and reported as freed when the array is. An error is returned if the Julia type of `T` is
+    /// not a bits type.
     ///
     /// If the array size is too large, Julia will throw an error. This error is caught and
     /// returned.
@@ -1555,7 +1714,7 @@ where
         dims: D,
     ) -> JlrsResult<TypedArrayResult<'target, 'static, S, T>>
     where
-        T: IntoJulia,
+        T: IsBits,
         D: Dims,
         S: Target<'target>,
     {
@@ -1581,9 +1740,11 @@ where
     /// Create a new n-dimensional Julia array of dimensions `dims` that takes ownership of Rust
     /// data.
     ///
-    /// This method can only be used in combination with types that implement `IntoJulia`. Because
+    /// This method can only be used in combination with types that implement `IsBits`. Because
     /// the data is allocated by Rust, operations that can change the size of the array (e.g.
-    /// `push!`) will fail.
+    /// `push!`) will fail. The size of the data is reported to the GC when the array is created,
+    /// and reported as freed when the array is. An error is returned if the Julia type of `T` is
+    /// not a bits type.
     ///
     /// Safety: If the array size is too large, Julia will throw an error. This error is not
     /// caught, which is UB from a `ccall`ed function.
@@ -1593,7 +1754,7 @@ where
         dims: D,
     ) -> JlrsResult<TypedArrayData<'target, 'static, S, T>>
     where
-        T: IntoJulia,
+        T: IsBits,
         D: Dims,
         S: Target<'target>,
     {
@@ -2221,6 +2382,21 @@ thread_local! {
     };
 }
 
+// Arrays created from Rust data store their elements inline, which is only valid if the element
+// type is a bits type. `IsBits` guarantees this for the implementations provided by jlrs, derived
+// implementations can't check if the Julia type is mutable.
+fn ensure_bits_type<T: IsBits>(frame: &GcFrame) -> JlrsResult<()> {
+    // Safety: the type of T is only used to check if it's a bits type.
+    let ty = unsafe { T::julia_type(frame).wrapper() };
+    if !ty.is_bits() {
+        Err(ArrayLayoutError::NotBits {
+            element_type: ty.display_string_or(CANNOT_DISPLAY_TYPE),
+        })?;
+    }
+
+    Ok(())
+}
+
 // Safety: dims.m_dimensions() <= 8
 unsafe fn small_dim_tuple<'scope, D>(
     frame: &mut GcFrame<'scope>,
@@ -2281,7 +2457,8 @@ unsafe extern "C" fn droparray<T>(a: Array) {
     let data_ptr = arr_ref.data.cast::<T>();
     arr_ref.data = null_mut();
 
-    // Set all dims to 0
+    // Set all dims to 0. The length is left as is because the GC uses it to account for the
+    // freed data when the array is swept.
     let arr_ptr = arr_nn_ptr.as_ptr();
     let dims_ptr = jl_array_dims_ptr(arr_ptr);
     let n_dims = jl_array_ndims(arr_ptr);
@@ -2370,3 +2547,27 @@ pub type TypedArrayData<'target, 'data, T, U> =
 /// type `T`.
 pub type TypedArrayResult<'target, 'data, T, U> =
     <T as TargetType<'target>>::Result<'data, TypedArray<'target, 'data, U>>;
+
+// Stores the values returned by `convert` in `arr`, a newly allocated array whose elements are
+// stored as pointers. The write barrier is inserted by the accessor.
+#[cfg(not(all(target_os = "windows", feature = "lts")))]
+unsafe fn fill_ptr_array<'data, I, F>(
+    frame: &mut GcFrame,
+    mut arr: Array<'_, 'data>,
+    items: I,
+    mut convert: F,
+) -> JlrsResult<()>
+where
+    I: Iterator,
+    F: for<'inner> FnMut(&mut GcFrame<'inner>, I::Item) -> JlrsResult<Value<'inner, 'data>>,
+{
+    let mut accessor = arr.value_data_mut()?;
+    for (idx, item) in items.enumerate() {
+        frame.scope(|mut frame| {
+            let value = convert(&mut frame, item)?;
+            accessor.set(idx, Some(value))
+        })?;
+    }
+
+    Ok(())
+}
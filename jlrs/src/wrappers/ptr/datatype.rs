@@ -1583,4 +1583,37 @@ impl DatatypeLayout<'_> {
     pub fn fielddesc_type(self) -> u16 {
         unsafe { self.0.as_ref().fielddesc_type() }
     }
+
+    /// Returns the offset in bytes of the `idx`-th pointer stored in an instance of the
+    /// `DataType`.
+    ///
+    /// Panics if `idx` is not smaller than the number of pointers.
+    pub fn pointer_offset(self, idx: usize) -> usize {
+        assert!(
+            idx < self.n_pointers() as usize,
+            "Pointer index out of bounds"
+        );
+
+        // Safety: the pointer offsets are stored directly after the field descriptors, their
+        // size depends on the field descriptor type.
+        unsafe {
+            let n_fields = self.n_fields() as usize;
+            let fielddesc_type = self.fielddesc_type();
+            let fielddesc_size = 2 << fielddesc_type;
+            let offsets = self
+                .0
+                .as_ptr()
+                .add(1)
+                .cast::<u8>()
+                .add(fielddesc_size * n_fields);
+
+            let offset = match fielddesc_type {
+                0 => offsets.add(idx).read() as usize,
+                1 => offsets.cast::<u16>().add(idx).read() as usize,
+                _ => offsets.cast::<u32>().add(idx).read() as usize,
+            };
+
+            offset * std::mem::size_of::<usize>()
+        }
+    }
 }
@@ -0,0 +1,405 @@
+//! Wrapper for `Dict`.
+//!
+//! A `Dict` is not a builtin type, all operations call functions defined in the `Base` module.
+//! Keys and values can be converted from Rust with [`IntoJulia`] and to Rust with [`Unbox`],
+//! which makes it possible to convert a `HashMap` or `BTreeMap` to a `Dict` and back. Keys and
+//! values that don't implement [`IntoJulia`], like strings, can be converted manually and
+//! inserted with [`Dict::from_values`]:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! # use jlrs::wrappers::ptr::dict::Dict;
+//! # use std::collections::HashMap;
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         let mut map = HashMap::new();
+//!         map.insert(1i64, 2.0f64);
+//!         map.insert(2, 4.0);
+//!
+//!         let dict = Dict::from_iter(frame.as_extended_target(), map.clone())?;
+//!         assert_eq!(dict.len(), 2);
+//!
+//!         let converted = dict.to_hash_map::<i64, f64>(&mut frame)?;
+//!         assert_eq!(converted, map);
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+//!
+//! [`IntoJulia`]: crate::convert::into_julia::IntoJulia
+//! [`Unbox`]: crate::convert::unbox::Unbox
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+use jl_sys::jl_value_t;
+
+use crate::{
+    call::Call,
+    convert::{
+        construct_type::ConstructType,
+        into_jlrs_result::IntoJlrsResult,
+        into_julia::IntoJulia,
+        unbox::Unbox,
+    },
+    error::{AccessError, JlrsResult},
+    layout::{
+        typecheck::Typecheck,
+        valid_layout::{ValidField, ValidLayout},
+    },
+    memory::target::{frame::GcFrame, target_type::TargetType, ExtendedTarget, Target},
+    private::Private,
+    wrappers::ptr::{
+        datatype::DataType,
+        module::{base_function, base_type_constructor, collect_any, is_base_type},
+        private::WrapperPriv,
+        union_all::UnionAll,
+        value::{Value, ValueData},
+        Ref,
+        Wrapper,
+    },
+};
+
+/// A Julia `Dict`.
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct Dict<'scope, 'data>(
+    NonNull<jl_value_t>,
+    PhantomData<&'scope ()>,
+    PhantomData<&'data mut ()>,
+);
+
+impl<'scope, 'data> Dict<'scope, 'data> {
+    /// Create a new, empty `Dict{K, V}`.
+    pub fn new<'target, K, V, T>(
+        target: ExtendedTarget<'target, '_, '_, T>,
+    ) -> JlrsResult<DictData<'target, 'static, T>>
+    where
+        K: IntoJulia + ConstructType,
+        V: IntoJulia + ConstructType,
+        T: Target<'target>,
+    {
+        Self::from_iter(target, std::iter::empty::<(K, V)>())
+    }
+
+    /// Create a new `Dict{K, V}` that contains all key-value pairs in `iter`, e.g. a `HashMap`
+    /// or a `BTreeMap`.
+    pub fn from_iter<'target, K, V, I, T>(
+        target: ExtendedTarget<'target, '_, '_, T>,
+        iter: I,
+    ) -> JlrsResult<DictData<'target, 'static, T>>
+    where
+        K: IntoJulia + ConstructType,
+        V: IntoJulia + ConstructType,
+        I: IntoIterator<Item = (K, V)>,
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let key_ty = K::construct_type(frame.as_extended_target());
+            let value_ty = V::construct_type(frame.as_extended_target());
+
+            // Safety: Dict{K, V}() only allocates a new dictionary, setindex! only stores the
+            // converted key and value.
+            unsafe {
+                let dict = base_type_constructor(&frame, "Dict")?
+                    .apply_type(&mut frame, [key_ty, value_ty])
+                    .into_jlrs_result()?
+                    .call0(&mut frame)
+                    .into_jlrs_result()?;
+
+                let setindex = base_function(&frame, "setindex!")?;
+                for (key, value) in iter {
+                    frame.scope(|mut frame| {
+                        let key = Value::new(&mut frame, key);
+                        let value = Value::new(&mut frame, value);
+                        setindex
+                            .call3(&mut frame, dict, value, key)
+                            .into_jlrs_result()?;
+                        Ok(())
+                    })?;
+                }
+
+                Ok(dict.cast_unchecked::<Dict>().root(output))
+            }
+        })
+    }
+
+    /// Create a new `Dict{K, V}` that contains all key-value pairs in `iter`, the keys and values
+    /// must already have been converted to Julia.
+    ///
+    /// Unlike [`Dict::from_iter`], `K` and `V` don't have to implement `IntoJulia`. This can be
+    /// used to create a `Dict` with keys and values that are converted in another way, e.g. a
+    /// `Dict{String, Int64}` whose keys are created with `JuliaString::new`. If Julia throws an
+    /// exception because a key or value can't be converted to `K` or `V` it's caught and
+    /// returned as an error.
+    pub fn from_values<'target, 'value, K, V, I, T>(
+        target: ExtendedTarget<'target, '_, '_, T>,
+        iter: I,
+    ) -> JlrsResult<DictData<'target, 'static, T>>
+    where
+        K: ConstructType,
+        V: ConstructType,
+        I: IntoIterator<Item = (Value<'value, 'static>, Value<'value, 'static>)>,
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let key_ty = K::construct_type(frame.as_extended_target());
+            let value_ty = V::construct_type(frame.as_extended_target());
+
+            // Safety: Dict{K, V}() only allocates a new dictionary, setindex! only stores the
+            // key and value.
+            unsafe {
+                let dict = base_type_constructor(&frame, "Dict")?
+                    .apply_type(&mut frame, [key_ty, value_ty])
+                    .into_jlrs_result()?
+                    .call0(&mut frame)
+                    .into_jlrs_result()?;
+
+                let setindex = base_function(&frame, "setindex!")?;
+                for (key, value) in iter {
+                    frame.scope(|mut frame| {
+                        setindex
+                            .call3(&mut frame, dict, value, key)
+                            .into_jlrs_result()?;
+                        Ok(())
+                    })?;
+                }
+
+                Ok(dict.cast_unchecked::<Dict>().root(output))
+            }
+        })
+    }
+
+    /// Returns the number of key-value pairs in this `Dict`.
+    pub fn len(self) -> usize {
+        self.as_value()
+            .field_accessor()
+            .field("count")
+            .and_then(|count| count.access::<isize>())
+            .expect("Dict has no count field") as usize
+    }
+
+    /// Returns `true` if this `Dict` is empty.
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if this `Dict` contains `key`.
+    pub fn contains_key(self, frame: &mut GcFrame, key: Value) -> JlrsResult<bool> {
+        frame.scope(|mut frame| {
+            // Safety: haskey doesn't mutate the dictionary.
+            unsafe {
+                base_function(&frame, "haskey")?
+                    .call2(&mut frame, self.as_value(), key)
+                    .into_jlrs_result()?
+                    .unbox::<bool>()
+                    .map(|b| b.as_bool())
+            }
+        })
+    }
+
+    /// Returns the value associated with `key`, or `None` if the key doesn't exist.
+    pub fn get<'target, T>(
+        self,
+        target: ExtendedTarget<'target, '_, '_, T>,
+        key: Value<'_, 'data>,
+    ) -> JlrsResult<Option<ValueData<'target, 'data, T>>>
+    where
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            if !self.contains_key(&mut frame, key)? {
+                return Ok(None);
+            }
+
+            // Safety: getindex doesn't mutate the dictionary.
+            unsafe {
+                let value = base_function(&frame, "getindex")?
+                    .call2(&mut frame, self.as_value(), key)
+                    .into_jlrs_result()?;
+                Ok(Some(value.root(output)))
+            }
+        })
+    }
+
+    /// Insert `value` with the key `key`, replacing the existing value if the key already
+    /// exists. An error is returned if the key or value can't be converted to the key or value
+    /// type of this `Dict`.
+    ///
+    /// Safety: mutating Julia data is generally unsafe because it can't be guaranteed mutating
+    /// this value is allowed.
+    pub unsafe fn insert(
+        self,
+        frame: &mut GcFrame,
+        key: Value<'_, 'data>,
+        value: Value<'_, 'data>,
+    ) -> JlrsResult<()> {
+        frame.scope(|mut frame| {
+            base_function(&frame, "setindex!")?
+                .call3(&mut frame, self.as_value(), value, key)
+                .into_jlrs_result()?;
+            Ok(())
+        })
+    }
+
+    /// Remove `key` and its associated value from this `Dict`. Nothing happens if the key
+    /// doesn't exist.
+    ///
+    /// Safety: mutating Julia data is generally unsafe because it can't be guaranteed mutating
+    /// this value is allowed.
+    pub unsafe fn remove(self, frame: &mut GcFrame, key: Value) -> JlrsResult<()> {
+        frame.scope(|mut frame| {
+            base_function(&frame, "delete!")?
+                .call2(&mut frame, self.as_value(), key)
+                .into_jlrs_result()?;
+            Ok(())
+        })
+    }
+
+    /// Call `func` for every key-value pair in this `Dict`.
+    ///
+    /// The keys and values are copied to two new arrays before `func` is called, mutating this
+    /// `Dict` in `func` doesn't affect the iteration.
+    pub fn for_each<F>(self, frame: &mut GcFrame, mut func: F) -> JlrsResult<()>
+    where
+        F: FnMut(Value<'_, 'data>, Value<'_, 'data>) -> JlrsResult<()>,
+    {
+        frame.scope(|mut frame| {
+            // Safety: keys and values don't mutate the dictionary.
+            unsafe {
+                let keys = base_function(&frame, "keys")?
+                    .call1(&mut frame, self.as_value())
+                    .into_jlrs_result()?;
+                let keys = collect_any(&mut frame, keys)?;
+
+                let values = base_function(&frame, "values")?
+                    .call1(&mut frame, self.as_value())
+                    .into_jlrs_result()?;
+                let values = collect_any(&mut frame, values)?;
+
+                let keys = keys.value_data()?;
+                let values = values.value_data()?;
+                for (key, value) in keys.as_slice().iter().zip(values.as_slice().iter()) {
+                    let key = key.ok_or(AccessError::UndefRef)?.value();
+                    let value = value.ok_or(AccessError::UndefRef)?.value();
+                    func(key, value)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Convert this `Dict` to a `HashMap`, every key and value is unboxed as `K` and `V`.
+    pub fn to_hash_map<K, V>(self, frame: &mut GcFrame) -> JlrsResult<HashMap<K::Output, V::Output>>
+    where
+        K: Unbox + Typecheck,
+        V: Unbox + Typecheck,
+        K::Output: Eq + Hash,
+    {
+        let mut map = HashMap::with_capacity(self.len());
+        self.for_each(frame, |key, value| {
+            map.insert(key.unbox::<K>()?, value.unbox::<V>()?);
+            Ok(())
+        })?;
+
+        Ok(map)
+    }
+
+    /// Convert this `Dict` to a `BTreeMap`, every key and value is unboxed as `K` and `V`.
+    pub fn to_btree_map<K, V>(
+        self,
+        frame: &mut GcFrame,
+    ) -> JlrsResult<BTreeMap<K::Output, V::Output>>
+    where
+        K: Unbox + Typecheck,
+        V: Unbox + Typecheck,
+        K::Output: Ord,
+    {
+        let mut map = BTreeMap::new();
+        self.for_each(frame, |key, value| {
+            map.insert(key.unbox::<K>()?, value.unbox::<V>()?);
+            Ok(())
+        })?;
+
+        Ok(map)
+    }
+}
+
+// Safety: the type name is compared with the type name of Base.Dict
+unsafe impl Typecheck for Dict<'_, '_> {
+    fn typecheck(t: DataType) -> bool {
+        is_base_type(t, "Dict")
+    }
+}
+
+impl_debug!(Dict<'_, '_>);
+
+impl<'scope, 'data> WrapperPriv<'scope, 'data> for Dict<'scope, 'data> {
+    type Wraps = jl_value_t;
+    type TypeConstructorPriv<'target, 'da> = Dict<'target, 'da>;
+    const NAME: &'static str = "Dict";
+
+    // Safety: `inner` must not have been freed yet, the result must never be
+    // used after the GC might have freed it.
+    unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+        Self(inner, PhantomData, PhantomData)
+    }
+
+    fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+        self.0
+    }
+}
+
+/// A reference to a [`Dict`] that has not been explicitly rooted.
+pub type DictRef<'scope, 'data> = Ref<'scope, 'data, Dict<'scope, 'data>>;
+
+unsafe impl ValidLayout for DictRef<'_, '_> {
+    fn valid_layout(v: Value) -> bool {
+        if let Ok(dt) = v.cast::<DataType>() {
+            dt.is::<Dict>()
+        } else if let Ok(ua) = v.cast::<UnionAll>() {
+            ua.base_type().is::<Dict>()
+        } else {
+            false
+        }
+    }
+
+    const IS_REF: bool = true;
+}
+
+unsafe impl ValidField for Option<DictRef<'_, '_>> {
+    fn valid_field(v: Value) -> bool {
+        if let Ok(dt) = v.cast::<DataType>() {
+            dt.is::<Dict>()
+        } else if let Ok(ua) = v.cast::<UnionAll>() {
+            ua.base_type().is::<Dict>()
+        } else {
+            false
+        }
+    }
+}
+
+/// `Dict` or `DictRef`, depending on the target type `T`.
+pub type DictData<'target, 'data, T> =
+    <T as TargetType<'target>>::Data<'data, Dict<'target, 'data>>;
+
+/// `JuliaResult<Dict>` or `JuliaResultRef<DictRef>`, depending on the target type `T`.
+pub type DictResult<'target, 'data, T> =
+    <T as TargetType<'target>>::Result<'data, Dict<'target, 'data>>;
@@ -1,4 +1,9 @@
 //! Wrapper for `Expr`.
+//!
+//! Expressions can be built, parsed and evaluated with the functionality in
+//! [`convert::julia_expr`].
+//!
+//! [`convert::julia_expr`]: crate::convert::julia_expr
 
 use std::{marker::PhantomData, ptr::NonNull};
 
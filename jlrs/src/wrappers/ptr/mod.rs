@@ -69,10 +69,17 @@ macro_rules! impl_debug {
 
 pub mod array;
 pub mod datatype;
+#[cfg(not(all(target_os = "windows", feature = "lts")))]
+pub mod dict;
 pub mod function;
 #[cfg(feature = "internal-types")]
 pub mod internal;
 pub mod module;
+pub mod named_tuple;
+#[cfg(not(all(target_os = "windows", feature = "lts")))]
+pub mod ref_value;
+#[cfg(not(all(target_os = "windows", feature = "lts")))]
+pub mod set;
 pub mod simple_vector;
 pub mod string;
 pub mod symbol;
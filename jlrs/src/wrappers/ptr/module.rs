@@ -4,7 +4,7 @@
 //! modules, `Main`, `Base` and `Core`. Any Julia code that you include in jlrs is made available
 //! relative to the `Main` module.
 
-use std::{marker::PhantomData, ptr::NonNull};
+use std::{collections::HashMap, marker::PhantomData, ptr::NonNull, sync::RwLock};
 
 use jl_sys::{
     jl_base_module,
@@ -25,20 +25,27 @@ use super::{
 };
 use crate::{
     call::Call,
-    convert::to_symbol::ToSymbol,
+    convert::{into_jlrs_result::IntoJlrsResult, to_symbol::ToSymbol},
     error::{AccessError, JlrsResult, TypeError},
     impl_julia_typecheck,
-    memory::target::Target,
+    memory::target::{frame::GcFrame, unrooted::Unrooted, Target},
     private::Private,
     wrappers::ptr::{
+        array::Array,
+        datatype::DataType,
         function::Function,
         private::WrapperPriv,
         symbol::Symbol,
+        union_all::UnionAll,
         value::{LeakedValue, Value},
         Wrapper as _,
     },
 };
 
+// Functions found with `Module::cached_function`, keyed on the addresses of the module and the
+// symbol.
+static FUNCTION_CACHE: RwLock<Option<HashMap<(usize, usize), usize>>> = RwLock::new(None);
+
 /// Functionality in Julia can be accessed through its module system. You can get a handle to the
 /// three standard modules, `Main`, `Base`, and `Core` and access their submodules through them.
 /// If you include your own Julia code with [`Julia::include`], [`AsyncJulia::include`], or
@@ -358,6 +365,92 @@ impl<'scope> Module<'scope> {
         }
     }
 
+    /// Returns the function named `name` in this module, the result is cached if the function is
+    /// a constant.
+    ///
+    /// The first time a function is looked up this method behaves like [`Module::function`], if
+    /// the global is a constant it's cached. The cache is keyed on the module and the `Symbol`,
+    /// so `name` is still converted to a `Symbol` every time this method is called unless it's
+    /// a [`StaticSymbol`]. Cached functions and their modules are rooted for the remaining
+    /// lifetime of the program, if the module is redefined the cached function from the old
+    /// module is still returned. Functions are only cached if the `Jlrs` module has been loaded,
+    /// which isn't the case if jlrs is only used through `ccall`.
+    ///
+    /// [`StaticSymbol`]: crate::wrappers::ptr::symbol::StaticSymbol
+    pub fn cached_function<'target, N, T>(
+        self,
+        target: T,
+        name: N,
+    ) -> JlrsResult<FunctionData<'target, 'static, T>>
+    where
+        N: ToSymbol,
+        T: Target<'target>,
+    {
+        // Safety: the pointer points to valid data, cached functions are rooted in
+        // Jlrs.rooted_functions.
+        unsafe {
+            let symbol = name.to_symbol_priv(Private);
+            let key = (
+                self.unwrap(Private) as usize,
+                symbol.unwrap(Private) as usize,
+            );
+
+            {
+                let cache = FUNCTION_CACHE.read().expect("Lock poisoned");
+                if let Some(func) = cache.as_ref().and_then(|cache| cache.get(&key)) {
+                    let func = NonNull::new_unchecked(*func as *mut _);
+                    return Ok(target.data_from_ptr(func, Private));
+                }
+            }
+
+            let func = self.function(&target, symbol)?.wrapper();
+            let is_const = Module::base(&target)
+                .function(&target, "isconst")?
+                .wrapper()
+                .call2(&target, self.as_value(), symbol.as_value())
+                .map(|v| v.value().unbox::<bool>().map(|b| b.as_bool()));
+
+            if let Ok(Ok(true)) = is_const {
+                // The module and the function are pushed to Jlrs.rooted_functions so neither can
+                // be freed while they're cached, which would allow their addresses to be reused.
+                // If the Jlrs module hasn't been loaded, e.g. because jlrs is only used through
+                // ccall, the function can't be rooted and isn't cached.
+                let rooted_functions = Module::main(&target)
+                    .submodule(&target, "Jlrs")
+                    .and_then(|jlrs| jlrs.wrapper().global(&target, "rooted_functions"));
+
+                if let Ok(rooted_functions) = rooted_functions {
+                    let rooted = Module::base(&target)
+                        .function(&target, "push!")?
+                        .wrapper()
+                        .call3(
+                            &target,
+                            rooted_functions.wrapper(),
+                            self.as_value(),
+                            func.as_value(),
+                        );
+
+                    // Julia must not be called while the lock is held, a thread that's blocked
+                    // on it can't reach a safepoint if the GC is triggered. Another thread might
+                    // have cached this function in the meantime, that entry is kept.
+                    if rooted.is_ok() {
+                        let func = *FUNCTION_CACHE
+                            .write()
+                            .expect("Lock poisoned")
+                            .get_or_insert_with(HashMap::new)
+                            .entry(key)
+                            .or_insert(func.unwrap(Private) as usize);
+
+                        let func = NonNull::new_unchecked(func as *mut _);
+                        return Ok(target.data_from_ptr(func, Private));
+                    }
+                }
+            }
+
+            Ok(target.data_from_ptr(func.unwrap_non_null(Private), Private))
+        }
+    }
+
     /// Convert `self` to a `LeakedValue`.
     pub fn as_leaked(self) -> LeakedValue {
         // Safety: the pointer points to valid data
@@ -426,3 +519,61 @@ pub type ModuleData<'target, T> = <T as TargetType<'target>>::Data<'static, Modu
 
 /// `JuliaResult<Module>` or `JuliaResultRef<ModuleRef>`, depending on the target type `T`.
 pub type ModuleResult<'target, T> = <T as TargetType<'target>>::Result<'static, Module<'target>>;
+
+// Returns true if `t` is an instance of the UnionAll `name` defined in Base.
+pub(crate) fn is_base_type(t: DataType, name: &str) -> bool {
+    // Safety: the global is a constant in Base, it's never freed.
+    unsafe {
+        let global = Unrooted::new();
+        match Module::base(&global).global(&global, name) {
+            Ok(ty) => match ty.value().cast::<UnionAll>() {
+                Ok(ua) => ua.base_type().type_name() == t.type_name(),
+                Err(_) => false,
+            },
+            Err(_) => false,
+        }
+    }
+}
+
+pub(crate) fn base_type_constructor<'target, T: Target<'target>>(
+    target: &T,
+    name: &str,
+) -> JlrsResult<Value<'target, 'static>> {
+    // Safety: the global is a constant in Base, it's never freed.
+    unsafe { Ok(Module::base(target).global(target, name)?.value()) }
+}
+
+pub(crate) fn base_function<'target, T: Target<'target>>(
+    target: &T,
+    name: &str,
+) -> JlrsResult<Function<'target, 'static>> {
+    // Safety: the function is a constant in Base, it's never freed.
+    unsafe { Ok(Module::base(target).function(target, name)?.wrapper()) }
+}
+
+pub(crate) fn jlrs_function<'target, T: Target<'target>>(
+    target: &T,
+    name: &str,
+) -> JlrsResult<Function<'target, 'static>> {
+    // Safety: the function is a constant in the Jlrs module, it's never freed.
+    unsafe {
+        Ok(Module::main(target)
+            .submodule(target, "Jlrs")?
+            .wrapper()
+            .function(target, name)?
+            .wrapper())
+    }
+}
+
+// Collects the contents of `itr` in a `Vector{Any}`.
+pub(crate) unsafe fn collect_any<'target, 'data>(
+    frame: &mut GcFrame<'target>,
+    itr: Value<'_, 'data>,
+) -> JlrsResult<Array<'target, 'data>> {
+    let collect = jlrs_function(&*frame, "collectany")?;
+
+    Ok(collect
+        .call1(&mut *frame, itr)
+        .into_jlrs_result()?
+        .cast_unchecked::<Array>())
+}
@@ -0,0 +1,338 @@
+//! Wrapper for `NamedTuple`.
+//!
+//! Every `NamedTuple` is an immutable instance of some concrete `NamedTuple{names, T}`. Unlike
+//! the [`NamedTuple`] typecheck, which only checks if a type is a named tuple, this wrapper
+//! provides access to the fields of a named tuple by name and can convert it to and from Rust
+//! data with the [`IntoNamedTuple`] and [`FromNamedTuple`] traits. These traits are implemented
+//! for `HashMap<String, V>` and `BTreeMap<String, V>`, and can be derived for structs with named
+//! fields if the `jlrs-derive` feature is enabled. The derived implementations map every field of
+//! the struct to the field of the named tuple with the same name:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! # use jlrs::wrappers::ptr::named_tuple::{FromNamedTuple, IntoNamedTuple};
+//! # use std::collections::BTreeMap;
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         let mut map = BTreeMap::new();
+//!         map.insert("a".to_string(), 1i64);
+//!         map.insert("b".to_string(), 2);
+//!
+//!         let named_tuple = map.clone().into_named_tuple(frame.as_extended_target())?;
+//!         assert_eq!(named_tuple.len(), 2);
+//!
+//!         let converted = BTreeMap::<String, i64>::from_named_tuple(&mut frame, named_tuple)?;
+//!         assert_eq!(converted, map);
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+//!
+//! [`NamedTuple`]: crate::layout::typecheck::NamedTuple
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+use jl_sys::{jl_namedtuple_typename, jl_value_t};
+
+use crate::{
+    convert::{into_julia::IntoJulia, to_symbol::ToSymbol, unbox::Unbox},
+    error::JlrsResult,
+    layout::{
+        typecheck::Typecheck,
+        valid_layout::{ValidField, ValidLayout},
+    },
+    memory::target::{frame::GcFrame, target_type::TargetType, ExtendedTarget, Target},
+    private::Private,
+    wrappers::ptr::{
+        datatype::DataType,
+        private::WrapperPriv,
+        symbol::Symbol,
+        union_all::UnionAll,
+        value::{Value, ValueData},
+        Ref,
+        Wrapper,
+    },
+};
+
+/// A Julia `NamedTuple`.
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct NamedTuple<'scope, 'data>(
+    NonNull<jl_value_t>,
+    PhantomData<&'scope ()>,
+    PhantomData<&'data ()>,
+);
+
+impl<'scope, 'data> NamedTuple<'scope, 'data> {
+    /// Create a new `NamedTuple` with the fields `names` and the values `values`. An error is
+    /// returned if the number of names and values is different.
+    pub fn new<'target, 'value, N, S, V, T>(
+        target: ExtendedTarget<'target, '_, '_, T>,
+        names: N,
+        values: V,
+    ) -> JlrsResult<NamedTupleData<'target, 'data, T>>
+    where
+        N: AsRef<[S]>,
+        S: ToSymbol,
+        V: AsRef<[Value<'value, 'data>]>,
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let named_tuple = Value::new_named_tuple(frame.as_extended_target(), names, values)?;
+
+            // Safety: new_named_tuple returns a named tuple
+            unsafe { Ok(named_tuple.cast_unchecked::<NamedTuple>().root(output)) }
+        })
+    }
+
+    /// Returns the names of the fields of this `NamedTuple`.
+    pub fn names(self) -> &'scope [Symbol<'scope>] {
+        self.as_value().field_names()
+    }
+
+    /// Returns the number of fields of this `NamedTuple`.
+    pub fn len(self) -> usize {
+        self.as_value().n_fields()
+    }
+
+    /// Returns `true` if this `NamedTuple` has no fields.
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value of the field `name`, or `None` if this `NamedTuple` has no such field.
+    pub fn get<'target, N, T>(
+        self,
+        target: T,
+        name: N,
+    ) -> JlrsResult<Option<ValueData<'target, 'data, T>>>
+    where
+        N: ToSymbol,
+        T: Target<'target>,
+    {
+        // Safety: the symbol is only used to look up the field
+        let name = unsafe { name.to_symbol_priv(Private) };
+        match self.names().iter().position(|n| *n == name) {
+            Some(idx) => Ok(Some(self.as_value().get_nth_field(target, idx)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Call `func` for every field of this `NamedTuple` with its name and value.
+    pub fn for_each<F>(self, frame: &mut GcFrame, mut func: F) -> JlrsResult<()>
+    where
+        F: FnMut(Symbol<'scope>, Value<'_, 'data>) -> JlrsResult<()>,
+    {
+        for (idx, name) in self.names().iter().copied().enumerate() {
+            frame.scope(|mut frame| {
+                let value = self.as_value().get_nth_field(&mut frame, idx)?;
+                func(name, value)
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Convert this `NamedTuple` to a `HashMap`, every value is unboxed as `V`.
+    pub fn to_hash_map<V>(self, frame: &mut GcFrame) -> JlrsResult<HashMap<String, V::Output>>
+    where
+        V: Unbox + Typecheck,
+    {
+        let mut map = HashMap::with_capacity(self.len());
+        self.for_each(frame, |name, value| {
+            map.insert(name.as_str()?.into(), value.unbox::<V>()?);
+            Ok(())
+        })?;
+
+        Ok(map)
+    }
+
+    /// Convert this `NamedTuple` to a `BTreeMap`, every value is unboxed as `V`.
+    pub fn to_btree_map<V>(self, frame: &mut GcFrame) -> JlrsResult<BTreeMap<String, V::Output>>
+    where
+        V: Unbox + Typecheck,
+    {
+        let mut map = BTreeMap::new();
+        self.for_each(frame, |name, value| {
+            map.insert(name.as_str()?.into(), value.unbox::<V>()?);
+            Ok(())
+        })?;
+
+        Ok(map)
+    }
+}
+
+/// Convert Rust data to a `NamedTuple`.
+pub trait IntoNamedTuple {
+    /// Convert `self` to a new `NamedTuple`. The fields of the named tuple are sorted by name if
+    /// `self` is a `HashMap`.
+    fn into_named_tuple<'target, T>(
+        self,
+        target: ExtendedTarget<'target, '_, '_, T>,
+    ) -> JlrsResult<NamedTupleData<'target, 'static, T>>
+    where
+        T: Target<'target>;
+}
+
+/// Convert a `NamedTuple` to Rust data.
+pub trait FromNamedTuple: Sized {
+    /// Convert the fields of `named_tuple` to a new instance of `Self`.
+    fn from_named_tuple(frame: &mut GcFrame, named_tuple: NamedTuple) -> JlrsResult<Self>;
+}
+
+impl<K, V> IntoNamedTuple for HashMap<K, V>
+where
+    K: AsRef<str>,
+    V: IntoJulia,
+{
+    fn into_named_tuple<'target, T>(
+        self,
+        target: ExtendedTarget<'target, '_, '_, T>,
+    ) -> JlrsResult<NamedTupleData<'target, 'static, T>>
+    where
+        T: Target<'target>,
+    {
+        // The iteration order of a HashMap is arbitrary, sort the fields by name so converting
+        // the same map always results in a named tuple of the same type.
+        let mut fields = self.into_iter().collect::<Vec<_>>();
+        fields.sort_unstable_by(|(a, _), (b, _)| a.as_ref().cmp(b.as_ref()));
+        into_named_tuple(target, fields)
+    }
+}
+
+impl<K, V> IntoNamedTuple for BTreeMap<K, V>
+where
+    K: AsRef<str>,
+    V: IntoJulia,
+{
+    fn into_named_tuple<'target, T>(
+        self,
+        target: ExtendedTarget<'target, '_, '_, T>,
+    ) -> JlrsResult<NamedTupleData<'target, 'static, T>>
+    where
+        T: Target<'target>,
+    {
+        into_named_tuple(target, self)
+    }
+}
+
+impl<V> FromNamedTuple for HashMap<String, V>
+where
+    V: Unbox<Output = V> + Typecheck,
+{
+    fn from_named_tuple(frame: &mut GcFrame, named_tuple: NamedTuple) -> JlrsResult<Self> {
+        named_tuple.to_hash_map::<V>(frame)
+    }
+}
+
+impl<V> FromNamedTuple for BTreeMap<String, V>
+where
+    V: Unbox<Output = V> + Typecheck,
+{
+    fn from_named_tuple(frame: &mut GcFrame, named_tuple: NamedTuple) -> JlrsResult<Self> {
+        named_tuple.to_btree_map::<V>(frame)
+    }
+}
+
+// Safety: the type name is compared with the type name of NamedTuple
+unsafe impl Typecheck for NamedTuple<'_, '_> {
+    fn typecheck(t: DataType) -> bool {
+        unsafe { t.unwrap_non_null(Private).as_ref().name == jl_namedtuple_typename }
+    }
+}
+
+impl_debug!(NamedTuple<'_, '_>);
+
+impl<'scope, 'data> WrapperPriv<'scope, 'data> for NamedTuple<'scope, 'data> {
+    type Wraps = jl_value_t;
+    type TypeConstructorPriv<'target, 'da> = NamedTuple<'target, 'da>;
+    const NAME: &'static str = "NamedTuple";
+
+    // Safety: `inner` must not have been freed yet, the result must never be
+    // used after the GC might have freed it.
+    unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+        Self(inner, PhantomData, PhantomData)
+    }
+
+    fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+        self.0
+    }
+}
+
+/// A reference to a [`NamedTuple`] that has not been explicitly rooted.
+pub type NamedTupleRef<'scope, 'data> = Ref<'scope, 'data, NamedTuple<'scope, 'data>>;
+
+unsafe impl ValidLayout for NamedTupleRef<'_, '_> {
+    fn valid_layout(v: Value) -> bool {
+        // A concrete named tuple type can be stored inline, so only fields and values whose
+        // type is not concrete are guaranteed to be references to a named tuple.
+        if let Ok(dt) = v.cast::<DataType>() {
+            dt.is::<NamedTuple>() && !dt.is_concrete_type()
+        } else if let Ok(ua) = v.cast::<UnionAll>() {
+            ua.base_type().is::<NamedTuple>()
+        } else {
+            false
+        }
+    }
+
+    const IS_REF: bool = true;
+}
+
+unsafe impl ValidField for Option<NamedTupleRef<'_, '_>> {
+    fn valid_field(v: Value) -> bool {
+        if let Ok(dt) = v.cast::<DataType>() {
+            dt.is::<NamedTuple>() && !dt.is_concrete_type()
+        } else if let Ok(ua) = v.cast::<UnionAll>() {
+            ua.base_type().is::<NamedTuple>()
+        } else {
+            false
+        }
+    }
+}
+
+/// `NamedTuple` or `NamedTupleRef`, depending on the target type `T`.
+pub type NamedTupleData<'target, 'data, T> =
+    <T as TargetType<'target>>::Data<'data, NamedTuple<'target, 'data>>;
+
+/// `JuliaResult<NamedTuple>` or `JuliaResultRef<NamedTupleRef>`, depending on the target type
+/// `T`.
+pub type NamedTupleResult<'target, 'data, T> =
+    <T as TargetType<'target>>::Result<'data, NamedTuple<'target, 'data>>;
+
+fn into_named_tuple<'target, K, V, I, T>(
+    target: ExtendedTarget<'target, '_, '_, T>,
+    iter: I,
+) -> JlrsResult<NamedTupleData<'target, 'static, T>>
+where
+    K: AsRef<str>,
+    V: IntoJulia,
+    I: IntoIterator<Item = (K, V)>,
+    T: Target<'target>,
+{
+    let (output, frame) = target.split();
+    frame.scope(|mut frame| {
+        let mut names = Vec::new();
+        let mut values = Vec::new();
+        for (name, value) in iter {
+            names.push(name);
+            values.push(Value::new(&mut frame, value));
+        }
+
+        NamedTuple::new(frame.as_extended_target(), names, values)
+            .map(|named_tuple| named_tuple.root(output))
+    })
+}
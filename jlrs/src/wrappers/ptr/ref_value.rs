@@ -0,0 +1,278 @@
+//! Wrapper for `RefValue`.
+//!
+//! Many Julia functions take a `Ref{T}` argument to return a value by reference, the concrete
+//! type of `Ref(x)` is `Base.RefValue{T}`. A [`RefValue`] can be created from an existing value,
+//! or with an undefined value of some type so it can be used as an output argument. Its contents
+//! can be accessed as a [`Value`], or read and written directly if they're stored inline:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! # use jlrs::wrappers::ptr::ref_value::RefValue;
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         let value = Value::new(&mut frame, 1usize);
+//!         let r = RefValue::new(frame.as_extended_target(), value)?;
+//!         assert_eq!(r.read::<usize>()?, 1);
+//!
+//!         unsafe { r.write(2usize)? };
+//!         let value = r.get(&mut frame)?;
+//!         assert_eq!(value.unbox::<usize>()?, 2);
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+
+use std::{marker::PhantomData, ptr::NonNull};
+
+use jl_sys::jl_value_t;
+
+use crate::{
+    call::Call,
+    convert::{
+        construct_type::ConstructType,
+        into_jlrs_result::IntoJlrsResult,
+        into_julia::IntoJulia,
+    },
+    error::{AccessError, JlrsResult, CANNOT_DISPLAY_TYPE},
+    layout::{
+        typecheck::Typecheck,
+        valid_layout::{ValidField, ValidLayout},
+    },
+    memory::target::{target_type::TargetType, ExtendedTarget, Target},
+    private::Private,
+    wrappers::ptr::{
+        datatype::DataType,
+        module::{base_type_constructor, is_base_type},
+        private::WrapperPriv,
+        union_all::UnionAll,
+        value::{Value, ValueData},
+        Ref,
+        Wrapper,
+    },
+};
+
+/// A Julia `Base.RefValue`.
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct RefValue<'scope, 'data>(
+    NonNull<jl_value_t>,
+    PhantomData<&'scope ()>,
+    PhantomData<&'data mut ()>,
+);
+
+impl<'scope, 'data> RefValue<'scope, 'data> {
+    /// Create a new `RefValue{typeof(value)}` that contains `value`.
+    pub fn new<'target, T>(
+        target: ExtendedTarget<'target, '_, '_, T>,
+        value: Value<'_, 'data>,
+    ) -> JlrsResult<RefValueData<'target, 'data, T>>
+    where
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let ty = value.datatype().as_value();
+
+            // Safety: RefValue{T}(value) only allocates a new RefValue.
+            unsafe {
+                let r = base_type_constructor(&frame, "RefValue")?
+                    .apply_type(&mut frame, [ty])
+                    .into_jlrs_result()?
+                    .call1(&mut frame, value)
+                    .into_jlrs_result()?;
+
+                Ok(r.cast_unchecked::<RefValue>().root(output))
+            }
+        })
+    }
+
+    /// Create a new `RefValue{U}` without initializing its contents. If `U` is an isbits type its
+    /// contents are arbitrary, otherwise they're undefined.
+    pub fn new_undef<'target, U, T>(
+        target: ExtendedTarget<'target, '_, '_, T>,
+    ) -> JlrsResult<RefValueData<'target, 'static, T>>
+    where
+        U: ConstructType,
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let ty = U::construct_type(frame.as_extended_target());
+
+            // Safety: RefValue{U}() only allocates a new RefValue.
+            unsafe {
+                let r = base_type_constructor(&frame, "RefValue")?
+                    .apply_type(&mut frame, [ty])
+                    .into_jlrs_result()?
+                    .call0(&mut frame)
+                    .into_jlrs_result()?;
+
+                Ok(r.cast_unchecked::<RefValue>().root(output))
+            }
+        })
+    }
+
+    /// Returns the type of the contents of this `RefValue`, i.e. `T` for a `RefValue{T}`.
+    pub fn element_type(self) -> Value<'scope, 'static> {
+        // Safety: the parameters of the type are rooted by this value.
+        unsafe {
+            self.as_value().datatype().parameters().data().as_slice()[0]
+                .expect("RefValue has no type parameter")
+                .value()
+        }
+    }
+
+    /// Returns `true` if the contents of this `RefValue` are undefined.
+    pub fn is_undefined(self) -> bool {
+        // Safety: RefValue has a single field. If it's stored as a pointer it's undefined if that
+        // pointer is null, inline fields are always defined.
+        unsafe {
+            self.as_value().datatype().is_pointer_field_unchecked(0)
+                && self
+                    .as_value()
+                    .data_ptr()
+                    .cast::<*mut jl_value_t>()
+                    .as_ptr()
+                    .read()
+                    .is_null()
+        }
+    }
+
+    /// Returns the contents of this `RefValue`. If the contents are stored inline they're boxed.
+    /// An error is returned if the contents are undefined.
+    pub fn get<'target, T>(self, target: T) -> JlrsResult<ValueData<'target, 'data, T>>
+    where
+        T: Target<'target>,
+    {
+        if self.is_undefined() {
+            Err(AccessError::UndefRef)?
+        }
+
+        self.as_value().get_field(target, "x")
+    }
+
+    /// Replace the contents of this `RefValue` with `value`. If Julia throws an exception it's
+    /// caught, rooted in the target, and returned. An error is returned if `value` is not an
+    /// instance of the element type.
+    ///
+    /// Safety: mutating Julia data is generally unsafe because it can't be guaranteed mutating
+    /// this value is allowed.
+    pub unsafe fn set<'target, T>(
+        self,
+        target: T,
+        value: Value<'_, 'data>,
+    ) -> JlrsResult<T::Exception<'data, ()>>
+    where
+        T: Target<'target>,
+    {
+        self.as_value().set_field(target, "x", value)
+    }
+
+    /// Read the contents of this `RefValue` as `U`. An error is returned if the layout of `U`
+    /// doesn't match the element type.
+    ///
+    /// If the contents are stored inline, e.g. if the element type is an isbits type, `U` must be
+    /// a matching layout type like `f64`. Otherwise the contents are stored as a pointer and `U`
+    /// must be a type like `Option<ValueRef>`.
+    pub fn read<U: ValidField + Clone>(self) -> JlrsResult<U> {
+        let elem_ty = self.element_type();
+        if !U::valid_field(elem_ty) {
+            Err(AccessError::InvalidLayout {
+                value_type: elem_ty.display_string_or(CANNOT_DISPLAY_TYPE),
+            })?
+        }
+
+        // Safety: the layout of U matches the layout of the field, RefValue has a single field
+        // at offset 0.
+        unsafe { Ok(self.as_value().data_ptr().cast::<U>().as_ref().clone()) }
+    }
+
+    /// Overwrite the contents of this `RefValue` with `value`. An error is returned if the
+    /// element type is not an isbits type whose layout matches `U`.
+    ///
+    /// Safety: mutating Julia data is generally unsafe because it can't be guaranteed mutating
+    /// this value is allowed.
+    pub unsafe fn write<U: ValidField + IntoJulia>(self, value: U) -> JlrsResult<()> {
+        let elem_ty = self.element_type();
+        let is_bits = elem_ty.cast::<DataType>().map_or(false, |dt| dt.is_bits());
+        if !is_bits || !U::valid_field(elem_ty) {
+            Err(AccessError::InvalidLayout {
+                value_type: elem_ty.display_string_or(CANNOT_DISPLAY_TYPE),
+            })?
+        }
+
+        self.as_value().data_ptr().cast::<U>().as_ptr().write(value);
+        Ok(())
+    }
+}
+
+// Safety: the type name is compared with the type name of Base.RefValue
+unsafe impl Typecheck for RefValue<'_, '_> {
+    fn typecheck(t: DataType) -> bool {
+        is_base_type(t, "RefValue")
+    }
+}
+
+impl_debug!(RefValue<'_, '_>);
+
+impl<'scope, 'data> WrapperPriv<'scope, 'data> for RefValue<'scope, 'data> {
+    type Wraps = jl_value_t;
+    type TypeConstructorPriv<'target, 'da> = RefValue<'target, 'da>;
+    const NAME: &'static str = "RefValue";
+
+    // Safety: `inner` must not have been freed yet, the result must never be
+    // used after the GC might have freed it.
+    unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+        Self(inner, PhantomData, PhantomData)
+    }
+
+    fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+        self.0
+    }
+}
+
+/// A reference to a [`RefValue`] that has not been explicitly rooted.
+pub type RefValueRef<'scope, 'data> = Ref<'scope, 'data, RefValue<'scope, 'data>>;
+
+unsafe impl ValidLayout for RefValueRef<'_, '_> {
+    fn valid_layout(v: Value) -> bool {
+        if let Ok(dt) = v.cast::<DataType>() {
+            dt.is::<RefValue>()
+        } else if let Ok(ua) = v.cast::<UnionAll>() {
+            ua.base_type().is::<RefValue>()
+        } else {
+            false
+        }
+    }
+
+    const IS_REF: bool = true;
+}
+
+unsafe impl ValidField for Option<RefValueRef<'_, '_>> {
+    fn valid_field(v: Value) -> bool {
+        if let Ok(dt) = v.cast::<DataType>() {
+            dt.is::<RefValue>()
+        } else if let Ok(ua) = v.cast::<UnionAll>() {
+            ua.base_type().is::<RefValue>()
+        } else {
+            false
+        }
+    }
+}
+
+/// `RefValue` or `RefValueRef`, depending on the target type `T`.
+pub type RefValueData<'target, 'data, T> =
+    <T as TargetType<'target>>::Data<'data, RefValue<'target, 'data>>;
+
+/// `JuliaResult<RefValue>` or `JuliaResultRef<RefValueRef>`, depending on the target type `T`.
+pub type RefValueResult<'target, 'data, T> =
+    <T as TargetType<'target>>::Result<'data, RefValue<'target, 'data>>;
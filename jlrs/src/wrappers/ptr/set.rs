@@ -0,0 +1,345 @@
+//! Wrapper for `Set`.
+//!
+//! Like [`Dict`], a `Set` is not a builtin type and all operations call functions defined in the
+//! `Base` module. Elements can be converted from Rust with [`IntoJulia`] and to Rust with
+//! [`Unbox`], which makes it possible to convert a `HashSet` or `BTreeSet` to a `Set` and back:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! # use jlrs::wrappers::ptr::set::Set;
+//! # use std::collections::HashSet;
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         let set: HashSet<i64> = [1, 2, 3].iter().copied().collect();
+//!
+//!         let julia_set = Set::from_iter(frame.as_extended_target(), set.clone())?;
+//!         assert_eq!(julia_set.len(), 3);
+//!
+//!         let converted = julia_set.to_hash_set::<i64>(&mut frame)?;
+//!         assert_eq!(converted, set);
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+//!
+//! [`Dict`]: crate::wrappers::ptr::dict::Dict
+//! [`IntoJulia`]: crate::convert::into_julia::IntoJulia
+//! [`Unbox`]: crate::convert::unbox::Unbox
+
+use std::{
+    collections::{BTreeSet, HashSet},
+    hash::Hash,
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+use jl_sys::jl_value_t;
+
+use crate::{
+    call::Call,
+    convert::{
+        construct_type::ConstructType,
+        into_jlrs_result::IntoJlrsResult,
+        into_julia::IntoJulia,
+        unbox::Unbox,
+    },
+    error::{AccessError, JlrsResult},
+    layout::{
+        typecheck::Typecheck,
+        valid_layout::{ValidField, ValidLayout},
+    },
+    memory::target::{frame::GcFrame, target_type::TargetType, ExtendedTarget, Target},
+    private::Private,
+    wrappers::ptr::{
+        datatype::DataType,
+        dict::Dict,
+        module::{base_function, base_type_constructor, collect_any, is_base_type},
+        private::WrapperPriv,
+        union_all::UnionAll,
+        value::Value,
+        Ref,
+        Wrapper,
+    },
+};
+
+/// A Julia `Set`.
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct Set<'scope, 'data>(
+    NonNull<jl_value_t>,
+    PhantomData<&'scope ()>,
+    PhantomData<&'data mut ()>,
+);
+
+impl<'scope, 'data> Set<'scope, 'data> {
+    /// Create a new, empty `Set{E}`.
+    pub fn new<'target, E, T>(
+        target: ExtendedTarget<'target, '_, '_, T>,
+    ) -> JlrsResult<SetData<'target, 'static, T>>
+    where
+        E: IntoJulia + ConstructType,
+        T: Target<'target>,
+    {
+        Self::from_iter(target, std::iter::empty::<E>())
+    }
+
+    /// Create a new `Set{E}` that contains all elements in `iter`, e.g. a `HashSet` or a
+    /// `BTreeSet`.
+    pub fn from_iter<'target, E, I, T>(
+        target: ExtendedTarget<'target, '_, '_, T>,
+        iter: I,
+    ) -> JlrsResult<SetData<'target, 'static, T>>
+    where
+        E: IntoJulia + ConstructType,
+        I: IntoIterator<Item = E>,
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let elem_ty = E::construct_type(frame.as_extended_target());
+
+            // Safety: Set{E}() only allocates a new set, push! only stores the converted
+            // element.
+            unsafe {
+                let set = base_type_constructor(&frame, "Set")?
+                    .apply_type(&mut frame, [elem_ty])
+                    .into_jlrs_result()?
+                    .call0(&mut frame)
+                    .into_jlrs_result()?;
+
+                let push = base_function(&frame, "push!")?;
+                for elem in iter {
+                    frame.scope(|mut frame| {
+                        let elem = Value::new(&mut frame, elem);
+                        push.call2(&mut frame, set, elem).into_jlrs_result()?;
+                        Ok(())
+                    })?;
+                }
+
+                Ok(set.cast_unchecked::<Set>().root(output))
+            }
+        })
+    }
+
+    /// Create a new `Set{E}` that contains all elements in `iter`, the elements must already
+    /// have been converted to Julia.
+    ///
+    /// Unlike [`Set::from_iter`], `E` doesn't have to implement `IntoJulia`. This can be used to
+    /// create a `Set` with elements that are converted in another way, e.g. a `Set{String}`
+    /// whose elements are created with `JuliaString::new`. If Julia throws an exception because
+    /// an element can't be converted to `E` it's caught and returned as an error.
+    pub fn from_values<'target, 'value, E, I, T>(
+        target: ExtendedTarget<'target, '_, '_, T>,
+        iter: I,
+    ) -> JlrsResult<SetData<'target, 'static, T>>
+    where
+        E: ConstructType,
+        I: IntoIterator<Item = Value<'value, 'static>>,
+        T: Target<'target>,
+    {
+        let (output, frame) = target.split();
+        frame.scope(|mut frame| {
+            let elem_ty = E::construct_type(frame.as_extended_target());
+
+            // Safety: Set{E}() only allocates a new set, push! only stores the element.
+            unsafe {
+                let set = base_type_constructor(&frame, "Set")?
+                    .apply_type(&mut frame, [elem_ty])
+                    .into_jlrs_result()?
+                    .call0(&mut frame)
+                    .into_jlrs_result()?;
+
+                let push = base_function(&frame, "push!")?;
+                for elem in iter {
+                    frame.scope(|mut frame| {
+                        push.call2(&mut frame, set, elem).into_jlrs_result()?;
+                        Ok(())
+                    })?;
+                }
+
+                Ok(set.cast_unchecked::<Set>().root(output))
+            }
+        })
+    }
+
+    /// Returns the number of elements in this `Set`.
+    pub fn len(self) -> usize {
+        // Safety: the dictionary is referenced by this set.
+        unsafe {
+            self.as_value()
+                .get_field_ref("dict")
+                .ok()
+                .flatten()
+                .map(|dict| dict.value().cast_unchecked::<Dict>().len())
+                .expect("Set has no dict field")
+        }
+    }
+
+    /// Returns `true` if this `Set` is empty.
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if this `Set` contains `elem`.
+    pub fn contains(self, frame: &mut GcFrame, elem: Value) -> JlrsResult<bool> {
+        frame.scope(|mut frame| {
+            // Safety: in doesn't mutate the set.
+            unsafe {
+                base_function(&frame, "in")?
+                    .call2(&mut frame, elem, self.as_value())
+                    .into_jlrs_result()?
+                    .unbox::<bool>()
+                    .map(|b| b.as_bool())
+            }
+        })
+    }
+
+    /// Insert `elem`. An error is returned if the element can't be converted to the element type
+    /// of this `Set`.
+    ///
+    /// Safety: mutating Julia data is generally unsafe because it can't be guaranteed mutating
+    /// this value is allowed.
+    pub unsafe fn insert(self, frame: &mut GcFrame, elem: Value<'_, 'data>) -> JlrsResult<()> {
+        frame.scope(|mut frame| {
+            base_function(&frame, "push!")?
+                .call2(&mut frame, self.as_value(), elem)
+                .into_jlrs_result()?;
+            Ok(())
+        })
+    }
+
+    /// Remove `elem` from this `Set`. Nothing happens if the element doesn't exist.
+    ///
+    /// Safety: mutating Julia data is generally unsafe because it can't be guaranteed mutating
+    /// this value is allowed.
+    pub unsafe fn remove(self, frame: &mut GcFrame, elem: Value) -> JlrsResult<()> {
+        frame.scope(|mut frame| {
+            base_function(&frame, "delete!")?
+                .call2(&mut frame, self.as_value(), elem)
+                .into_jlrs_result()?;
+            Ok(())
+        })
+    }
+
+    /// Call `func` for every element in this `Set`.
+    ///
+    /// The elements are copied to a new array before `func` is called, mutating this `Set` in
+    /// `func` doesn't affect the iteration.
+    pub fn for_each<F>(self, frame: &mut GcFrame, mut func: F) -> JlrsResult<()>
+    where
+        F: FnMut(Value<'_, 'data>) -> JlrsResult<()>,
+    {
+        frame.scope(|mut frame| {
+            // Safety: collecting the elements doesn't mutate the set.
+            unsafe {
+                let elems = collect_any(&mut frame, self.as_value())?;
+                let elems = elems.value_data()?;
+                for elem in elems.as_slice().iter() {
+                    func(elem.ok_or(AccessError::UndefRef)?.value())?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Convert this `Set` to a `HashSet`, every element is unboxed as `E`.
+    pub fn to_hash_set<E>(self, frame: &mut GcFrame) -> JlrsResult<HashSet<E::Output>>
+    where
+        E: Unbox + Typecheck,
+        E::Output: Eq + Hash,
+    {
+        let mut set = HashSet::with_capacity(self.len());
+        self.for_each(frame, |elem| {
+            set.insert(elem.unbox::<E>()?);
+            Ok(())
+        })?;
+
+        Ok(set)
+    }
+
+    /// Convert this `Set` to a `BTreeSet`, every element is unboxed as `E`.
+    pub fn to_btree_set<E>(self, frame: &mut GcFrame) -> JlrsResult<BTreeSet<E::Output>>
+    where
+        E: Unbox + Typecheck,
+        E::Output: Ord,
+    {
+        let mut set = BTreeSet::new();
+        self.for_each(frame, |elem| {
+            set.insert(elem.unbox::<E>()?);
+            Ok(())
+        })?;
+
+        Ok(set)
+    }
+}
+
+// Safety: the type name is compared with the type name of Base.Set
+unsafe impl Typecheck for Set<'_, '_> {
+    fn typecheck(t: DataType) -> bool {
+        is_base_type(t, "Set")
+    }
+}
+
+impl_debug!(Set<'_, '_>);
+
+impl<'scope, 'data> WrapperPriv<'scope, 'data> for Set<'scope, 'data> {
+    type Wraps = jl_value_t;
+    type TypeConstructorPriv<'target, 'da> = Set<'target, 'da>;
+    const NAME: &'static str = "Set";
+
+    // Safety: `inner` must not have been freed yet, the result must never be
+    // used after the GC might have freed it.
+    unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+        Self(inner, PhantomData, PhantomData)
+    }
+
+    fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+        self.0
+    }
+}
+
+/// A reference to a [`Set`] that has not been explicitly rooted.
+pub type SetRef<'scope, 'data> = Ref<'scope, 'data, Set<'scope, 'data>>;
+
+unsafe impl ValidLayout for SetRef<'_, '_> {
+    fn valid_layout(v: Value) -> bool {
+        if let Ok(dt) = v.cast::<DataType>() {
+            dt.is::<Set>()
+        } else if let Ok(ua) = v.cast::<UnionAll>() {
+            ua.base_type().is::<Set>()
+        } else {
+            false
+        }
+    }
+
+    const IS_REF: bool = true;
+}
+
+unsafe impl ValidField for Option<SetRef<'_, '_>> {
+    fn valid_field(v: Value) -> bool {
+        if let Ok(dt) = v.cast::<DataType>() {
+            dt.is::<Set>()
+        } else if let Ok(ua) = v.cast::<UnionAll>() {
+            ua.base_type().is::<Set>()
+        } else {
+            false
+        }
+    }
+}
+
+/// `Set` or `SetRef`, depending on the target type `T`.
+pub type SetData<'target, 'data, T> = <T as TargetType<'target>>::Data<'data, Set<'target, 'data>>;
+
+/// `JuliaResult<Set>` or `JuliaResultRef<SetRef>`, depending on the target type `T`.
+pub type SetResult<'target, 'data, T> =
+    <T as TargetType<'target>>::Result<'data, Set<'target, 'data>>;
@@ -1,8 +1,13 @@
 //! Wrapper for `String`.
+//!
+//! Besides [`JuliaString`], this module provides [`StringView`] to access a `String` or a
+//! `SubString{String}` through the same interface, and [`StringWriter`] to build a new `String`
+//! with the `Write` traits from `std`.
 
 use std::{
     ffi::CStr,
-    fmt::{Debug, Formatter, Result as FmtResult},
+    fmt::{self, Debug, Formatter, Result as FmtResult},
+    io,
     marker::PhantomData,
     mem,
     ptr::NonNull,
@@ -13,12 +18,22 @@ use jl_sys::{jl_pchar_to_string, jl_string_type};
 
 use super::Ref;
 use crate::{
-    convert::unbox::Unbox,
-    error::{JlrsError, JlrsResult},
+    call::Call,
+    convert::{into_jlrs_result::IntoJlrsResult, unbox::Unbox},
+    error::{JlrsError, JlrsResult, TypeError, CANNOT_DISPLAY_TYPE},
     impl_julia_typecheck,
-    memory::target::Target,
+    memory::target::{frame::GcFrame, Target},
     private::Private,
-    wrappers::ptr::{private::WrapperPriv, value::Value},
+    wrappers::{
+        inline::sub_string::SubString,
+        ptr::{
+            function::Function,
+            module::{jlrs_function, Module},
+            private::WrapperPriv,
+            value::Value,
+            Wrapper,
+        },
+    },
 };
 
 /// A Julia string.
@@ -98,6 +113,190 @@ impl<'scope> JuliaString<'scope> {
 
 impl_julia_typecheck!(JuliaString<'scope>, jl_string_type, 'scope);
 
+/// A `String` or a `SubString{String}`.
+///
+/// Many functions return either of these types, a `StringView` can be used to access the
+/// contents of both as a `&str` without copying them.
+#[derive(Copy, Clone, Debug)]
+pub enum StringView<'scope> {
+    /// A `String`.
+    String(JuliaString<'scope>),
+    /// A `SubString{String}`.
+    SubString(SubString<'scope>),
+}
+
+impl<'scope> StringView<'scope> {
+    /// Create a new `StringView` of `value`. An error is returned if `value` is neither a
+    /// `String` nor a `SubString{String}`.
+    pub fn new(value: Value<'scope, '_>) -> JlrsResult<Self> {
+        if let Ok(s) = value.cast::<JuliaString>() {
+            return Ok(StringView::String(s));
+        }
+
+        if value.is::<SubString>() {
+            // Safety: the layout has been checked, the parent string is referenced by value.
+            let s = unsafe { value.unbox_unchecked::<SubString<'scope>>() };
+            return Ok(StringView::SubString(s));
+        }
+
+        Err(TypeError::IncompatibleType {
+            element_type: value.datatype().display_string_or(CANNOT_DISPLAY_TYPE),
+            value_type: "String or SubString{String}".into(),
+        })?
+    }
+
+    /// Returns the length of the string in bytes.
+    pub fn len(self) -> usize {
+        match self {
+            StringView::String(s) => s.len(),
+            StringView::SubString(s) => s.len(),
+        }
+    }
+
+    /// Returns `true` if the string is empty.
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the string as a slice of bytes, including all null characters.
+    ///
+    /// Safety: if this is a view of a `SubString`, the requirements of [`SubString::as_bytes`]
+    /// apply.
+    pub unsafe fn as_bytes(self) -> &'scope [u8] {
+        match self {
+            StringView::String(s) => s.as_bytes(),
+            StringView::SubString(s) => s.as_bytes(),
+        }
+    }
+
+    /// Returns the string as a string slice, or an error if it the string contains invalid
+    /// characters.
+    ///
+    /// Safety: the same requirements as [`StringView::as_bytes`] apply.
+    pub unsafe fn as_str(self) -> JlrsResult<&'scope str> {
+        Ok(str::from_utf8(self.as_bytes()).map_err(JlrsError::other)?)
+    }
+
+    /// Returns the string as a string slice without checking if the string is properly encoded.
+    ///
+    /// Safety: the same requirements as [`StringView::as_bytes`] apply, and the string must be
+    /// properly encoded.
+    pub unsafe fn as_str_unchecked(self) -> &'scope str {
+        str::from_utf8_unchecked(self.as_bytes())
+    }
+}
+
+/// Build a new Julia `String` by writing to an `IOBuffer`.
+///
+/// `StringWriter` implements both `std::fmt::Write` and `std::io::Write`, so it can be used with
+/// the `write!` macro. The data is written directly to the buffer, when [`StringWriter::finish`]
+/// is called its contents are copied to a new `String`.
+///
+/// ```
+/// # use jlrs::prelude::*;
+/// # use jlrs::util::test::JULIA;
+/// # use jlrs::wrappers::ptr::string::StringWriter;
+/// use std::fmt::Write;
+///
+/// # fn main() {
+/// # JULIA.with(|j| {
+/// # let mut julia = j.borrow_mut();
+/// # let mut frame = StackFrame::new();
+/// # let mut julia = julia.instance(&mut frame);
+/// julia
+///     .scope(|mut frame| {
+///         let mut writer = StringWriter::new(&mut frame)?;
+///         for i in 0..3 {
+///             write!(writer, "{} ", i).unwrap();
+///         }
+///
+///         let s = writer.finish()?;
+///         assert_eq!(s.as_str()?, "0 1 2 ");
+///
+///         Ok(())
+///     })
+///     .unwrap();
+/// # });
+/// # }
+/// ```
+pub struct StringWriter<'scope, 'borrow> {
+    frame: &'borrow mut GcFrame<'scope>,
+    io: Value<'scope, 'static>,
+    write_bytes: Function<'scope, 'static>,
+}
+
+impl<'scope, 'borrow> StringWriter<'scope, 'borrow> {
+    /// Create a new `StringWriter`. The buffer and the resulting string are rooted in `frame`.
+    pub fn new(frame: &'borrow mut GcFrame<'scope>) -> JlrsResult<Self> {
+        // Safety: IOBuffer() only allocates a new buffer, the functions are constants.
+        unsafe {
+            let io = Module::base(&*frame)
+                .function(&*frame, "IOBuffer")?
+                .wrapper()
+                .call0(&mut *frame)
+                .into_jlrs_result()?;
+
+            let write_bytes = jlrs_function(&*frame, "writebytes")?;
+
+            Ok(StringWriter {
+                frame,
+                io,
+                write_bytes,
+            })
+        }
+    }
+
+    /// Append `bytes` to the buffer.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> JlrsResult<()> {
+        let io = self.io;
+        let write_bytes = self.write_bytes;
+        self.frame.scope(|mut frame| {
+            let ptr = Value::new(&mut frame, bytes.as_ptr() as *mut u8);
+            let n = Value::new(&mut frame, bytes.len());
+
+            // Safety: the pointer is valid for n bytes, writebytes copies them to the buffer.
+            unsafe {
+                write_bytes
+                    .call3(&mut frame, io, ptr, n)
+                    .into_jlrs_result()?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Copy the contents of the buffer to a new `String`, which is rooted in the frame this writer
+    /// was created with. The buffer is emptied.
+    pub fn finish(self) -> JlrsResult<JuliaString<'scope>> {
+        // Safety: takestring! copies the contents of the buffer to a new String.
+        unsafe {
+            let s = jlrs_function(&*self.frame, "takestring!")?
+                .call1(&mut *self.frame, self.io)
+                .into_jlrs_result()?;
+
+            Ok(s.cast_unchecked::<JuliaString>())
+        }
+    }
+}
+
+impl fmt::Write for StringWriter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_bytes(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+impl io::Write for StringWriter<'_, '_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_bytes(buf)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 unsafe impl Unbox for String {
     type Output = Result<String, Vec<u8>>;
     unsafe fn unbox(value: Value) -> Self::Output {
@@ -1,4 +1,10 @@
 //! Wrapper for `Symbol`. Symbols represent identifiers like module and function names.
+//!
+//! Converting a string to a `Symbol` requires calling into Julia. If the same name is used
+//! repeatedly, e.g. to access a field or a function in a hot loop, a [`StaticSymbol`] can be used
+//! instead. The easiest way to create one is with the [`sym`] macro.
+//!
+//! [`sym`]: crate::sym
 
 #[cfg(not(all(target_os = "windows", feature = "lts")))]
 use std::mem::MaybeUninit;
@@ -6,7 +12,8 @@ use std::{
     ffi::CStr,
     hash::{Hash, Hasher},
     marker::PhantomData,
-    ptr::NonNull,
+    ptr::{null_mut, NonNull},
+    sync::atomic::{AtomicPtr, Ordering},
 };
 
 use jl_sys::{jl_sym_t, jl_symbol_n, jl_symbol_name_ as jl_symbol_name, jl_symbol_type};
@@ -171,6 +178,107 @@ impl<'scope> WrapperPriv<'scope, '_> for Symbol<'scope> {
     }
 }
 
+/// A lazily-initialized `Symbol` that can be stored in a `static`.
+///
+/// The `Symbol` is created the first time it's used and cached afterwards. Because symbols are
+/// never freed by the garbage collector, the cached `Symbol` remains valid for as long as Julia
+/// is running. A `StaticSymbol` can be used with any method that takes an argument that
+/// implements [`ToSymbol`], and can be shared between threads.
+///
+/// You should normally use the [`sym`] macro to create a `StaticSymbol`.
+///
+/// [`ToSymbol`]: crate::convert::to_symbol::ToSymbol
+/// [`sym`]: crate::sym
+pub struct StaticSymbol {
+    name: &'static str,
+    ptr: AtomicPtr<jl_sym_t>,
+}
+
+impl StaticSymbol {
+    /// Create a new `StaticSymbol`, no `Symbol` is created until it's used.
+    pub const fn new(name: &'static str) -> Self {
+        StaticSymbol {
+            name,
+            ptr: AtomicPtr::new(null_mut()),
+        }
+    }
+
+    /// Returns the name of this symbol.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the cached `Symbol`, it's created if it doesn't exist yet.
+    pub fn get<'target, T>(&self, _: &T) -> Symbol<'static>
+    where
+        T: Target<'target>,
+    {
+        // Safety: Requiring a reference to a target guarantees this method can only be called
+        // from a thread known to Julia.
+        unsafe { self.get_unchecked() }
+    }
+
+    // Safety: must only be called from a thread known to Julia.
+    pub(crate) unsafe fn get_unchecked(&self) -> Symbol<'static> {
+        let ptr = self.ptr.load(Ordering::Acquire);
+        if let Some(ptr) = NonNull::new(ptr) {
+            return Symbol::wrap_non_null(ptr, Private);
+        }
+
+        // If multiple threads race to initialize the symbol they all get the same pointer.
+        let bytes = self.name.as_bytes();
+        let sym = jl_symbol_n(bytes.as_ptr().cast(), bytes.len());
+        self.ptr.store(sym, Ordering::Release);
+        Symbol::wrap_non_null(NonNull::new_unchecked(sym), Private)
+    }
+}
+
+impl std::fmt::Debug for StaticSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("StaticSymbol").field(&self.name).finish()
+    }
+}
+
+/// Create a [`StaticSymbol`] for the given name.
+///
+/// `sym!("name")` expands to a reference to a `static` `StaticSymbol`, the `Symbol` is created
+/// the first time it's used and cached afterwards. It can be used with any method that takes an
+/// argument that implements `ToSymbol`. If a target is provided, `sym!(&target, "name")`
+/// returns the `Symbol<'static>` immediately.
+///
+/// ```
+/// # use jlrs::prelude::*;
+/// # use jlrs::util::test::JULIA;
+/// # fn main() {
+/// # JULIA.with(|j| {
+/// # let mut julia = j.borrow_mut();
+/// # let mut frame = StackFrame::new();
+/// # let mut julia = julia.instance(&mut frame);
+/// julia.scope(|mut frame| {
+///     let _func = Module::base(&frame).function(&frame, jlrs::sym!("+"))?;
+///     let symbol = jlrs::sym!(&frame, "+");
+///     assert_eq!(symbol.as_str()?, "+");
+///     Ok(())
+/// }).unwrap();
+/// # });
+/// # }
+/// ```
+///
+/// [`StaticSymbol`]: crate::wrappers::ptr::symbol::StaticSymbol
+#[macro_export]
+macro_rules! sym {
+    ($name:literal) => {{
+        static SYMBOL: $crate::wrappers::ptr::symbol::StaticSymbol =
+            $crate::wrappers::ptr::symbol::StaticSymbol::new($name);
+        &SYMBOL
+    }};
+    ($target:expr, $name:literal) => {{
+        static SYMBOL: $crate::wrappers::ptr::symbol::StaticSymbol =
+            $crate::wrappers::ptr::symbol::StaticSymbol::new($name);
+        SYMBOL.get($target)
+    }};
+}
+
 /// A reference to a [`Symbol`] that has not been explicitly rooted.
 pub type SymbolRef<'scope> = Ref<'scope, 'static, Symbol<'scope>>;
 impl_valid_layout!(SymbolRef, Symbol);
@@ -635,9 +635,16 @@ impl<'scope, 'data> Value<'scope, 'data> {
     /// if the layout of `T::Output` is incompatible with the layout of the type in Julia.
     pub fn unbox<T: Unbox + Typecheck>(self) -> JlrsResult<T::Output> {
         if !self.is::<T>() {
-            Err(AccessError::InvalidLayout {
-                value_type: self.datatype().display_string_or(CANNOT_DISPLAY_TYPE),
-            })?;
+            let value_type = self.datatype().display_string_or(CANNOT_DISPLAY_TYPE);
+            if let Some(mismatch) = T::layout_mismatch(self.datatype()) {
+                Err(TypeError::LayoutMismatch {
+                    rust_type: std::any::type_name::<T>().into(),
+                    value_type,
+                    mismatch,
+                })?;
+            } else {
+                Err(AccessError::InvalidLayout { value_type })?;
+            }
         }
 
         // Safety: self.is::<T>() returning true guarantees this is safe
@@ -1549,7 +1556,15 @@ impl<'scope, 'data> FieldAccessor<'scope, 'data> {
             let ty = self.current_field_type.unwrap().value();
             if !T::valid_layout(ty) {
                 let value_type = ty.display_string_or(CANNOT_DISPLAY_TYPE).into();
-                Err(AccessError::InvalidLayout { value_type })?;
+                if let Some(mismatch) = T::layout_mismatch(ty) {
+                    Err(TypeError::LayoutMismatch {
+                        rust_type: std::any::type_name::<T>().into(),
+                        value_type,
+                        mismatch,
+                    })?;
+                } else {
+                    Err(AccessError::InvalidLayout { value_type })?;
+                }
             }
 
             #[cfg(not(feature = "lts"))]
@@ -6,7 +6,7 @@ mod tests {
     use jlrs::{
         layout::valid_layout::ValidLayout,
         prelude::*,
-        wrappers::ptr::array::dimensions::Dims,
+        wrappers::ptr::{array::dimensions::Dims, string::StringRef},
     };
 
     use crate::util::JULIA;
@@ -356,6 +356,73 @@ mod tests {
         });
     }
 
+    fn strings_roundtrip() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let strings = vec!["foo", "bär", ""];
+                    let arr = Array::from_strings(frame.as_extended_target(), &strings)?;
+                    assert!(arr.contains::<Option<StringRef>>());
+                    assert_eq!(arr.to_strings()?, strings);
+
+                    let joined = Module::base(&frame)
+                        .function(&frame, "join")?
+                        .wrapper()
+                        .call1(&mut frame, arr.as_value())
+                        .into_jlrs_result()?;
+                    assert_eq!(joined.cast::<JuliaString>()?.as_str()?, "foobär");
+
+                    let parts = Value::eval_string(&mut frame, "split(\"a b c\")")
+                        .into_jlrs_result()?
+                        .cast::<Array>()?;
+                    assert_eq!(parts.to_strings()?, ["a", "b", "c"]);
+
+                    let arr = Value::eval_string(&mut frame, "Vector{String}(undef, 1)")
+                        .into_jlrs_result()?
+                        .cast::<Array>()?;
+                    assert!(arr.to_strings().is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn vecs_roundtrip() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let vecs = vec![vec![1.0f64, 2.0], vec![], vec![3.0]];
+                    let arr = Array::from_vecs(frame.as_extended_target(), vecs.clone())?;
+                    assert!(arr.element_type().is::<DataType>());
+                    assert_eq!(arr.to_vecs::<f64>()?, vecs);
+                    assert!(arr.to_vecs::<f32>().is_err());
+
+                    let lengths = Value::eval_string(&mut frame, "x -> length.(x)")
+                        .into_jlrs_result()?
+                        .call1(&mut frame, arr.as_value())
+                        .into_jlrs_result()?
+                        .cast::<Array>()?
+                        .copy_inline_data::<isize>()?;
+                    assert_eq!(lengths.as_slice(), &[2, 0, 1]);
+
+                    let arr = Value::eval_string(&mut frame, "[[1, 2], [3 4; 5 6]]")
+                        .into_jlrs_result()?
+                        .cast::<Array>()?;
+                    assert_eq!(arr.to_vecs::<i64>()?, [vec![1, 2], vec![3, 5, 4, 6]]);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
     #[test]
     fn arrays_tests() {
         array_can_be_cast();
@@ -377,5 +444,7 @@ mod tests {
         cannot_access_f32_as_unrestricted_value_mut();
         convert_back_to_value();
         invalid_layout();
+        strings_roundtrip();
+        vecs_roundtrip();
     }
 }
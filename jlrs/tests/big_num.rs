@@ -0,0 +1,77 @@
+mod util;
+
+#[cfg(all(feature = "sync-rt", feature = "bignum"))]
+mod tests {
+    use jlrs::{
+        convert::big_num::{BigFloatString, FromJuliaBigNum, IntoJuliaBigNum},
+        prelude::*,
+    };
+    use num_bigint::BigInt;
+
+    use super::util::JULIA;
+
+    fn bigint_roundtrip() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let n = -BigInt::from(u128::MAX) * BigInt::from(7);
+                    let value = n.to_julia_big(frame.as_extended_target())?;
+                    assert_eq!(value.datatype().name(), "BigInt");
+                    assert_eq!(BigInt::from_julia_big(&mut frame, value)?, n);
+
+                    let value = Value::eval_string(&mut frame, "big(2)^200").into_jlrs_result()?;
+                    assert_eq!(
+                        BigInt::from_julia_big(&mut frame, value)?,
+                        BigInt::from(2).pow(200)
+                    );
+
+                    let value = Value::new(&mut frame, 1i64);
+                    assert!(BigInt::from_julia_big(&mut frame, value).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn bigfloat_roundtrip() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let value =
+                        Value::eval_string(&mut frame, "BigFloat(1) / 3").into_jlrs_result()?;
+                    let s = BigFloatString::from_julia_big(&mut frame, value)?;
+                    assert_eq!(s.precision, 256);
+                    assert!(s.value.starts_with("0.3333333333"));
+
+                    let back = s.to_julia_big(frame.as_extended_target())?;
+                    let is_equal = Module::base(&frame)
+                        .function(&frame, "==")?
+                        .wrapper()
+                        .call2(&mut frame, value, back)
+                        .into_jlrs_result()?
+                        .unbox::<bool>()?;
+                    assert!(is_equal.as_bool());
+
+                    let s = BigFloatString::new("1.5", 64);
+                    let value = s.to_julia_big(frame.as_extended_target())?;
+                    assert_eq!(BigFloatString::from_julia_big(&mut frame, value)?, s);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn big_num_tests() {
+        bigint_roundtrip();
+        bigfloat_roundtrip();
+    }
+}
@@ -0,0 +1,73 @@
+mod util;
+#[cfg(all(feature = "sync-rt", feature = "ccall", feature = "jlrs-log"))]
+mod tests {
+    use jlrs::{ccall::JuliaLogger, prelude::*};
+
+    use super::util::JULIA;
+
+    unsafe extern "C" fn logs_messages() {
+        log::debug!("Logged at debug level");
+        log::warn!(target: "jlrs_tests", answer = 42; "Hello from Rust");
+    }
+
+    fn forward_log_records() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    JuliaLogger::init()?;
+
+                    Value::eval_string(
+                        &mut frame,
+                        "function capturelogs(fptr::Ptr{Cvoid}, level)::String
+                            io = IOBuffer()
+                            logger = Base.CoreLogging.SimpleLogger(io, level)
+                            Base.CoreLogging.with_logger(logger) do
+                                ccall(fptr, Cvoid, ())
+                            end
+                            String(take!(io))
+                        end",
+                    )
+                    .into_jlrs_result()?;
+
+                    let fn_ptr = Value::new(&mut frame, logs_messages as *mut std::ffi::c_void);
+                    let capturelogs = Module::main(&frame)
+                        .function(&frame, "capturelogs")?
+                        .wrapper();
+                    let logging = Module::base(&frame)
+                        .submodule(&frame, "CoreLogging")?
+                        .wrapper();
+
+                    let info = logging.global(&frame, "Info")?.value();
+                    let output = capturelogs
+                        .call2(&mut frame, fn_ptr, info)
+                        .into_jlrs_result()?
+                        .unbox::<String>()?
+                        .unwrap();
+
+                    assert!(!output.contains("Logged at debug level"));
+                    assert!(output.contains("Warning: Hello from Rust"));
+                    assert!(output.contains("answer = 42"));
+
+                    let debug = logging.global(&frame, "Debug")?.value();
+                    let output = capturelogs
+                        .call2(&mut frame, fn_ptr, debug)
+                        .into_jlrs_result()?
+                        .unbox::<String>()?
+                        .unwrap();
+
+                    assert!(output.contains("Debug: Logged at debug level"));
+                    assert!(output.contains("Warning: Hello from Rust"));
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn ccall_log_tests() {
+        forward_log_records();
+    }
+}
@@ -0,0 +1,98 @@
+mod util;
+
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use jlrs::{call::cfunction::CFunction, prelude::*};
+
+    use super::util::JULIA;
+
+    extern "C" fn mul_add(a: f64, b: f64, c: i32) -> f64 {
+        a * b + c as f64
+    }
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    unsafe extern "C" fn increment() {
+        COUNTER.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn call_julia_function() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = (mul_add as extern "C" fn(f64, f64, i32) -> f64)
+                        .julia_function(frame.as_extended_target())?;
+
+                    let a = Value::new(&mut frame, 2.0f64);
+                    let b = Value::new(&mut frame, 3.0f64);
+                    let c = Value::new(&mut frame, 1i32);
+                    let res = func.call3(&mut frame, a, b, c).into_jlrs_result()?;
+                    assert_eq!(res.unbox::<f64>()?, 7.0);
+
+                    // The arguments are typed, so calling it with the wrong types is an error.
+                    assert!(func.call3(&mut frame, a, b, b).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn call_returns_nothing() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = (increment as unsafe extern "C" fn())
+                        .julia_function(frame.as_extended_target())?;
+
+                    let before = COUNTER.load(Ordering::Relaxed);
+                    let res = func.call0(&mut frame).into_jlrs_result()?;
+                    assert!(res.is::<Nothing>());
+                    assert_eq!(COUNTER.load(Ordering::Relaxed), before + 1);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn pass_pointer_to_julia() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let ptr =
+                        (mul_add as extern "C" fn(f64, f64, i32) -> f64).julia_pointer(&mut frame);
+                    assert!(ptr.is::<*mut std::ffi::c_void>());
+
+                    let func = Value::eval_string(
+                        &mut frame,
+                        "cfunction_call(ptr) = ccall(ptr, Float64, (Float64, Float64, Int32), 1.0, 2.0, 3)",
+                    )
+                    .into_jlrs_result()?;
+                    let res = func.call1(&mut frame, ptr).into_jlrs_result()?;
+                    assert_eq!(res.unbox::<f64>()?, 5.0);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn cfunction_tests() {
+        call_julia_function();
+        call_returns_nothing();
+        pass_pointer_to_julia();
+    }
+}
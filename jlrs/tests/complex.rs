@@ -0,0 +1,80 @@
+mod util;
+
+#[cfg(all(feature = "sync-rt", feature = "complex"))]
+mod tests {
+    use jlrs::prelude::*;
+    use num_complex::Complex;
+
+    use super::util::JULIA;
+
+    fn complex_roundtrip() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let z = Value::new(&mut frame, Complex::new(1.0f64, 2.0));
+                    assert_eq!(z.datatype().name(), "Complex");
+                    assert!(z.is::<Complex<f64>>());
+                    assert!(!z.is::<Complex<f32>>());
+
+                    let res = Module::base(&frame)
+                        .function(&frame, "*")?
+                        .wrapper()
+                        .call2(&mut frame, z, z)
+                        .into_jlrs_result()?
+                        .unbox::<Complex<f64>>()?;
+                    assert_eq!(res, Complex::new(-3.0, 4.0));
+
+                    let z = Value::eval_string(&mut frame, "Complex{Int32}(3, 4)")
+                        .into_jlrs_result()?;
+                    assert_eq!(z.unbox::<Complex<i32>>()?, Complex::new(3, 4));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn complex_arrays() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let data = vec![Complex::new(1.0f64, 0.0), Complex::new(0.0, 1.0)];
+                    let arr =
+                        Array::from_vec(frame.as_extended_target(), data, 2)?.into_jlrs_result()?;
+                    assert!(arr.contains::<Complex<f64>>());
+
+                    let res = Module::base(&frame)
+                        .function(&frame, "sum")?
+                        .wrapper()
+                        .call1(&mut frame, arr.as_value())
+                        .into_jlrs_result()?
+                        .unbox::<Complex<f64>>()?;
+                    assert_eq!(res, Complex::new(1.0, 1.0));
+
+                    let arr = Value::eval_string(&mut frame, "ComplexF32[1 + 2im, 3 - 4im]")
+                        .into_jlrs_result()?
+                        .cast::<Array>()?;
+                    let data = arr.copy_inline_data::<Complex<f32>>()?;
+                    assert_eq!(
+                        data.as_slice(),
+                        &[Complex::new(1.0, 2.0), Complex::new(3.0, -4.0)]
+                    );
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn complex_tests() {
+        complex_roundtrip();
+        complex_arrays();
+    }
+}
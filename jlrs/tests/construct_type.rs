@@ -0,0 +1,98 @@
+mod util;
+
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{
+        convert::construct_type::ConstructType,
+        prelude::*,
+        wrappers::ptr::{array::ArrayRef, module::ModuleRef},
+    };
+
+    use super::util::JULIA;
+
+    fn construct_primitive_types() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let ty = f64::construct_type(frame.as_extended_target());
+                    assert!(ty.egal(DataType::float64_type(&frame).as_value()));
+
+                    let ty = Bool::construct_type(frame.as_extended_target());
+                    assert!(ty.egal(DataType::bool_type(&frame).as_value()));
+
+                    let ty = Value::construct_type(frame.as_extended_target());
+                    assert!(ty.egal(DataType::any_type(&frame).as_value()));
+
+                    let ty = ModuleRef::construct_type(frame.as_extended_target());
+                    assert!(ty.egal(DataType::module_type(&frame).as_value()));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn construct_parametric_types() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let ty = TypedArray::<f64>::construct_type(frame.as_extended_target());
+                    let expected =
+                        Value::eval_string(&mut frame, "Array{Float64}").into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    let ty = Tuple2::<i32, f64>::construct_type(frame.as_extended_target());
+                    let expected = Value::eval_string(&mut frame, "Tuple{Int32, Float64}")
+                        .into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    let ty = Tuple0::construct_type(frame.as_extended_target());
+                    let expected = Value::eval_string(&mut frame, "Tuple{}").into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    let ty = <*mut u8>::construct_type(frame.as_extended_target());
+                    let expected =
+                        Value::eval_string(&mut frame, "Ptr{UInt8}").into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    let ty = Tuple1::<Option<ArrayRef>>::construct_type(frame.as_extended_target());
+                    let expected =
+                        Value::eval_string(&mut frame, "Tuple{Array}").into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn typed_array_new_with_constructed_type() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let arr = TypedArray::<Tuple2<i8, f32>>::new(frame.as_extended_target(), 4)
+                        .into_jlrs_result()?;
+                    assert_eq!(unsafe { arr.dimensions().as_slice() }, &[4]);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn construct_type_tests() {
+        construct_primitive_types();
+        construct_parametric_types();
+        typed_array_new_with_constructed_type();
+    }
+}
@@ -0,0 +1,196 @@
+mod util;
+
+#[cfg(all(feature = "sync-rt", feature = "dates"))]
+mod tests {
+    use std::convert::TryFrom;
+
+    use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+    use jlrs::{
+        convert::construct_type::ConstructType,
+        prelude::*,
+        wrappers::inline::dates::{load_dates, Date, DateTime, Day, Millisecond, Month, Time},
+    };
+
+    use super::util::JULIA;
+
+    fn typecheck_before_load_dates() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let value = Value::eval_string(
+                        &mut frame,
+                        "Base.require(Main, :Dates).Date(2022, 10, 18)",
+                    )
+                    .into_jlrs_result()?;
+                    assert!(value.is::<Date>());
+                    assert!(!value.is::<DateTime>());
+                    assert!(!Value::new(&mut frame, 1i64).is::<Date>());
+
+                    let other = Value::eval_string(
+                        &mut frame,
+                        "module NotDates; struct Date; value::Int64; end; end; NotDates.Date(1)",
+                    )
+                    .into_jlrs_result()?;
+                    assert!(!other.is::<Date>());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn convert_before_load_dates() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let value = Value::new(&mut frame, Time::new(1_000));
+                    assert_eq!(value.datatype().name(), "Time");
+                    assert!(value.is::<Time>());
+                    assert_eq!(value.unbox::<Time>()?, Time::new(1_000));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn dates_roundtrip() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    load_dates(&mut frame)?;
+
+                    let date = NaiveDate::from_ymd_opt(2022, 10, 18).unwrap();
+                    let value = Value::new(&mut frame, Date::from(date));
+                    assert_eq!(value.datatype().name(), "Date");
+                    assert!(value.is::<Date>());
+                    assert!(value.is::<NaiveDate>());
+                    assert!(!value.is::<DateTime>());
+                    assert_eq!(value.unbox::<NaiveDate>()?, Some(date));
+
+                    let value = Value::eval_string(
+                        &mut frame,
+                        "Base.require(Main, :Dates).DateTime(1970, 1, 2, 3, 4, 5, 6)",
+                    )
+                    .into_jlrs_result()?;
+                    assert_eq!(value.unbox::<DateTime>()?.value, 62135596800000 + 97445006);
+                    let expected = NaiveDate::from_ymd_opt(1970, 1, 2)
+                        .unwrap()
+                        .and_hms_milli_opt(3, 4, 5, 6)
+                        .unwrap();
+                    assert_eq!(value.unbox::<NaiveDateTime>()?, Some(expected));
+
+                    let time = NaiveTime::from_hms_nano_opt(12, 34, 56, 789).unwrap();
+                    let value = Value::new(&mut frame, Time::from(time));
+                    assert_eq!(value.unbox::<NaiveTime>()?, Some(time));
+
+                    let value = Value::new(&mut frame, Date::new(i64::MAX));
+                    assert_eq!(value.unbox::<NaiveDate>()?, None);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn periods() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let date = Value::new(
+                        &mut frame,
+                        Date::from(NaiveDate::from_ymd_opt(2022, 1, 31).unwrap()),
+                    );
+                    let month = Value::new(&mut frame, Month::new(1));
+                    assert_eq!(month.datatype().name(), "Month");
+
+                    let res = Module::base(&frame)
+                        .function(&frame, "+")?
+                        .wrapper()
+                        .call2(&mut frame, date, month)
+                        .into_jlrs_result()?
+                        .unbox::<NaiveDate>()?;
+                    assert_eq!(res, NaiveDate::from_ymd_opt(2022, 2, 28));
+
+                    let day = Value::new(&mut frame, Day::new(2));
+                    let ms_ty = Millisecond::construct_type(frame.as_extended_target());
+                    let res = Module::base(&frame)
+                        .function(&frame, "convert")?
+                        .wrapper()
+                        .call2(&mut frame, ms_ty, day)
+                        .into_jlrs_result()?
+                        .unbox::<Millisecond>()?;
+                    assert_eq!(Duration::try_from(res).unwrap(), Duration::days(2));
+                    assert!(Duration::try_from(Day::new(i64::MAX)).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn datetime_arrays() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let start = NaiveDate::from_ymd_opt(2000, 1, 1)
+                        .unwrap()
+                        .and_hms_opt(0, 0, 0)
+                        .unwrap();
+                    let data: Vec<DateTime> = (0..4)
+                        .map(|i| DateTime::from(start + Duration::hours(i)))
+                        .collect();
+
+                    let arr = Array::from_vec(frame.as_extended_target(), data.clone(), 4)?
+                        .into_jlrs_result()?;
+                    assert!(arr.contains::<DateTime>());
+
+                    let res = Module::base(&frame)
+                        .function(&frame, "maximum")?
+                        .wrapper()
+                        .call1(&mut frame, arr.as_value())
+                        .into_jlrs_result()?
+                        .unbox::<NaiveDateTime>()?;
+                    assert_eq!(res, Some(start + Duration::hours(3)));
+
+                    let diff = Module::base(&frame)
+                        .function(&frame, "diff")?
+                        .wrapper()
+                        .call1(&mut frame, arr.as_value())
+                        .into_jlrs_result()?
+                        .cast::<Array>()?;
+                    let diff = diff.copy_inline_data::<Millisecond>()?;
+                    assert!(diff.as_slice().iter().all(|ms| ms.value == 3_600_000));
+
+                    assert_eq!(arr.copy_inline_data::<DateTime>()?.as_slice(), &data[..]);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn dates_tests() {
+        typecheck_before_load_dates();
+        convert_before_load_dates();
+        dates_roundtrip();
+        periods();
+        datetime_arrays();
+    }
+}
@@ -2,7 +2,17 @@ mod util;
 
 #[cfg(all(test, feature = "jlrs-derive", feature = "sync-rt"))]
 mod tests {
-    use jlrs::prelude::*;
+    use jlrs::{
+        convert::{construct_type::ConstructType, julia_struct::JuliaStruct},
+        error::{JlrsError, TypeError},
+        layout::{
+            diagnostics::LayoutMismatch,
+            julia_enum::{IntoJuliaVariant, RegisterEnum},
+            valid_layout::ValidLayout,
+        },
+        prelude::*,
+        wrappers::ptr::named_tuple::{FromNamedTuple, IntoNamedTuple},
+    };
 
     use super::util::{derive_impls::*, JULIA_DERIVE};
 
@@ -1085,6 +1095,372 @@ mod tests {
         })
     }
 
+    fn derive_bits_type_array() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let data = vec![BitsTypeUInt8 { a: 1 }, BitsTypeUInt8 { a: 2 }];
+                    let arr =
+                        Array::from_vec(frame.as_extended_target(), data, 2)?.into_jlrs_result()?;
+
+                    assert!(arr.contains::<BitsTypeUInt8>());
+                    let data = unsafe { arr.copy_inline_data::<BitsTypeUInt8>()? };
+                    assert_eq!(data[1].a, 2);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn derive_mutable_bits_array() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let data = vec![MutableBits { a: 1 }, MutableBits { a: 2 }];
+                    let arr = Array::from_vec(frame.as_extended_target(), data, 2);
+                    assert!(arr.is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn derive_into_julia_non_bits() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let s = JuliaString::new(&mut frame, "foo");
+                    let v = Value::new(
+                        &mut frame,
+                        NonBitsUnion {
+                            a: Some(s.as_value().as_ref()),
+                        },
+                    );
+
+                    assert!(v.is::<NonBitsUnion>());
+                    let first = v.get_nth_field(&mut frame, 0).unwrap();
+                    assert_eq!(first.unbox::<String>().unwrap().unwrap(), "foo");
+
+                    let module = Module::main(&frame).as_ref();
+                    let v = Value::new(
+                        &mut frame,
+                        WithPropagatedLifetime {
+                            a: WithGenericT { a: Some(module) },
+                        },
+                    );
+                    assert!(v.is::<WithPropagatedLifetime>());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn derive_into_julia_mutable() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let a = Value::new(&mut frame, 3.0f64);
+                    let v = Value::new(
+                        &mut frame,
+                        MutableWithValue {
+                            a: Some(a.as_ref()),
+                            b: 2,
+                        },
+                    );
+
+                    assert!(v.datatype().mutable());
+                    assert!(v.is::<MutableWithValue>());
+
+                    let first = v.get_nth_field(&mut frame, 0).unwrap();
+                    assert_eq!(first.unbox::<f64>().unwrap(), 3.0);
+                    let second = v.get_nth_field(&mut frame, 1).unwrap();
+                    assert_eq!(second.unbox::<i64>().unwrap(), 2);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn derive_construct_type() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let ty = WithGenericT::<i32>::construct_type(frame.as_extended_target());
+                    let expected =
+                        Value::eval_string(&mut frame, "WithGeneric.WithGenericT{Int32}")
+                            .into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    let ty = WithNestedGenericT::<f64>::construct_type(frame.as_extended_target());
+                    let expected =
+                        Value::eval_string(&mut frame, "WithGeneric.WithNestedGenericT{Float64}")
+                            .into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn derive_julia_enum() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let green = Value::eval_string(&mut frame, "Enums.green").into_jlrs_result()?;
+                    assert!(!green.is::<Color>());
+
+                    Color::register(&mut frame)?;
+                    assert!(green.is::<Color>());
+                    assert_eq!(green.unbox::<Color>()?, Color::Green);
+
+                    assert!(ColorMissingVariant::register(&mut frame).is_err());
+                    assert!(ColorWrongValue::register(&mut frame).is_err());
+                    assert!(ColorWrongRepr::register(&mut frame).is_err());
+                    assert!(!green.is::<ColorMissingVariant>());
+                    assert!(!green.is::<ColorWrongValue>());
+                    assert!(!green.is::<ColorWrongRepr>());
+
+                    let blue = Value::new(&mut frame, Color::Blue);
+                    let expected =
+                        Value::eval_string(&mut frame, "Enums.blue").into_jlrs_result()?;
+                    assert!(blue.egal(expected));
+
+                    let ty = Color::construct_type(frame.as_extended_target());
+                    let expected =
+                        Value::eval_string(&mut frame, "Enums.Color").into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    let data = vec![Color::Red, Color::Blue];
+                    let colors = Array::from_vec(frame.as_extended_target(), data.clone(), 2)?
+                        .into_jlrs_result()?;
+                    assert!(colors.contains::<Color>());
+                    assert_eq!(colors.copy_inline_data::<Color>()?.as_slice(), &data[..]);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn derive_julia_sum_type() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let circle =
+                        Value::eval_string(&mut frame, "Enums.Circle(2.0)").into_jlrs_result()?;
+                    assert!(circle.is::<Shape>());
+                    assert!(matches!(circle.unbox::<Shape>()?, Shape::Circle { r } if r == 2.0));
+
+                    let rect = Shape::Rect(1.0, 3.0).into_julia_variant(&mut frame);
+                    assert_eq!(rect.datatype().name(), "Rect");
+                    assert!(
+                        matches!(rect.unbox::<Shape>()?, Shape::Rect(w, h) if w == 1.0 && h == 3.0)
+                    );
+
+                    let no_shape = Shape::NoShape.into_julia_variant(&mut frame);
+                    let expected =
+                        Value::eval_string(&mut frame, "Enums.NoShape()").into_jlrs_result()?;
+                    assert!(no_shape.egal(expected));
+
+                    let label = JuliaString::new(&mut frame, "label");
+                    let labeled = Shape::Labeled {
+                        label: Some(label.as_value().as_ref()),
+                    }
+                    .into_julia_variant(&mut frame);
+                    let field = labeled.get_nth_field(&mut frame, 0)?;
+                    assert_eq!(field.unbox::<String>()?.unwrap(), "label");
+
+                    let not_a_shape = Value::new(&mut frame, 1.0f64);
+                    assert!(!not_a_shape.is::<Shape>());
+
+                    let ty = Shape::construct_type(frame.as_extended_target());
+                    let expected =
+                        Value::eval_string(&mut frame, "Enums.Shape").into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn derive_julia_struct() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    assert_eq!(
+                        DefinedBits::julia_definition(),
+                        "struct DefinedBits\n    a::Core.Int8\n    b::Core.Float64\n    c::Core.Bool\n    d::Core.Char\n    e::Core.Tuple{Core.Int16, Core.UInt8}\nend\n"
+                    );
+                    assert_eq!(
+                        DefinedGeneric::<i32>::julia_definition(),
+                        "struct DefinedGeneric{T}\n    a::T\n    b::Core.Any\n    c::Core.Tuple{T}\nend\n"
+                    );
+                    assert_eq!(
+                        DefinedUnion::julia_definition(),
+                        "struct DefinedUnion\n    a::Core.Int8\n    b::Union{Int16, Int32}\n    c::Core.Int8\nend\n"
+                    );
+
+                    let module = Value::eval_string(&mut frame, "module Defined end")
+                        .into_jlrs_result()?
+                        .cast::<Module>()?;
+
+                    let ty = DefinedBits::define(frame.as_extended_target(), module)?;
+                    assert!(ty.is::<DefinedBits>());
+
+                    let ty = DefinedGeneric::<i32>::define(frame.as_extended_target(), module)?;
+                    assert!(ty.is::<DefinedGeneric<i32>>());
+                    assert_eq!(ty.name(), "DefinedGeneric");
+
+                    let ty = DefinedUnion::define(frame.as_extended_target(), module)?;
+                    assert!(ty.is::<DefinedUnion>());
+
+                    let defined = DefinedBits {
+                        a: 1,
+                        b: 2.0,
+                        c: Bool::new(true),
+                        d: Char::new('c'),
+                        e: Tuple2(3, 4),
+                    };
+                    let v = Value::new(&mut frame, defined);
+                    assert_eq!(v.unbox::<DefinedBits>()?.b, 2.0);
+
+                    // Redefining a struct with the same definition is allowed
+                    assert!(DefinedBits::define(frame.as_extended_target(), module).is_ok());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn derive_layout_mismatch() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let s = BitsUInt8TupleInt32Int64 {
+                        a: 1,
+                        b: Tuple2(2, 3),
+                    };
+                    let v = Value::new(&mut frame, s);
+                    let ty = v.datatype().as_value();
+
+                    assert!(BitsUInt8TupleInt32Int64::layout_mismatch(ty).is_none());
+
+                    match BitsIntBool::layout_mismatch(ty) {
+                        Some(LayoutMismatch::FieldType {
+                            name, is_pointer, ..
+                        }) => {
+                            assert_eq!(name, "a");
+                            assert!(!is_pointer);
+                        }
+                        _ => panic!("expected a field type mismatch"),
+                    }
+
+                    match BitsCharFloat32Float64::layout_mismatch(ty) {
+                        Some(LayoutMismatch::FieldCount { rust, julia }) => {
+                            assert_eq!(rust, 3);
+                            assert_eq!(julia, 2);
+                        }
+                        _ => panic!("expected a field count mismatch"),
+                    }
+
+                    let err = v.unbox::<BitsIntBool>().unwrap_err();
+                    match *err {
+                        JlrsError::TypeError(TypeError::LayoutMismatch {
+                            ref mismatch, ..
+                        }) => {
+                            assert!(matches!(mismatch, LayoutMismatch::FieldType { .. }))
+                        }
+                        _ => panic!("expected a layout mismatch"),
+                    }
+                    assert!(err.to_string().contains("field a"));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn derive_named_tuple() {
+        JULIA_DERIVE.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let options = NamedTupleOptions {
+                        scale: 0.5,
+                        iterations: 10,
+                        offset: jlrs::wrappers::inline::tuple::Tuple2(1, -1),
+                    };
+
+                    let nt = options.clone().into_named_tuple(frame.as_extended_target())?;
+                    let ty = nt.as_value().datatype().display_string()?;
+                    assert_eq!(
+                        ty,
+                        "NamedTuple{(:scale, :iterations, :offset), Tuple{Float64, Int64, Tuple{Int32, Int32}}}"
+                    );
+
+                    let converted = NamedTupleOptions::from_named_tuple(&mut frame, nt)?;
+                    assert_eq!(converted, options);
+
+                    let iterations = nt.get(&mut frame, "iterations")?.unwrap();
+                    assert_eq!(iterations.unbox::<i64>()?, 10);
+
+                    let partial = unsafe { Value::eval_string(&mut frame, "(scale = 1.0, iterations = 2)") }
+                        .into_jlrs_result()?
+                        .cast::<jlrs::wrappers::ptr::named_tuple::NamedTuple>()?;
+                    assert!(NamedTupleOptions::from_named_tuple(&mut frame, partial).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
     #[test]
     fn derive_tests() {
         derive_bits_type_bool();
@@ -1118,6 +1494,16 @@ mod tests {
         derive_with_set_generic_tuple();
         derive_with_value_type();
         derive_zero_sized();
+        derive_construct_type();
+        derive_bits_type_array();
+        derive_mutable_bits_array();
+        derive_into_julia_non_bits();
+        derive_into_julia_mutable();
+        derive_julia_enum();
+        derive_julia_sum_type();
+        derive_julia_struct();
+        derive_layout_mismatch();
+        derive_named_tuple();
         #[cfg(not(all(target_os = "windows", feature = "lts")))]
         derive_double_variant();
         #[cfg(not(all(target_os = "windows", feature = "lts")))]
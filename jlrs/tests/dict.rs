@@ -0,0 +1,119 @@
+mod util;
+
+#[cfg(all(feature = "sync-rt", not(all(target_os = "windows", feature = "lts"))))]
+mod tests {
+    use std::collections::HashMap;
+
+    use jlrs::{prelude::*, wrappers::ptr::dict::Dict};
+
+    use super::util::JULIA;
+
+    fn dict_from_hash_map() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let mut map = HashMap::new();
+                    map.insert(1i64, 2.0f64);
+                    map.insert(2, 4.0);
+
+                    let dict = Dict::from_iter(frame.as_extended_target(), map.clone())?;
+                    assert_eq!(dict.len(), 2);
+                    assert!(dict.as_value().is::<Dict>());
+
+                    let converted = dict.to_hash_map::<i64, f64>(&mut frame)?;
+                    assert_eq!(converted, map);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn dict_string_keys() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let a = JuliaString::new(&mut frame, "a").as_value();
+                    let b = JuliaString::new(&mut frame, "b").as_value();
+                    let one = Value::new(&mut frame, 1u32);
+                    let two = Value::new(&mut frame, 2u32);
+
+                    let dict = Dict::from_values::<JuliaString, u32, _, _>(
+                        frame.as_extended_target(),
+                        [(a, one), (b, two)],
+                    )?;
+                    let converted = dict.to_btree_map::<String, u32>(&mut frame)?;
+                    assert_eq!(converted.len(), 2);
+                    assert_eq!(converted[&Ok(String::from("a"))], 1);
+                    assert_eq!(converted[&Ok(String::from("b"))], 2);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn dict_get_insert_remove() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let dict = Dict::new::<i64, i64, _>(frame.as_extended_target())?;
+                    assert!(dict.is_empty());
+
+                    let key = Value::new(&mut frame, 1i64);
+                    let value = Value::new(&mut frame, 3i64);
+                    dict.insert(&mut frame, key, value)?;
+                    assert_eq!(dict.len(), 1);
+                    assert!(dict.contains_key(&mut frame, key)?);
+
+                    let found = dict.get(frame.as_extended_target(), key)?.unwrap();
+                    assert_eq!(found.unbox::<i64>()?, 3);
+
+                    let missing = Value::new(&mut frame, 2i64);
+                    assert!(dict.get(frame.as_extended_target(), missing)?.is_none());
+
+                    let wrong_type = Value::new(&mut frame, 1.5f64);
+                    assert!(dict.insert(&mut frame, wrong_type, value).is_err());
+
+                    dict.remove(&mut frame, key)?;
+                    assert!(dict.is_empty());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn dict_typecheck() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let dict =
+                        Value::eval_string(&mut frame, "Dict(:a => 1)").into_jlrs_result()?;
+                    assert!(dict.cast::<Dict>().is_ok());
+
+                    let not_a_dict = Value::new(&mut frame, 1usize);
+                    assert!(not_a_dict.cast::<Dict>().is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn dict_tests() {
+        dict_from_hash_map();
+        dict_string_keys();
+        dict_get_insert_remove();
+        dict_typecheck();
+    }
+}
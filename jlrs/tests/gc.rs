@@ -1,8 +1,13 @@
 mod util;
 #[cfg(feature = "sync-rt")]
 mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
     use jlrs::{
-        memory::gc::{Gc, GcCollection},
+        memory::gc::{report_external_alloc, report_external_free, Gc, GcCollection, GcEvent},
         prelude::*,
     };
 
@@ -65,10 +70,105 @@ mod tests {
         })
     }
 
+    fn read_gc_stats() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let mut jlrs = jlrs.instance(&mut frame);
+
+            let before = jlrs.gc_stats().unwrap();
+            jlrs.gc_collect(GcCollection::Full);
+            let after = jlrs.gc_stats().unwrap();
+
+            assert!(after.collections > before.collections);
+            assert!(after.full_collections > before.full_collections);
+            assert!(after.total_time >= before.total_time);
+            assert!(after.live_bytes > 0);
+            assert_eq!(
+                after.incremental_collections(),
+                after.collections - after.full_collections
+            );
+
+            jlrs.scope(|frame| {
+                let stats = frame.gc_stats()?;
+                assert!(stats.total_bytes >= stats.total_allocd);
+                Ok(())
+            })
+            .unwrap();
+        })
+    }
+
+    fn gc_callbacks() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let jlrs = jlrs.instance(&mut frame);
+
+            let starts = Arc::new(AtomicUsize::new(0));
+            let full_ends = Arc::new(AtomicUsize::new(0));
+
+            let starts_cb = starts.clone();
+            let full_ends_cb = full_ends.clone();
+            let handle = jlrs.add_gc_callback(move |event| match event {
+                GcEvent::Start { .. } => {
+                    starts_cb.fetch_add(1, Ordering::Relaxed);
+                }
+                GcEvent::End { full } => {
+                    if full {
+                        full_ends_cb.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            });
+
+            jlrs.gc_collect(GcCollection::Full);
+            assert!(starts.load(Ordering::Relaxed) >= 1);
+            assert!(full_ends.load(Ordering::Relaxed) >= 1);
+
+            assert!(jlrs.remove_gc_callback(handle));
+            assert!(!jlrs.remove_gc_callback(handle));
+
+            let n_starts = starts.load(Ordering::Relaxed);
+            jlrs.gc_collect(GcCollection::Full);
+            assert_eq!(starts.load(Ordering::Relaxed), n_starts);
+        })
+    }
+
+    fn external_memory() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let mut jlrs = jlrs.instance(&mut frame);
+
+            const N_BYTES: usize = 1 << 24;
+            let before = jlrs.gc_stats().unwrap();
+            unsafe {
+                report_external_alloc(N_BYTES);
+                report_external_free(N_BYTES);
+            }
+            let after = jlrs.gc_stats().unwrap();
+            assert!(after.total_bytes >= before.total_bytes + N_BYTES as i64);
+
+            jlrs.scope(|mut frame| {
+                let before = frame.gc_stats()?;
+                let data = vec![0u8; N_BYTES];
+                let _array = Array::from_vec(frame.as_extended_target(), data, N_BYTES)?
+                    .into_jlrs_result()?;
+                let after = frame.gc_stats()?;
+                assert!(after.total_bytes >= before.total_bytes + N_BYTES as i64);
+
+                Ok(())
+            })
+            .unwrap();
+        })
+    }
+
     #[test]
     fn gc_tests() {
         disable_enable_gc();
         collect_garbage();
         insert_safepoint();
+        read_gc_stats();
+        gc_callbacks();
+        external_memory();
     }
 }
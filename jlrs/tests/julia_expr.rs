@@ -0,0 +1,129 @@
+mod util;
+
+#[cfg(all(feature = "sync-rt", not(all(target_os = "windows", feature = "lts"))))]
+mod tests {
+    use jlrs::{
+        convert::julia_expr::{self, JuliaExpr},
+        prelude::*,
+    };
+
+    use super::util::JULIA;
+
+    fn build_expr() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let expr = JuliaExpr::call(JuliaExpr::symbol("f"), [JuliaExpr::from(1)]);
+                    let expr = expr.build(frame.as_extended_target())?;
+                    assert_eq!(expr.datatype().name(), "Expr");
+
+                    let expected = unsafe { Value::eval_string(&mut frame, "Expr(:call, :f, 1)") }
+                        .into_jlrs_result()?;
+                    let is_equal = unsafe {
+                        Module::base(&frame)
+                            .function(&frame, "==")?
+                            .wrapper()
+                            .call2(&mut frame, expr, expected)
+                            .into_jlrs_result()?
+                            .unbox::<bool>()?
+                    };
+                    assert!(is_equal.as_bool());
+
+                    let node = JuliaExpr::line_number(3, Some("generated.jl"))
+                        .build(frame.as_extended_target())?;
+                    assert_eq!(node.datatype().name(), "LineNumberNode");
+
+                    let node = JuliaExpr::global_ref(Module::base(&frame), "sum")
+                        .build(frame.as_extended_target())?;
+                    assert_eq!(node.datatype().name(), "GlobalRef");
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn eval_interpolated() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let data = Value::new(&mut frame, 3.0f64);
+                    let main = Module::main(&frame);
+
+                    let expr = JuliaExpr::call(
+                        JuliaExpr::global_ref(Module::base(&frame), "+"),
+                        [JuliaExpr::value(data), JuliaExpr::from(2.0)],
+                    );
+                    let res = expr
+                        .eval(frame.as_extended_target(), main)?
+                        .into_jlrs_result()?;
+                    assert_eq!(res.unbox::<f64>()?, 5.0);
+
+                    // Strings are literals, they're never parsed
+                    let input = "\"); error(\"";
+                    let res = JuliaExpr::from(input)
+                        .eval(frame.as_extended_target(), main)?
+                        .into_jlrs_result()?;
+                    assert_eq!(res.unbox::<String>()?.unwrap(), input);
+
+                    let res = JuliaExpr::quote(JuliaExpr::symbol("x"))
+                        .eval(frame.as_extended_target(), main)?
+                        .into_jlrs_result()?;
+                    assert_eq!(res.cast::<Symbol>()?.as_str()?, "x");
+
+                    let expr = JuliaExpr::block([
+                        JuliaExpr::assign(JuliaExpr::symbol("expr_test_a"), JuliaExpr::from(4)),
+                        JuliaExpr::call(
+                            JuliaExpr::symbol("*"),
+                            [JuliaExpr::symbol("expr_test_a"), JuliaExpr::from(2)],
+                        ),
+                    ]);
+                    let res = expr
+                        .eval(frame.as_extended_target(), main)?
+                        .into_jlrs_result()?;
+                    assert_eq!(res.unbox::<isize>()?, 8);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn parse_and_eval() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let main = Module::main(&frame);
+
+                    let expr = julia_expr::parse(frame.as_extended_target(), "1 + 2", "parsed.jl")?;
+                    let res = julia_expr::eval(&mut frame, main, expr)?.into_jlrs_result()?;
+                    assert_eq!(res.unbox::<isize>()?, 3);
+
+                    let res = julia_expr::include_string(
+                        frame.as_extended_target(),
+                        main,
+                        "error(\"oops\")",
+                        "included.jl",
+                    )?;
+                    let err = res.into_jlrs_result().unwrap_err();
+                    assert!(err.to_string().contains("included.jl"));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn julia_expr_tests() {
+        build_expr();
+        eval_interpolated();
+        parse_and_eval();
+    }
+}
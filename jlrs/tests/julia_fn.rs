@@ -0,0 +1,108 @@
+mod util;
+
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{call::julia_fn::JuliaFn, prelude::*};
+
+    use super::util::JULIA;
+
+    fn call_julia_fn() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "juliafn_mul(a, b) = a * b")
+                        .into_jlrs_result()?;
+                    let mul = JuliaFn::<(f64, i64), f64>::new(func);
+                    let res = mul.call(&mut frame, (1.5, 2))?;
+                    assert_eq!(res, 3.0);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn call_julia_fn_no_args() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "juliafn_const() = UInt8(3)")
+                        .into_jlrs_result()?;
+                    let constant = JuliaFn::<(), u8>::new_checked(&mut frame, func)?;
+                    assert_eq!(constant.call(&mut frame, ())?, 3);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn julia_fn_checks_method() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "juliafn_int(a::Int) = a")
+                        .into_jlrs_result()?;
+                    assert!(JuliaFn::<(i64,), i64>::new_checked(&mut frame, func).is_ok());
+                    assert!(JuliaFn::<(f32,), i64>::new_checked(&mut frame, func).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn julia_fn_checks_return_type() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "juliafn_float(a) = Float64(a)")
+                        .into_jlrs_result()?;
+                    let float = JuliaFn::<(i64,), i64>::new(func);
+                    assert!(float.call(&mut frame, (1,)).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn julia_fn_returns_exception() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "juliafn_throws(a) = error(\"x\")")
+                        .into_jlrs_result()?;
+                    let throws = JuliaFn::<(i64,), i64>::new(func);
+                    assert!(throws.call(&mut frame, (1,)).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn julia_fn_tests() {
+        call_julia_fn();
+        call_julia_fn_no_args();
+        julia_fn_checks_method();
+        julia_fn_checks_return_type();
+        julia_fn_returns_exception();
+    }
+}
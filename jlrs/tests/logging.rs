@@ -0,0 +1,131 @@
+mod util;
+
+#[cfg(all(feature = "sync-rt", feature = "logging"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use jlrs::{
+        logging::{self, LogLevel},
+        prelude::*,
+    };
+
+    use super::util::JULIA;
+
+    fn redirect_stdout() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    logging::init(&mut frame);
+
+                    let output = Arc::new(Mutex::new(Vec::new()));
+                    let cloned = output.clone();
+                    logging::redirect_stdout(&mut frame, move |data| {
+                        cloned.lock().unwrap().extend_from_slice(data)
+                    })?;
+
+                    Value::eval_string(&mut frame, "print(\"Hello from Julia\")")
+                        .into_jlrs_result()?;
+
+                    logging::restore_stdout(&mut frame)?;
+                    assert_eq!(output.lock().unwrap().as_slice(), b"Hello from Julia");
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn redirect_stderr() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    logging::init(&mut frame);
+
+                    let output = Arc::new(Mutex::new(Vec::new()));
+                    let cloned = output.clone();
+                    logging::redirect_stderr(&mut frame, move |data| {
+                        cloned.lock().unwrap().extend_from_slice(data)
+                    })?;
+
+                    Value::eval_string(&mut frame, "println(stderr, \"Error\")")
+                        .into_jlrs_result()?;
+
+                    logging::restore_stderr(&mut frame)?;
+                    assert_eq!(output.lock().unwrap().as_slice(), b"Error\n");
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn redirect_logger() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    logging::init(&mut frame);
+
+                    let records = Arc::new(Mutex::new(Vec::new()));
+                    let cloned = records.clone();
+                    logging::redirect_logger(&mut frame, LogLevel::Info, move |record| {
+                        let key_values: Vec<(String, String)> = record
+                            .key_values
+                            .iter()
+                            .map(|(k, v)| (k.to_string(), v.to_string()))
+                            .collect();
+
+                        cloned.lock().unwrap().push((
+                            record.level,
+                            record.message.to_string(),
+                            record.module.to_string(),
+                            record.line,
+                            key_values,
+                        ))
+                    })?;
+
+                    Value::eval_string(
+                        &mut frame,
+                        "@debug \"Not logged\"\n@warn \"Logged\" x = 1",
+                    )
+                    .into_jlrs_result()?;
+
+                    logging::restore_logger(&mut frame)?;
+
+                    let records = records.lock().unwrap();
+                    assert_eq!(records.len(), 1);
+                    let (level, message, module, line, key_values) = &records[0];
+                    assert_eq!(*level, LogLevel::Warn);
+                    assert_eq!(message, "Logged");
+                    assert_eq!(module, "Main");
+                    assert_eq!(*line, 2);
+                    assert_eq!(key_values, &[("x".to_string(), "1".to_string())]);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn log_levels() {
+        assert_eq!(LogLevel::from_julia(-1000), LogLevel::Debug);
+        assert_eq!(LogLevel::from_julia(-1001), LogLevel::Trace);
+        assert_eq!(LogLevel::from_julia(0), LogLevel::Info);
+        assert_eq!(LogLevel::from_julia(1500), LogLevel::Warn);
+        assert_eq!(LogLevel::from_julia(2000), LogLevel::Error);
+        assert_eq!(LogLevel::from_julia(LogLevel::Warn.to_julia()), LogLevel::Warn);
+    }
+
+    #[test]
+    fn logging_tests() {
+        redirect_stdout();
+        redirect_stderr();
+        redirect_logger();
+        log_levels();
+    }
+}
@@ -556,6 +556,29 @@ mod tests {
         })
     }
 
+    fn cached_function() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            let res = jlrs.instance(&mut frame).scope(|mut frame| unsafe {
+                let base = Module::base(&frame);
+                let f1 = base.cached_function(&frame, "+")?.wrapper();
+                let f2 = base.cached_function(&frame, jlrs::sym!("+"))?.wrapper();
+                assert_eq!(f1.as_value(), f2.as_value());
+
+                let a = Value::new(&mut frame, 1usize);
+                let b = Value::new(&mut frame, 2usize);
+                let res = f2.call2(&mut frame, a, b).into_jlrs_result()?;
+                assert_eq!(res.unbox::<usize>()?, 3);
+
+                assert!(base.cached_function(&frame, "QUX_NOT_A_FUNCTION").is_err());
+                Ok(())
+            });
+
+            assert!(res.is_ok());
+        })
+    }
+
     #[test]
     fn module_tests() {
         core_module();
@@ -589,5 +612,6 @@ mod tests {
         global_can_be_leaked();
         leaked_global_must_exist();
         module_can_be_leaked();
+        cached_function();
     }
 }
@@ -1,7 +1,13 @@
 mod util;
 #[cfg(feature = "sync-rt")]
 mod tests {
-    use jlrs::{layout::typecheck::NamedTuple, prelude::*};
+    use std::collections::{BTreeMap, HashMap};
+
+    use jlrs::{
+        layout::typecheck::NamedTuple,
+        prelude::*,
+        wrappers::ptr::named_tuple::{self, FromNamedTuple, IntoNamedTuple},
+    };
 
     use super::util::JULIA;
 
@@ -44,9 +50,71 @@ mod tests {
         });
     }
 
+    fn named_tuple_wrapper() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let a_value = Value::new(&mut frame, 1u32);
+                    let b_value = Value::new(&mut frame, 2u32);
+                    let nt = named_tuple::NamedTuple::new(
+                        frame.as_extended_target(),
+                        ["a", "b"],
+                        [a_value, b_value],
+                    )?;
+
+                    assert_eq!(nt.len(), 2);
+                    assert_eq!(nt.names()[1].as_str()?, "b");
+                    assert!(nt.as_value().is::<named_tuple::NamedTuple>());
+
+                    let b = nt.get(&mut frame, "b")?.unwrap();
+                    assert_eq!(b.unbox::<u32>()?, 2);
+                    assert!(nt.get(&mut frame, "c")?.is_none());
+
+                    let map = nt.to_hash_map::<u32>(&mut frame)?;
+                    assert_eq!(map["a"], 1);
+                    assert_eq!(map["b"], 2);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn named_tuple_conversions() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let mut map = BTreeMap::new();
+                    map.insert("x", 1.0f64);
+                    map.insert("y", 2.0);
+
+                    let nt = map.into_named_tuple(frame.as_extended_target())?;
+                    assert!(nt.as_value().is::<NamedTuple>());
+
+                    let converted = BTreeMap::<String, f64>::from_named_tuple(&mut frame, nt)?;
+                    assert_eq!(converted["x"], 1.0);
+                    assert_eq!(converted["y"], 2.0);
+
+                    let converted = HashMap::<String, f64>::from_named_tuple(&mut frame, nt)?;
+                    assert_eq!(converted.len(), 2);
+
+                    assert!(HashMap::<String, i64>::from_named_tuple(&mut frame, nt).is_err());
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
     #[test]
     fn named_tuple_tests() {
         create_named_tuple();
         create_named_tuple_macro();
+        named_tuple_wrapper();
+        named_tuple_conversions();
     }
 }
@@ -0,0 +1,46 @@
+mod util;
+
+#[cfg(all(feature = "sync-rt", feature = "rational"))]
+mod tests {
+    use jlrs::{prelude::*, wrappers::inline::rational::Rational};
+    use num_rational::Ratio;
+
+    use super::util::JULIA;
+
+    fn rational_roundtrip() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let half = Rational::from(Ratio::new(2i64, 4));
+                    assert_eq!(half, Rational { num: 1, den: 2 });
+
+                    let half = Value::new(&mut frame, half);
+                    assert_eq!(half.datatype().name(), "Rational");
+
+                    let res = Module::base(&frame)
+                        .function(&frame, "+")?
+                        .wrapper()
+                        .call2(&mut frame, half, half)
+                        .into_jlrs_result()?;
+                    assert_eq!(res.unbox::<Ratio<i64>>()?, Ratio::from_integer(1));
+
+                    let third = Value::eval_string(&mut frame, "Int32(2) // Int32(6)")
+                        .into_jlrs_result()?;
+                    assert!(third.is::<Rational<i32>>());
+                    assert!(!third.is::<Rational<i64>>());
+                    assert_eq!(third.unbox::<Ratio<i32>>()?, Ratio::new(1, 3));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn rational_tests() {
+        rational_roundtrip();
+    }
+}
@@ -0,0 +1,98 @@
+mod util;
+
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{
+        prelude::*,
+        wrappers::{inline::pointer::Ptr, ptr::ref_value::RefValue},
+    };
+
+    use super::util::JULIA;
+
+    fn typed_pointers() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let mut data = vec![1.0f64, 2.0, 3.0];
+                    let ptr = Ptr::new(data.as_mut_ptr());
+                    let value = Value::new(&mut frame, ptr);
+                    assert!(value.is::<Ptr<f64>>());
+                    assert!(!value.is::<Ptr<f32>>());
+                    assert_eq!(value.unbox::<Ptr<f64>>()?, ptr);
+
+                    let idx = Value::new(&mut frame, 2usize);
+                    let loaded = Module::base(&frame)
+                        .function(&frame, "unsafe_load")?
+                        .wrapper()
+                        .call2(&mut frame, value, idx)
+                        .into_jlrs_result()?
+                        .unbox::<f64>()?;
+                    assert_eq!(loaded, 2.0);
+
+                    let value = Value::eval_string(&mut frame, "Ptr{Ptr{Float64}}(C_NULL)")
+                        .into_jlrs_result()?;
+                    assert!(value.is::<Ptr<Ptr<f64>>>());
+                    assert!(value.unbox::<Ptr<Ptr<f64>>>()?.is_null());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn ref_values() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(
+                        &mut frame,
+                        "function jlrs_ref_value_test(r::Ref{Int})
+                            r[] += 40
+                            nothing
+                        end",
+                    )
+                    .into_jlrs_result()?;
+
+                    let value = Value::new(&mut frame, 2isize);
+                    let r = RefValue::new(frame.as_extended_target(), value)?;
+                    assert!(r.as_value().is::<RefValue>());
+                    assert!(!r.is_undefined());
+
+                    func.call1(&mut frame, r.as_value()).into_jlrs_result()?;
+                    assert_eq!(r.read::<isize>()?, 42);
+                    assert!(r.read::<f64>().is_err());
+
+                    r.write(1isize)?;
+                    assert_eq!(r.get(&mut frame)?.unbox::<isize>()?, 1);
+
+                    let r = RefValue::new_undef::<JuliaString, _>(frame.as_extended_target())?;
+                    assert!(r.is_undefined());
+                    assert!(r.get(&mut frame).is_err());
+                    assert!(r.write(1isize).is_err());
+
+                    let s = JuliaString::new(&mut frame, "foo");
+                    r.set(&mut frame, s.as_value())?.into_jlrs_result()?;
+                    assert!(!r.is_undefined());
+                    assert_eq!(r.get(&mut frame)?.cast::<JuliaString>()?.as_str()?, "foo");
+
+                    let n = Value::new(&mut frame, 1usize);
+                    assert!(r.set(&mut frame, n).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn ref_value_tests() {
+        typed_pointers();
+        ref_values();
+    }
+}
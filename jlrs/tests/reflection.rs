@@ -0,0 +1,151 @@
+mod util;
+
+#[cfg(all(feature = "sync-rt", feature = "reflection"))]
+mod tests {
+    use jlrs::{prelude::*, reflection};
+
+    use super::util::JULIA;
+
+    const REFLECTION_MODULE: &str = r#"
+    module ReflectionTests
+    export documented
+
+    "A documented function."
+    documented(x::Int, y; scale=1.0) = x * y * scale
+    documented(xs::Float64...) = sum(xs)
+
+    undocumented(_) = nothing
+
+    struct Foo
+        a::Int
+    end
+
+    module Inner end
+
+    const VALUE = 3
+    end
+    "#;
+
+    fn module_info() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    reflection::init(&mut frame);
+
+                    let module = Value::eval_string(&mut frame, REFLECTION_MODULE)
+                        .into_jlrs_result()?
+                        .cast::<Module>()?;
+
+                    let info = reflection::module_info(&mut frame, module, true)?;
+                    assert_eq!(info.name, "ReflectionTests");
+                    assert!(info.exported.contains(&"documented".into()));
+                    assert!(!info.exported.contains(&"undocumented".into()));
+                    assert!(info.functions.contains(&"documented".into()));
+                    assert!(info.functions.contains(&"undocumented".into()));
+                    assert!(info.types.contains(&"Foo".into()));
+                    assert!(info.submodules.contains(&"Inner".into()));
+                    assert!(info.constants.contains(&"VALUE".into()));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn methods() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    reflection::init(&mut frame);
+
+                    let func = Value::eval_string(&mut frame, "ReflectionTests.documented")
+                        .into_jlrs_result()?;
+
+                    let mut methods = reflection::methods(&mut frame, func)?;
+                    assert_eq!(methods.len(), 2);
+                    methods.sort_by_key(|m| m.line);
+
+                    let first = &methods[0];
+                    assert_eq!(first.name, "documented");
+                    assert_eq!(first.module, "Main.ReflectionTests");
+                    assert_eq!(first.arg_names, ["x", "y"]);
+                    assert_eq!(first.arg_types, ["Int64", "Any"]);
+                    assert_eq!(first.kwarg_names, ["scale"]);
+                    assert!(!first.is_vararg);
+
+                    let second = &methods[1];
+                    assert_eq!(second.arg_names, ["xs"]);
+                    assert!(second.kwarg_names.is_empty());
+                    assert!(second.is_vararg);
+
+                    let func = Value::eval_string(&mut frame, "ReflectionTests.undocumented")
+                        .into_jlrs_result()?;
+                    let methods = reflection::methods(&mut frame, func)?;
+                    assert_eq!(methods[0].arg_names, [""]);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn type_methods() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    reflection::init(&mut frame);
+
+                    let ty = Value::eval_string(
+                        &mut frame,
+                        "struct ReflectionTypeMethods; a::Int; end; ReflectionTypeMethods",
+                    )
+                    .into_jlrs_result()?;
+
+                    let methods = reflection::methods(&mut frame, ty)?;
+                    assert!(!methods.is_empty());
+                    assert!(methods.iter().any(|m| m.arg_names == ["a"]));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn docs() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    reflection::init(&mut frame);
+
+                    let module = Module::main(&frame)
+                        .submodule(&frame, "ReflectionTests")?
+                        .wrapper();
+
+                    let docs = reflection::docs(&mut frame, module, "documented")?;
+                    assert!(docs.unwrap().contains("A documented function."));
+
+                    let docs = reflection::docs(&mut frame, module, "undocumented")?;
+                    assert!(docs.is_none());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn reflection_tests() {
+        module_info();
+        methods();
+        type_methods();
+        docs();
+    }
+}
@@ -0,0 +1,105 @@
+mod util;
+
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{call::rust_function::RustFunction, error::JlrsError, prelude::*};
+
+    use super::util::JULIA;
+
+    fn call_rust_function() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let add = RustFunction::create(frame.as_extended_target(), |frame, args| {
+                        let mut sum = 0;
+                        for arg in args {
+                            sum += arg.unbox::<i64>()?;
+                        }
+                        Ok(Value::new(frame, sum))
+                    })?;
+
+                    let a = Value::new(&mut frame, 1i64);
+                    let b = Value::new(&mut frame, 2i64);
+                    let res = add.call2(&mut frame, a, b).into_jlrs_result()?;
+                    assert_eq!(res.unbox::<i64>()?, 3);
+
+                    let res = add.call0(&mut frame).into_jlrs_result()?;
+                    assert_eq!(res.unbox::<i64>()?, 0);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn pass_to_higher_order_function() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let square =
+                        RustFunction::create(frame.as_extended_target(), |frame, args| {
+                            let x = args[0].unbox::<f64>()?;
+                            Ok(Value::new(frame, x * x))
+                        })?;
+
+                    let data = Array::from_vec(frame.as_extended_target(), vec![1.0, 2.0, 3.0], 3)?
+                        .into_jlrs_result()?;
+                    let res = Module::base(&frame)
+                        .function(&frame, "map")?
+                        .wrapper()
+                        .call2(&mut frame, square, data.as_value())
+                        .into_jlrs_result()?
+                        .cast::<Array>()?;
+
+                    let res = res.copy_inline_data::<f64>()?;
+                    assert_eq!(res.as_slice(), &[1.0, 4.0, 9.0]);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn errors_are_thrown() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let fails = RustFunction::create(frame.as_extended_target(), |_, _| {
+                        Err(JlrsError::exception("failed".into()))?
+                    })?;
+
+                    let err = fails.call0(&mut frame).unwrap_err();
+                    assert_eq!(err.datatype().name(), "RustFunctionError");
+                    assert!(err.error_string_or("").contains("failed"));
+
+                    let panics = RustFunction::create(frame.as_extended_target(), |_, _| {
+                        panic!("panicked")
+                    })?;
+
+                    let err = panics.call0(&mut frame).unwrap_err();
+                    assert_eq!(err.datatype().name(), "RustFunctionError");
+                    let msg = err.error_string_or("");
+                    assert!(msg.contains("Rust function panicked"));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn rust_function_tests() {
+        call_rust_function();
+        pass_to_higher_order_function();
+        errors_are_thrown();
+    }
+}
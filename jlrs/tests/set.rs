@@ -0,0 +1,67 @@
+mod util;
+
+#[cfg(all(feature = "sync-rt", not(all(target_os = "windows", feature = "lts"))))]
+mod tests {
+    use std::collections::{BTreeSet, HashSet};
+
+    use jlrs::{prelude::*, wrappers::ptr::set::Set};
+
+    use super::util::JULIA;
+
+    fn set_from_hash_set() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let set: HashSet<i64> = [1, 2, 3].iter().copied().collect();
+
+                    let julia_set = Set::from_iter(frame.as_extended_target(), set.clone())?;
+                    assert_eq!(julia_set.len(), 3);
+                    assert!(julia_set.as_value().is::<Set>());
+
+                    let converted = julia_set.to_hash_set::<i64>(&mut frame)?;
+                    assert_eq!(converted, set);
+
+                    let converted = julia_set.to_btree_set::<i64>(&mut frame)?;
+                    assert_eq!(converted, set.into_iter().collect::<BTreeSet<_>>());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn set_insert_remove() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let set = Set::new::<u8, _>(frame.as_extended_target())?;
+                    assert!(set.is_empty());
+
+                    let elem = Value::new(&mut frame, 1u8);
+                    set.insert(&mut frame, elem)?;
+                    set.insert(&mut frame, elem)?;
+                    assert_eq!(set.len(), 1);
+                    assert!(set.contains(&mut frame, elem)?);
+
+                    let other = Value::new(&mut frame, 2u8);
+                    assert!(!set.contains(&mut frame, other)?);
+
+                    set.remove(&mut frame, elem)?;
+                    assert!(set.is_empty());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn set_tests() {
+        set_from_hash_set();
+        set_insert_remove();
+    }
+}
@@ -4,7 +4,14 @@ mod util;
 mod tests {
     use std::borrow::Cow;
 
-    use jlrs::{layout::valid_layout::ValidLayout, prelude::*, wrappers::ptr::string::JuliaString};
+    use jlrs::{
+        layout::valid_layout::ValidLayout,
+        prelude::*,
+        wrappers::{
+            inline::sub_string::SubString,
+            ptr::string::{JuliaString, StringView, StringWriter},
+        },
+    };
 
     use crate::util::JULIA;
 
@@ -178,6 +185,76 @@ mod tests {
         });
     }
 
+    fn sub_strings() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let parts = Value::eval_string(&mut frame, "split(\"foo,bär,baz\", ',')")
+                        .into_jlrs_result()?
+                        .cast::<Array>()?;
+                    assert!(parts.contains::<SubString>());
+
+                    let parts = parts.inline_data::<SubString>()?;
+                    let parts = parts
+                        .as_slice()
+                        .iter()
+                        .map(|s| s.as_str())
+                        .collect::<JlrsResult<Vec<_>>>()?;
+                    assert_eq!(parts, ["foo", "bär", "baz"]);
+
+                    let sub = Value::eval_string(&mut frame, "SubString(\"foobar\", 4)")
+                        .into_jlrs_result()?;
+                    assert!(sub.is::<SubString>());
+                    assert!(!sub.is::<JuliaString>());
+                    assert_eq!(sub.unbox::<SubString>()?.as_str()?, "bar");
+
+                    let view = StringView::new(sub)?;
+                    assert!(matches!(view, StringView::SubString(_)));
+                    assert_eq!(view.as_str()?, "bar");
+
+                    let s = JuliaString::new(&mut frame, "foo");
+                    let view = StringView::new(s.as_value())?;
+                    assert!(matches!(view, StringView::String(_)));
+                    assert_eq!(view.as_str()?, "foo");
+
+                    let n = Value::new(&mut frame, 1usize);
+                    assert!(StringView::new(n).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn string_writer() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let mut writer = StringWriter::new(&mut frame)?;
+                    for i in 0..1000 {
+                        std::fmt::Write::write_fmt(&mut writer, format_args!("{},", i)).unwrap();
+                    }
+                    std::io::Write::write_all(&mut writer, "end".as_bytes()).unwrap();
+
+                    let s = writer.finish()?;
+                    let expected = (0..1000).map(|i| format!("{},", i)).collect::<String>() + "end";
+                    assert_eq!(s.as_str()?, expected);
+
+                    let s = StringWriter::new(&mut frame)?.finish()?;
+                    assert_eq!(s.as_str()?, "");
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
     #[test]
     fn string_tests() {
         create_and_unbox_str_data();
@@ -188,5 +265,7 @@ mod tests {
         create_utf8_string();
         format_string();
         extend_lifeime();
+        sub_strings();
+        string_writer();
     }
 }
@@ -5,6 +5,7 @@ mod tests {
     use std::collections::HashSet;
 
     use jlrs::{
+        convert::to_symbol::ToSymbol,
         memory::gc::{Gc, GcCollection},
         prelude::*,
     };
@@ -209,6 +210,45 @@ mod tests {
         })
     }
 
+    fn static_symbol() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|frame| {
+                    let s1 = jlrs::sym!(&frame, "foo");
+                    let s2 = Symbol::new(&frame, "foo");
+                    assert_eq!(s1, s2);
+
+                    let cached = jlrs::sym!("bar");
+                    assert_eq!(cached.name(), "bar");
+                    assert_eq!(cached.get(&frame), cached.get(&frame));
+                    assert_eq!(cached.to_symbol(&frame).as_str().unwrap(), "bar");
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn static_symbol_for_access() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|frame| {
+                    for _ in 0..2 {
+                        assert!(Module::base(&frame)
+                            .function(&frame, jlrs::sym!("+"))
+                            .is_ok());
+                    }
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
     #[test]
     fn symbol_tests() {
         create_symbol();
@@ -220,6 +260,8 @@ mod tests {
         leak_symbol();
         extend_lifetime();
         symbol_implements_hash();
+        static_symbol();
+        static_symbol_for_access();
         #[cfg(not(all(target_os = "windows", feature = "lts")))]
         bytes_to_symbol();
         #[cfg(not(all(target_os = "windows", feature = "lts")))]
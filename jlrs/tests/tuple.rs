@@ -53,10 +53,56 @@ mod tests {
         })
     }
 
+    fn create_cast_native_tuple() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let v = Value::new(&mut frame, Tuple3::from((1u8, 2.0f32, true)));
+                    assert!(v.is::<(u8, f32, bool)>());
+                    assert!(v.is::<Tuple3<u8, f32, bool>>());
+                    assert!(!v.is::<(u8, f32)>());
+                    assert_eq!(v.unbox::<(u8, f32, bool)>()?, (1, 2.0, true));
+
+                    let ty = v.datatype().display_string()?;
+                    assert_eq!(ty, "Tuple{UInt8, Float32, Bool}");
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn create_cast_ntuple() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let v = Value::new(&mut frame, [1i32, 2, 3, 4]);
+                    assert!(v.is::<[i32; 4]>());
+                    assert!(!v.is::<[i32; 3]>());
+                    assert!(!v.is::<[u32; 4]>());
+                    assert_eq!(v.unbox::<[i32; 4]>()?, [1, 2, 3, 4]);
+
+                    let ty = v.datatype().display_string()?;
+                    assert_eq!(ty, "NTuple{4, Int32}");
+
+                    let v = unsafe { Value::eval_string(&mut frame, "(1.0, 2.0)") }
+                        .into_jlrs_result()?;
+                    assert_eq!(v.unbox::<[f64; 2]>()?, [1.0, 2.0]);
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
     #[test]
     fn tuple_tests() {
         create_cast_tuple0();
         create_cast_tuple1();
         create_cast_tuple2();
+        create_cast_native_tuple();
+        create_cast_ntuple();
     }
 }
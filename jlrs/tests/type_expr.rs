@@ -0,0 +1,152 @@
+mod util;
+
+#[cfg(all(feature = "sync-rt", not(all(target_os = "windows", feature = "lts"))))]
+mod tests {
+    use jlrs::{convert::type_expr::TypeExpr, prelude::*, wrappers::ptr::union_all::UnionAll};
+
+    use super::util::JULIA;
+
+    fn parse_type_expr() {
+        let parsed = TypeExpr::parse("Base.Dict{Symbol, Vector{Union{Missing, Float64}}}").unwrap();
+        let built = TypeExpr::name("Base.Dict").apply([
+            TypeExpr::name("Symbol"),
+            TypeExpr::name("Vector").apply([TypeExpr::union([
+                TypeExpr::name("Missing"),
+                TypeExpr::name("Float64"),
+            ])]),
+        ]);
+
+        assert_eq!(parsed, built);
+        assert_eq!(
+            parsed.to_string(),
+            "Base.Dict{Symbol, Vector{Union{Missing, Float64}}}"
+        );
+
+        let parsed: TypeExpr = "NamedTuple{:a, Array{Int, -1}}".parse().unwrap();
+        let built = TypeExpr::name("NamedTuple").apply([
+            TypeExpr::symbol("a"),
+            TypeExpr::name("Array").apply([TypeExpr::name("Int"), TypeExpr::int(-1)]),
+        ]);
+        assert_eq!(parsed, built);
+    }
+
+    fn parse_invalid_type_expr() {
+        assert!(TypeExpr::parse("").is_err());
+        assert!(TypeExpr::parse("Vector{Int").is_err());
+        assert!(TypeExpr::parse("Vector{Int}}").is_err());
+        assert!(TypeExpr::parse("Vector{Int Float64}").is_err());
+        assert!(TypeExpr::parse("Base.").is_err());
+    }
+
+    fn resolve_type_expr() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let main = Module::main(&frame);
+                    let expr = TypeExpr::parse("Dict{Symbol, Vector{Union{Missing, Float64}}}")?;
+                    let ty = expr.resolve(frame.as_extended_target(), main)?;
+
+                    let expected = Value::eval_string(
+                        &mut frame,
+                        "Dict{Symbol, Vector{Union{Missing, Float64}}}",
+                    )
+                    .into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    let expr = TypeExpr::parse("Array{Float64, 2}")?;
+                    let ty = expr.resolve(frame.as_extended_target(), main)?;
+                    let expected =
+                        Value::eval_string(&mut frame, "Matrix{Float64}").into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    let expr = TypeExpr::parse("Vector")?;
+                    let ty = expr.resolve(frame.as_extended_target(), main)?;
+                    assert!(ty.is::<UnionAll>());
+
+                    let expr = TypeExpr::parse("Core.Union{NTuple{2, Int}, Nothing}")?;
+                    let ty = expr.resolve(frame.as_extended_target(), main)?;
+                    let expected =
+                        Value::eval_string(&mut frame, "Union{Tuple{Int, Int}, Nothing}")
+                            .into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    let expr = TypeExpr::parse("Tuple{Vararg{Int}}")?;
+                    let ty = expr.resolve(frame.as_extended_target(), main)?;
+                    let expected =
+                        Value::eval_string(&mut frame, "Tuple{Vararg{Int}}").into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    let expr = TypeExpr::parse("Tuple{Int, Vararg{Float64, 2}}")?;
+                    let ty = expr.resolve(frame.as_extended_target(), main)?;
+                    let expected =
+                        Value::eval_string(&mut frame, "Tuple{Int, Float64, Float64}")
+                            .into_jlrs_result()?;
+                    assert!(ty.egal(expected));
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn resolve_invalid_type_expr() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let main = Module::main(&frame);
+
+                    let unknown = TypeExpr::parse("Vector{DoesNotExist}")?;
+                    assert!(unknown.resolve(frame.as_extended_target(), main).is_err());
+
+                    let too_many = TypeExpr::parse("Vector{Int, 1, 2}")?;
+                    assert!(too_many.resolve(frame.as_extended_target(), main).is_err());
+
+                    let not_a_type = TypeExpr::parse("println")?;
+                    assert!(not_a_type
+                        .resolve(frame.as_extended_target(), main)
+                        .is_err());
+
+                    let concrete = TypeExpr::parse("Float64{Int}")?;
+                    assert!(concrete.resolve(frame.as_extended_target(), main).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn resolve_cached_type_expr() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let main = Module::main(&frame);
+                    let expr = TypeExpr::parse("Union{Int, Vector{Float32}}")?;
+
+                    let first = expr.resolve_cached(frame.as_extended_target(), main)?;
+                    let second = expr.resolve_cached(frame.as_extended_target(), main)?;
+                    assert_eq!(first.data_ptr(), second.data_ptr());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn type_expr_tests() {
+        parse_type_expr();
+        parse_invalid_type_expr();
+        resolve_type_expr();
+        resolve_invalid_type_expr();
+        resolve_cached_type_expr();
+    }
+}
@@ -0,0 +1,138 @@
+mod util;
+
+#[cfg(all(
+    feature = "sync-rt",
+    feature = "internal-types",
+    not(all(target_os = "windows", feature = "lts"))
+))]
+mod tests {
+    use jlrs::{call::typed_callable::TypedCallable, prelude::*};
+
+    use super::util::JULIA;
+
+    fn call_typed_callable() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "typedcallable_add(a, b) = a + b")
+                        .into_jlrs_result()?;
+                    let ty = DataType::float64_type(&frame);
+                    let mut callable = TypedCallable::new(&mut frame, func, &[ty, ty])?;
+                    assert!(callable.code_instance().is_some());
+
+                    let a = Value::new(&mut frame, 1.0f64);
+                    let b = Value::new(&mut frame, 2.0f64);
+                    let res = callable
+                        .call(&mut frame, [a, b])?
+                        .into_jlrs_result()?
+                        .unbox::<f64>()?;
+                    assert_eq!(res, 3.0);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn typed_callable_checks_arguments() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "typedcallable_neg(a) = -a")
+                        .into_jlrs_result()?;
+                    let ty = DataType::int64_type(&frame);
+                    let mut callable = TypedCallable::new(&mut frame, func, &[ty])?;
+
+                    let a = Value::new(&mut frame, 1.0f64);
+                    assert!(callable.call(&mut frame, [a]).is_err());
+                    assert!(callable.call(&mut frame, []).is_err());
+
+                    let abstract_ty = DataType::number_type(&frame);
+                    assert!(TypedCallable::new(&mut frame, func, &[abstract_ty]).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn typed_callable_catches_exceptions() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(
+                        &mut frame,
+                        "typedcallable_throws(a::Int) = a > 0 ? throw(ErrorException(\"positive\")) : a",
+                    )
+                    .into_jlrs_result()?;
+                    let ty = DataType::int64_type(&frame);
+                    let mut callable = TypedCallable::new(&mut frame, func, &[ty])?;
+
+                    let a = Value::new(&mut frame, 1isize);
+                    assert!(callable.call(&mut frame, [a])?.is_err());
+
+                    let b = Value::new(&mut frame, -1isize);
+                    let res = callable
+                        .call(&mut frame, [b])?
+                        .into_jlrs_result()?
+                        .unbox::<isize>()?;
+                    assert_eq!(res, -1);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn typed_callable_after_redefinition() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "typedcallable_redef(a) = 2a")
+                        .into_jlrs_result()?;
+                    let ty = DataType::int64_type(&frame);
+                    let mut callable = TypedCallable::new(&mut frame, func, &[ty])?;
+
+                    let a = Value::new(&mut frame, 3isize);
+                    let res = callable
+                        .call(&mut frame, [a])?
+                        .into_jlrs_result()?
+                        .unbox::<isize>()?;
+                    assert_eq!(res, 6);
+
+                    Value::eval_string(&mut frame, "typedcallable_redef(a) = 3a")
+                        .into_jlrs_result()?;
+
+                    let res = callable
+                        .call(&mut frame, [a])?
+                        .into_jlrs_result()?
+                        .unbox::<isize>()?;
+                    assert_eq!(res, 9);
+                    assert!(callable.code_instance().is_some());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[test]
+    fn typed_callable_tests() {
+        call_typed_callable();
+        typed_callable_checks_arguments();
+        typed_callable_catches_exceptions();
+        typed_callable_after_redefinition();
+    }
+}
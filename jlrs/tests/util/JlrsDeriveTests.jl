@@ -173,6 +173,38 @@ module ZST
 struct ZeroSized end
 end
 
+module Enums
+@enum Color::Int32 red = 1 green = 2 blue = 4
+
+abstract type Shape end
+
+struct Circle <: Shape
+    r::Float64
+end
+
+struct Rect <: Shape
+    w::Float64
+    h::Float64
+end
+
+struct NoShape <: Shape end
+
+struct Labeled <: Shape
+    label::Any
+end
+end
+
+module WithMutable
+mutable struct MutableWithValue
+    a::Any
+    b::Int64
+end
+
+mutable struct MutableBits
+    a::Int64
+end
+end
+
 
 # JlrsReflect.reflect([
 #     SingleFieldBits.BitsTypeBool,
@@ -208,5 +240,7 @@ end
 #     WithGeneric.WithSetGenericTuple,
 #     WithGeneric.WithPropagatedLifetime,
 #     WithGeneric.WithPropagatedLifetimes,
-#     ZST.ZeroSized
+#     ZST.ZeroSized,
+#     WithMutable.MutableWithValue,
+#     WithMutable.MutableBits
 # ])
\ No newline at end of file
@@ -1,7 +1,7 @@
 use jlrs::prelude::*;
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.BitsWithCustom.BitsCharBitsIntChar")]
 pub struct BitsCharBitsIntChar {
     pub a: ::jlrs::wrappers::inline::char::Char,
@@ -10,7 +10,7 @@ pub struct BitsCharBitsIntChar {
 
 #[cfg(target_pointer_width = "64")]
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.BitsWithCustom.BitsIntChar")]
 pub struct BitsIntChar {
     pub a: i64,
@@ -19,7 +19,7 @@ pub struct BitsIntChar {
 
 #[cfg(target_pointer_width = "32")]
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.BitsWithCustom.BitsIntChar")]
 pub struct BitsIntChar {
     pub a: i32,
@@ -27,7 +27,7 @@ pub struct BitsIntChar {
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.BitsWithTuples.BitsUInt8TupleInt32Int64")]
 pub struct BitsUInt8TupleInt32Int64 {
     pub a: u8,
@@ -35,7 +35,7 @@ pub struct BitsUInt8TupleInt32Int64 {
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.BitsWithTuples.BitsUInt8TupleInt32TupleInt16UInt16")]
 pub struct BitsUInt8TupleInt32TupleInt16UInt16 {
     pub a: u8,
@@ -46,7 +46,7 @@ pub struct BitsUInt8TupleInt32TupleInt16UInt16 {
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.MultiFieldBits.BitsCharFloat32Float64")]
 pub struct BitsCharFloat32Float64 {
     pub a: ::jlrs::wrappers::inline::char::Char,
@@ -56,7 +56,7 @@ pub struct BitsCharFloat32Float64 {
 
 #[cfg(target_pointer_width = "64")]
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.MultiFieldBits.BitsIntBool")]
 pub struct BitsIntBool {
     pub a: i64,
@@ -65,7 +65,7 @@ pub struct BitsIntBool {
 
 #[cfg(target_pointer_width = "32")]
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.MultiFieldBits.BitsIntBool")]
 pub struct BitsIntBool {
     pub a: i32,
@@ -73,28 +73,28 @@ pub struct BitsIntBool {
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeBool")]
 pub struct BitsTypeBool {
     pub a: ::jlrs::wrappers::inline::bool::Bool,
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeChar")]
 pub struct BitsTypeChar {
     pub a: ::jlrs::wrappers::inline::char::Char,
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeFloat32")]
 pub struct BitsTypeFloat32 {
     pub a: f32,
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeFloat64")]
 pub struct BitsTypeFloat64 {
     pub a: f64,
@@ -102,7 +102,7 @@ pub struct BitsTypeFloat64 {
 
 #[cfg(target_pointer_width = "64")]
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeInt")]
 pub struct BitsTypeInt {
     pub a: i64,
@@ -110,35 +110,35 @@ pub struct BitsTypeInt {
 
 #[cfg(target_pointer_width = "32")]
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeInt")]
 pub struct BitsTypeInt {
     pub a: i32,
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeInt16")]
 pub struct BitsTypeInt16 {
     pub a: i16,
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeInt32")]
 pub struct BitsTypeInt32 {
     pub a: i32,
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeInt64")]
 pub struct BitsTypeInt64 {
     pub a: i64,
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeInt8")]
 pub struct BitsTypeInt8 {
     pub a: i8,
@@ -146,7 +146,7 @@ pub struct BitsTypeInt8 {
 
 #[cfg(target_pointer_width = "64")]
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeUInt")]
 pub struct BitsTypeUInt {
     pub a: u64,
@@ -154,34 +154,34 @@ pub struct BitsTypeUInt {
 
 #[cfg(target_pointer_width = "32")]
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeUInt")]
 pub struct BitsTypeUInt {
     pub a: u32,
 }
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeUInt16")]
 pub struct BitsTypeUInt16 {
     pub a: u16,
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeUInt32")]
 pub struct BitsTypeUInt32 {
     pub a: u32,
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeUInt64")]
 pub struct BitsTypeUInt64 {
     pub a: u64,
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.SingleFieldBits.BitsTypeUInt8")]
 pub struct BitsTypeUInt8 {
     pub a: u8,
@@ -202,7 +202,7 @@ pub struct DoubleVariant {
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.WithBitsUnion.SingleVariant")]
 pub struct SingleVariant {
     pub a: i8,
@@ -234,7 +234,7 @@ pub struct UnionInTuple<'frame, 'data> {
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, ConstructType)]
 #[jlrs(julia_type = "Main.WithGeneric.WithGenericT")]
 pub struct WithGenericT<T>
 where
@@ -251,7 +251,7 @@ pub struct WithGenericUnionAll<'frame, 'data> {
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, ConstructType)]
 #[jlrs(julia_type = "Main.WithGeneric.WithNestedGenericT")]
 pub struct WithNestedGenericT<T>
 where
@@ -261,7 +261,7 @@ where
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
 #[jlrs(julia_type = "Main.WithGeneric.WithPropagatedLifetime")]
 pub struct WithPropagatedLifetime<'frame> {
     pub a: WithGenericT<::std::option::Option<::jlrs::wrappers::ptr::module::ModuleRef<'frame>>>,
@@ -303,13 +303,133 @@ pub struct WithValueType {
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
 #[jlrs(julia_type = "Main.WithNonBitsUnion.NonBitsUnion")]
 pub struct NonBitsUnion<'frame, 'data> {
     pub a: ::std::option::Option<::jlrs::wrappers::ptr::value::ValueRef<'frame, 'data>>,
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, IsBits)]
 #[jlrs(julia_type = "Main.ZST.ZeroSized", zero_sized_type)]
 pub struct ZeroSized {}
+
+#[repr(C)]
+#[derive(Clone, Debug, Unbox, ValidLayout, Typecheck, IntoJulia)]
+#[jlrs(julia_type = "Main.WithMutable.MutableWithValue")]
+pub struct MutableWithValue<'frame, 'data> {
+    pub a: ::std::option::Option<::jlrs::wrappers::ptr::value::ValueRef<'frame, 'data>>,
+    pub b: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Debug, Unbox, ValidLayout, Typecheck, IntoJulia, IsBits)]
+#[jlrs(julia_type = "Main.WithMutable.MutableBits")]
+pub struct MutableBits {
+    pub a: i64,
+}
+
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, JuliaEnum)]
+#[jlrs(julia_type = "Main.Enums.Color")]
+pub enum Color {
+    #[jlrs(rename = "red")]
+    Red = 1,
+    #[jlrs(rename = "green")]
+    Green = 2,
+    #[jlrs(rename = "blue")]
+    Blue = 4,
+}
+
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, JuliaEnum)]
+#[jlrs(julia_type = "Main.Enums.Color")]
+pub enum ColorMissingVariant {
+    #[jlrs(rename = "red")]
+    Red = 1,
+    #[jlrs(rename = "green")]
+    Green = 2,
+}
+
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, JuliaEnum)]
+#[jlrs(julia_type = "Main.Enums.Color")]
+pub enum ColorWrongValue {
+    #[jlrs(rename = "red")]
+    Red = 1,
+    #[jlrs(rename = "green")]
+    Green = 2,
+    #[jlrs(rename = "blue")]
+    Blue = 3,
+}
+
+#[repr(i64)]
+#[derive(Clone, Copy, Debug, PartialEq, JuliaEnum)]
+#[jlrs(julia_type = "Main.Enums.Color")]
+pub enum ColorWrongRepr {
+    #[jlrs(rename = "red")]
+    Red = 1,
+    #[jlrs(rename = "green")]
+    Green = 2,
+    #[jlrs(rename = "blue")]
+    Blue = 4,
+}
+
+#[derive(Clone, Debug, JuliaEnum)]
+#[jlrs(julia_type = "Main.Enums.Shape")]
+pub enum Shape<'frame, 'data> {
+    #[jlrs(julia_type = "Main.Enums.Circle")]
+    Circle { r: f64 },
+    #[jlrs(julia_type = "Main.Enums.Rect")]
+    Rect(f64, f64),
+    #[jlrs(julia_type = "Main.Enums.NoShape")]
+    NoShape,
+    #[jlrs(julia_type = "Main.Enums.Labeled")]
+    Labeled {
+        label: ::std::option::Option<::jlrs::wrappers::ptr::value::ValueRef<'frame, 'data>>,
+    },
+}
+
+#[repr(C)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, IntoJulia, JuliaStruct)]
+#[jlrs(julia_type = "Main.Defined.DefinedBits")]
+pub struct DefinedBits {
+    pub a: i8,
+    pub b: f64,
+    pub c: ::jlrs::wrappers::inline::bool::Bool,
+    pub d: ::jlrs::wrappers::inline::char::Char,
+    pub e: ::jlrs::wrappers::inline::tuple::Tuple2<i16, u8>,
+}
+
+#[repr(C)]
+#[derive(Clone, Debug, Unbox, ValidLayout, Typecheck, JuliaStruct)]
+#[jlrs(julia_type = "Main.Defined.DefinedGeneric")]
+pub struct DefinedGeneric<'frame, 'data, T>
+where
+    T: ::jlrs::layout::valid_layout::ValidField + Clone + ::std::fmt::Debug,
+{
+    pub a: T,
+    pub b: ::std::option::Option<::jlrs::wrappers::ptr::value::ValueRef<'frame, 'data>>,
+    pub c: ::jlrs::wrappers::inline::tuple::Tuple1<T>,
+}
+
+#[repr(C)]
+#[derive(Clone, Debug, Unbox, ValidLayout, ValidField, Typecheck, JuliaStruct)]
+#[jlrs(julia_type = "Main.Defined.DefinedUnion")]
+pub struct DefinedUnion {
+    pub a: i8,
+    #[jlrs(bits_union_align)]
+    _b_align: ::jlrs::wrappers::inline::union::Align4,
+    #[jlrs(bits_union, julia_type = "Union{Int16, Int32}")]
+    pub b: ::jlrs::wrappers::inline::union::BitsUnion<4>,
+    #[jlrs(bits_union_flag)]
+    pub b_flag: u8,
+    pub c: i8,
+}
+
+#[derive(Clone, Debug, PartialEq, IntoNamedTuple, FromNamedTuple)]
+pub struct NamedTupleOptions {
+    pub scale: f64,
+    pub iterations: i64,
+    pub offset: ::jlrs::wrappers::inline::tuple::Tuple2<i32, i32>,
+}
@@ -2,7 +2,7 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TS2;
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::{self, Meta};
 
 #[derive(Default)]
@@ -102,6 +102,37 @@ impl JlrsTypeAttrs {
     }
 }
 
+struct JlrsVariantAttrs {
+    julia_type: Option<String>,
+    rename: Option<String>,
+}
+
+impl JlrsVariantAttrs {
+    fn parse(variant: &syn::Variant) -> Self {
+        let mut julia_type = None;
+        let mut rename = None;
+        for attr in &variant.attrs {
+            if attr.path.is_ident("jlrs") {
+                if let Ok(Meta::List(p)) = attr.parse_meta() {
+                    for item in &p.nested {
+                        if let syn::NestedMeta::Meta(Meta::NameValue(nv)) = item {
+                            if let syn::Lit::Str(string) = &nv.lit {
+                                if nv.path.is_ident("julia_type") {
+                                    julia_type = Some(string.value())
+                                } else if nv.path.is_ident("rename") {
+                                    rename = Some(string.value())
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        JlrsVariantAttrs { julia_type, rename }
+    }
+}
+
 enum JlrsFieldAttr {
     BitsUnionAlign,
     BitsUnion,
@@ -128,6 +159,10 @@ impl JlrsFieldAttr {
 
         None
     }
+
+    fn parse_all(attrs: &[syn::Attribute]) -> Option<Self> {
+        attrs.iter().find_map(JlrsFieldAttr::parse)
+    }
 }
 
 #[proc_macro_derive(IntoJulia, attributes(jlrs))]
@@ -136,6 +171,12 @@ pub fn into_julia_derive(input: TokenStream) -> TokenStream {
     impl_into_julia(&ast)
 }
 
+#[proc_macro_derive(IsBits, attributes(jlrs))]
+pub fn is_bits_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_is_bits(&ast)
+}
+
 #[proc_macro_derive(Unbox, attributes(jlrs))]
 pub fn unbox_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).unwrap();
@@ -160,6 +201,36 @@ pub fn valid_field_derive(input: TokenStream) -> TokenStream {
     impl_valid_field(&ast)
 }
 
+#[proc_macro_derive(ConstructType, attributes(jlrs))]
+pub fn construct_type_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_construct_type(&ast)
+}
+
+#[proc_macro_derive(JuliaStruct, attributes(jlrs))]
+pub fn julia_struct_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_julia_struct(&ast)
+}
+
+#[proc_macro_derive(JuliaEnum, attributes(jlrs))]
+pub fn julia_enum_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_julia_enum(&ast)
+}
+
+#[proc_macro_derive(IntoNamedTuple, attributes(jlrs))]
+pub fn into_named_tuple_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_into_named_tuple(&ast)
+}
+
+#[proc_macro_derive(FromNamedTuple, attributes(jlrs))]
+pub fn from_named_tuple_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_from_named_tuple(&ast)
+}
+
 fn impl_into_julia(ast: &syn::DeriveInput) -> TokenStream {
     let name = &ast.ident;
     if !is_repr_c(ast) {
@@ -184,10 +255,16 @@ fn impl_into_julia(ast: &syn::DeriveInput) -> TokenStream {
     let modules_it = modules.iter();
     let modules_it_b = modules_it.clone();
 
+    if ast.generics.type_params().next().is_some() {
+        panic!("IntoJulia cannot be derived for types with type parameters.");
+    }
+
+    let generics = with_static_data_lifetimes(&ast.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let into_julia_fn = impl_into_julia_fn(&attrs);
 
     let into_julia_impl = quote! {
-        unsafe impl ::jlrs::convert::into_julia::IntoJulia for #name {
+        unsafe impl #impl_generics ::jlrs::convert::into_julia::IntoJulia for #name #ty_generics #where_clause {
             fn julia_type<'scope, T>(target: T) -> ::jlrs::wrappers::ptr::datatype::DataTypeData<'scope, T>
             where
                 T: ::jlrs::memory::target::Target<'scope>,
@@ -238,6 +315,198 @@ fn impl_into_julia_fn(attrs: &JlrsTypeAttrs) -> Option<TS2> {
     }
 }
 
+// Values converted to Julia have no data lifetime, so the data referenced by their pointer fields
+// must not be borrowed from Rust. Every lifetime except the first one must be 'static.
+fn with_static_data_lifetimes(generics: &syn::Generics) -> syn::Generics {
+    let mut generics = generics.clone();
+    let data_lifetimes = generics
+        .lifetimes()
+        .skip(1)
+        .map(|l| l.lifetime.clone())
+        .collect::<Vec<_>>();
+
+    let where_clause = generics.make_where_clause();
+    for lifetime in data_lifetimes {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#lifetime: 'static));
+    }
+
+    generics
+}
+
+fn impl_is_bits(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    if !is_repr_c(ast) {
+        panic!("IsBits can only be derived for types with the attribute #[repr(C)].");
+    }
+
+    if ast.generics.params.iter().next().is_some() {
+        panic!("IsBits cannot be derived for types with type parameters or lifetimes.");
+    }
+
+    let fields = match &ast.data {
+        syn::Data::Struct(s) => &s.fields,
+        _ => panic!("IsBits can only be derived for structs."),
+    };
+
+    for field in fields {
+        if JlrsFieldAttr::parse_all(&field.attrs).is_some() {
+            panic!("IsBits cannot be derived for types with bits union fields.");
+        }
+
+        if is_pointer_field(&field.ty) {
+            panic!("IsBits cannot be derived for types with pointer fields.");
+        }
+    }
+
+    // Every field must be a bits type, these bounds are checked by the compiler because the
+    // implementation has no generics.
+    let field_types = fields.iter().map(|field| &field.ty);
+
+    let is_bits_impl = quote! {
+        unsafe impl ::jlrs::convert::into_julia::IsBits for #name
+        where
+            #(#field_types: ::jlrs::convert::into_julia::IsBits,)*
+        {}
+    };
+
+    is_bits_impl.into()
+}
+
+fn impl_construct_type(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    if !is_repr_c(ast) {
+        panic!("ConstructType can only be derived for types with the attribute #[repr(C)].");
+    }
+
+    let mut attrs = JlrsTypeAttrs::parse(ast);
+    let jl_type = attrs.julia_type
+        .take()
+        .expect("ConstructType can only be derived if the corresponding Julia type is set with #[jlrs(julia_type = \"Main.MyModule.Submodule.StructType\")]");
+
+    let mut type_it = jl_type.split('.');
+    let func = match type_it.next() {
+        Some("Main") => quote::format_ident!("main"),
+        Some("Base") => quote::format_ident!("base"),
+        Some("Core") => quote::format_ident!("core"),
+        _ => panic!("ConstructType can only be derived if the first module of \"julia_type\" is either \"Main\", \"Base\" or \"Core\"."),
+    };
+
+    let mut modules = type_it.collect::<Vec<_>>();
+    let ty = modules.pop().expect("ConstructType can only be derived if the corresponding Julia type is set with #[jlrs(julia_type = \"Main.MyModule.Submodule.StructType\")]");
+    let modules_it = modules.iter();
+    let modules_it_b = modules_it.clone();
+
+    if ast.generics.const_params().next().is_some() {
+        panic!("ConstructType cannot be derived for types with const generics.");
+    }
+
+    let mut generics = ast.generics.clone();
+    let type_params = ast
+        .generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect::<Vec<_>>();
+
+    // Every type parameter is applied to the Julia type, so it must be used by the layout. The
+    // Julia type can't have additional parameters because they can't be constructed.
+    let fields = match &ast.data {
+        syn::Data::Struct(s) => &s.fields,
+        _ => panic!("ConstructType can only be derived for structs."),
+    };
+
+    for param in type_params.iter() {
+        let used = fields
+            .iter()
+            .any(|field| contains_ident(field.ty.to_token_stream(), param));
+
+        if !used {
+            panic!(
+                "ConstructType cannot be derived for types with type parameters that aren't used by a field, {} is unused.",
+                param
+            );
+        }
+    }
+
+    {
+        let where_clause = generics.make_where_clause();
+        for param in type_params.iter() {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#param: ::jlrs::convert::construct_type::ConstructType));
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let n_params = type_params.len();
+    let apply_params = if type_params.is_empty() {
+        quote! {
+            Ok(base.root(output))
+        }
+    } else {
+        quote! {
+            let params: [::jlrs::wrappers::ptr::value::Value; #n_params] = [
+                #(
+                    <#type_params as ::jlrs::convert::construct_type::ConstructType>::construct_type(frame.as_extended_target()),
+                )*
+            ];
+
+            let ty = base.wrapper().apply_type_unchecked(&mut frame, params);
+            Ok(::jlrs::wrappers::ptr::Wrapper::as_ref(ty).root(output))
+        }
+    };
+
+    let construct_type_impl = quote! {
+        unsafe impl #impl_generics ::jlrs::convert::construct_type::ConstructType for #name #ty_generics #where_clause {
+            fn construct_type<'target, 'current, 'borrow, Tgt>(
+                target: ::jlrs::memory::target::ExtendedTarget<'target, 'current, 'borrow, Tgt>,
+            ) -> ::jlrs::wrappers::ptr::value::ValueData<'target, 'static, Tgt>
+            where
+                Tgt: ::jlrs::memory::target::Target<'target>,
+            {
+                unsafe {
+                    let (output, frame) = target.split();
+                    frame
+                        .scope(|mut frame| {
+                            let global = frame.unrooted();
+                            let base = ::jlrs::wrappers::ptr::module::Module::#func(&global)
+                                #(
+                                    .submodule(&global, #modules_it)
+                                    .expect(&format!("Submodule {} cannot be found", #modules_it_b))
+                                    .wrapper()
+                                )*
+                                .global(&global, #ty)
+                                .expect(&format!("Type {} cannot be found in module", #ty));
+
+                            let mut n_type_vars = 0usize;
+                            let mut body = base.wrapper();
+                            while let Ok(ua) = body.cast::<::jlrs::wrappers::ptr::union_all::UnionAll>() {
+                                n_type_vars += 1;
+                                body = ua.body();
+                            }
+
+                            debug_assert_eq!(
+                                n_type_vars,
+                                #n_params,
+                                "Type {} has {} type parameters, expected {}",
+                                #ty,
+                                n_type_vars,
+                                #n_params
+                            );
+
+                            #apply_params
+                        })
+                        .unwrap()
+                }
+            }
+        }
+    };
+
+    construct_type_impl.into()
+}
+
 fn impl_unbox(ast: &syn::DeriveInput) -> TokenStream {
     let name = &ast.ident;
     if !is_repr_c(ast) {
@@ -270,6 +539,12 @@ fn impl_typecheck(ast: &syn::DeriveInput) -> TokenStream {
             fn typecheck(dt: ::jlrs::wrappers::ptr::datatype::DataType) -> bool {
                 <Self as ::jlrs::layout::valid_layout::ValidLayout>::valid_layout(dt.as_value())
             }
+
+            fn layout_mismatch(
+                dt: ::jlrs::wrappers::ptr::datatype::DataType,
+            ) -> Option<::jlrs::layout::diagnostics::LayoutMismatch> {
+                <Self as ::jlrs::layout::valid_layout::ValidLayout>::layout_mismatch(dt.as_value())
+            }
         }
     };
 
@@ -306,6 +581,39 @@ fn impl_valid_layout(ast: &syn::DeriveInput) -> TokenStream {
     let n_fields = classified_fields.jl_union_field_idxs.len()
         + classified_fields.jl_non_union_field_idxs.len();
 
+    let mut field_layouts = vec![];
+    let mut julia_index = 0usize;
+    if let syn::Fields::Named(n) = fields {
+        for field in n.named.iter() {
+            let attr = field.attrs.iter().find_map(JlrsFieldAttr::parse);
+            let valid_field = match attr {
+                Some(JlrsFieldAttr::BitsUnionAlign) | Some(JlrsFieldAttr::BitsUnionFlag) => {
+                    continue
+                }
+                Some(JlrsFieldAttr::BitsUnion) => quote!(None),
+                None => {
+                    let ty = &field.ty;
+                    quote!(Some(<#ty as ::jlrs::layout::valid_layout::ValidField>::valid_field))
+                }
+            };
+
+            let ident = field.ident.as_ref().unwrap();
+            let ty = &field.ty;
+            field_layouts.push(quote! {
+                ::jlrs::layout::diagnostics::RustFieldLayout {
+                    name: stringify!(#ident),
+                    julia_index: #julia_index,
+                    offset: unsafe { ::std::ptr::addr_of!((*base).#ident) as usize - base as usize },
+                    size: ::std::mem::size_of::<#ty>(),
+                    type_name: ::std::any::type_name::<#ty>(),
+                    valid_field: #valid_field,
+                }
+            });
+
+            julia_index += 1;
+        }
+    }
+
     let valid_layout_impl = quote! {
         unsafe impl #generics ::jlrs::layout::valid_layout::ValidLayout for #name #generics #where_clause {
             fn valid_layout(v: ::jlrs::wrappers::ptr::value::Value) -> bool {
@@ -345,6 +653,21 @@ fn impl_valid_layout(ast: &syn::DeriveInput) -> TokenStream {
                 false
             }
 
+            fn layout_mismatch(
+                ty: ::jlrs::wrappers::ptr::value::Value,
+            ) -> Option<::jlrs::layout::diagnostics::LayoutMismatch> {
+                let uninit = ::std::mem::MaybeUninit::<Self>::uninit();
+                let base = uninit.as_ptr();
+
+                let layout = ::jlrs::layout::diagnostics::RustLayout {
+                    size: ::std::mem::size_of::<Self>(),
+                    align: ::std::mem::align_of::<Self>(),
+                    fields: vec![#(#field_layouts),*],
+                };
+
+                layout.compare(ty)
+            }
+
             const IS_REF: bool = false;
         }
     };
@@ -372,6 +695,700 @@ fn impl_valid_field(ast: &syn::DeriveInput) -> TokenStream {
     valid_field_impl.into()
 }
 
+fn impl_julia_struct(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    if !is_repr_c(ast) {
+        panic!("JuliaStruct can only be derived for types with the attribute #[repr(C)].");
+    }
+
+    let fields = match &ast.data {
+        syn::Data::Struct(s) => match &s.fields {
+            syn::Fields::Named(n) => n.named.iter().collect::<Vec<_>>(),
+            syn::Fields::Unit => Vec::new(),
+            _ => panic!("JuliaStruct cannot be derived for tuple structs."),
+        },
+        _ => panic!("JuliaStruct can only be derived for structs."),
+    };
+
+    let mut attrs = JlrsTypeAttrs::parse(ast);
+    let jl_path = attrs.julia_type.take().unwrap_or_else(|| name.to_string());
+    let jl_name = jl_path.rsplit('.').next().unwrap().to_string();
+
+    let type_params = ast
+        .generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect::<Vec<_>>();
+    let markers = type_params
+        .iter()
+        .map(|p| quote::format_ident!("__JlrsTypeParam{}", p))
+        .collect::<Vec<_>>();
+    let param_names = type_params
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>();
+
+    let header = if type_params.is_empty() {
+        format!("struct {}\n", jl_name)
+    } else {
+        format!("struct {}{{{}}}\n", jl_name, param_names.join(", "))
+    };
+
+    let mut field_names = Vec::with_capacity(fields.len());
+    let mut field_types = Vec::with_capacity(fields.len());
+    for field in fields {
+        let field_attr = field.attrs.iter().find_map(JlrsFieldAttr::parse);
+        match field_attr {
+            Some(JlrsFieldAttr::BitsUnionAlign) | Some(JlrsFieldAttr::BitsUnionFlag) => continue,
+            _ => (),
+        }
+
+        field_names.push(field.ident.as_ref().unwrap().to_string());
+        match field_julia_type(field) {
+            Some(jl_type) => field_types.push(quote! { ::std::string::String::from(#jl_type) }),
+            None => {
+                if let Some(JlrsFieldAttr::BitsUnion) = field_attr {
+                    panic!(
+                        "JuliaStruct can only be derived if the Julia type of a bits union is set with {}",
+                        "#[jlrs(bits_union, julia_type = \"Union{...}\")]"
+                    );
+                }
+
+                let ty = &field.ty;
+                let ty = replace_type_params(quote!(#ty), &type_params, &markers);
+                field_types.push(quote! {
+                    <#ty as ::jlrs::convert::julia_struct::JuliaTypeString>::julia_type_string()
+                });
+            }
+        }
+    }
+
+    let mut generics = ast.generics.clone();
+    {
+        let where_clause = generics.make_where_clause();
+        for param in type_params.iter() {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#param: ::jlrs::convert::julia_struct::JuliaTypeString));
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let type_string = if type_params.is_empty() {
+        quote! { ::std::string::String::from(#jl_path) }
+    } else {
+        quote! {
+            let params: &[::std::string::String] = &[
+                #(<#type_params as ::jlrs::convert::julia_struct::JuliaTypeString>::julia_type_string()),*
+            ];
+            format!("{}{{{}}}", #jl_path, params.join(", "))
+        }
+    };
+
+    let julia_struct_impl = quote! {
+        const _: () = {
+            #(
+                #[derive(Clone, Copy, Debug)]
+                struct #markers;
+
+                unsafe impl ::jlrs::convert::julia_struct::JuliaTypeString for #markers {
+                    fn julia_type_string() -> ::std::string::String {
+                        ::std::string::String::from(#param_names)
+                    }
+                }
+
+                unsafe impl ::jlrs::layout::valid_layout::ValidField for #markers {
+                    fn valid_field(_: ::jlrs::wrappers::ptr::value::Value) -> bool {
+                        false
+                    }
+                }
+            )*
+
+            unsafe impl #impl_generics ::jlrs::convert::julia_struct::JuliaTypeString for #name #ty_generics #where_clause {
+                fn julia_type_string() -> ::std::string::String {
+                    #type_string
+                }
+            }
+
+            unsafe impl #impl_generics ::jlrs::convert::julia_struct::JuliaStruct for #name #ty_generics #where_clause {
+                fn julia_definition() -> ::std::string::String {
+                    let mut definition = ::std::string::String::from(#header);
+                    #(
+                        definition.push_str(&format!("    {}::{}\n", #field_names, #field_types));
+                    )*
+                    definition.push_str("end\n");
+                    definition
+                }
+            }
+        };
+    };
+
+    julia_struct_impl.into()
+}
+
+fn impl_into_named_tuple(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let fields = named_tuple_fields(ast, "IntoNamedTuple");
+    let field_idents = fields
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let field_names = field_idents
+        .iter()
+        .map(|f| f.to_string())
+        .collect::<Vec<_>>();
+
+    let mut generics = ast.generics.clone();
+    {
+        let where_clause = generics.make_where_clause();
+        for field in fields.iter() {
+            let ty = &field.ty;
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: ::jlrs::convert::into_julia::IntoJulia));
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let into_named_tuple_impl = quote! {
+        impl #impl_generics ::jlrs::wrappers::ptr::named_tuple::IntoNamedTuple for #name #ty_generics #where_clause {
+            fn into_named_tuple<'__target, __T>(
+                self,
+                target: ::jlrs::memory::target::ExtendedTarget<'__target, '_, '_, __T>,
+            ) -> ::jlrs::error::JlrsResult<::jlrs::wrappers::ptr::named_tuple::NamedTupleData<'__target, 'static, __T>>
+            where
+                __T: ::jlrs::memory::target::Target<'__target>,
+            {
+                let (output, frame) = target.split();
+                frame.scope(|mut frame| {
+                    let names: &[&str] = &[#(#field_names),*];
+                    let values: &[::jlrs::wrappers::ptr::value::Value] = &[
+                        #(::jlrs::wrappers::ptr::value::Value::new(&mut frame, self.#field_idents)),*
+                    ];
+
+                    let named_tuple = ::jlrs::wrappers::ptr::named_tuple::NamedTuple::new(
+                        frame.as_extended_target(),
+                        names,
+                        values,
+                    )?;
+
+                    Ok(::jlrs::wrappers::ptr::Wrapper::root(named_tuple, output))
+                })
+            }
+        }
+    };
+
+    into_named_tuple_impl.into()
+}
+
+fn impl_from_named_tuple(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let fields = named_tuple_fields(ast, "FromNamedTuple");
+    let field_idents = fields
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let field_names = field_idents
+        .iter()
+        .map(|f| f.to_string())
+        .collect::<Vec<_>>();
+    let field_types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+
+    let mut generics = ast.generics.clone();
+    {
+        let where_clause = generics.make_where_clause();
+        for ty in field_types.iter() {
+            where_clause.predicates.push(syn::parse_quote!(
+                #ty: ::jlrs::convert::unbox::Unbox<Output = #ty> + ::jlrs::layout::typecheck::Typecheck
+            ));
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let from_named_tuple_impl = quote! {
+        impl #impl_generics ::jlrs::wrappers::ptr::named_tuple::FromNamedTuple for #name #ty_generics #where_clause {
+            fn from_named_tuple(
+                frame: &mut ::jlrs::memory::target::frame::GcFrame,
+                named_tuple: ::jlrs::wrappers::ptr::named_tuple::NamedTuple,
+            ) -> ::jlrs::error::JlrsResult<Self> {
+                frame.scope(|mut frame| {
+                    let value = ::jlrs::wrappers::ptr::Wrapper::as_value(named_tuple);
+                    Ok(#name {
+                        #(
+                            #field_idents: value
+                                .get_field(&mut frame, #field_names)?
+                                .unbox::<#field_types>()?,
+                        )*
+                    })
+                })
+            }
+        }
+    };
+
+    from_named_tuple_impl.into()
+}
+
+// Returns the fields of a struct that is converted to or from a named tuple.
+fn named_tuple_fields<'a>(ast: &'a syn::DeriveInput, derive_name: &str) -> Vec<&'a syn::Field> {
+    match &ast.data {
+        syn::Data::Struct(s) => match &s.fields {
+            syn::Fields::Named(n) => n.named.iter().collect(),
+            syn::Fields::Unit => Vec::new(),
+            _ => panic!("{} cannot be derived for tuple structs.", derive_name),
+        },
+        _ => panic!("{} can only be derived for structs.", derive_name),
+    }
+}
+
+// Returns the Julia type of a field that has been set with `#[jlrs(julia_type = "...")]`.
+fn field_julia_type(field: &syn::Field) -> Option<String> {
+    for attr in &field.attrs {
+        if attr.path.is_ident("jlrs") {
+            if let Ok(Meta::List(p)) = attr.parse_meta() {
+                for item in &p.nested {
+                    if let syn::NestedMeta::Meta(Meta::NameValue(nv)) = item {
+                        if nv.path.is_ident("julia_type") {
+                            if let syn::Lit::Str(string) = &nv.lit {
+                                return Some(string.value());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// Replaces every occurrence of a type parameter in `tokens` with its marker type.
+fn replace_type_params(tokens: TS2, params: &[syn::Ident], markers: &[syn::Ident]) -> TS2 {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => match params.iter().position(|p| p == &ident) {
+                Some(idx) => proc_macro2::TokenTree::Ident(markers[idx].clone()),
+                None => proc_macro2::TokenTree::Ident(ident),
+            },
+            proc_macro2::TokenTree::Group(group) => {
+                let stream = replace_type_params(group.stream(), params, markers);
+                let mut replaced = proc_macro2::Group::new(group.delimiter(), stream);
+                replaced.set_span(group.span());
+                proc_macro2::TokenTree::Group(replaced)
+            }
+            token => token,
+        })
+        .collect()
+}
+
+fn impl_julia_enum(ast: &syn::DeriveInput) -> TokenStream {
+    let data = match &ast.data {
+        syn::Data::Enum(e) => e,
+        _ => panic!("JuliaEnum can only be derived for enums."),
+    };
+
+    let mut attrs = JlrsTypeAttrs::parse(ast);
+    let jl_type = attrs.julia_type
+        .take()
+        .expect("JuliaEnum can only be derived if the corresponding Julia type is set with #[jlrs(julia_type = \"Main.MyModule.Submodule.EnumType\")]");
+
+    let is_sum_type = data.variants.iter().any(|v| {
+        !matches!(v.fields, syn::Fields::Unit) || JlrsVariantAttrs::parse(v).julia_type.is_some()
+    });
+
+    if is_sum_type {
+        impl_julia_sum_type(ast, data, &jl_type)
+    } else {
+        impl_julia_c_enum(ast, data, &jl_type)
+    }
+}
+
+// Maps an enum without data to an enum defined with `@enum`.
+fn impl_julia_c_enum(ast: &syn::DeriveInput, data: &syn::DataEnum, jl_type: &str) -> TokenStream {
+    let name = &ast.ident;
+    if !ast.generics.params.is_empty() {
+        panic!("JuliaEnum cannot be derived for enums with generics.");
+    }
+
+    let repr = enum_repr(ast).expect(
+        "JuliaEnum can only be derived for enums without data if they have an integer representation, e.g. #[repr(i32)].",
+    );
+
+    let variants = data.variants.iter().map(|v| &v.ident);
+    let jl_names = data.variants.iter().map(|v| {
+        JlrsVariantAttrs::parse(v)
+            .rename
+            .unwrap_or_else(|| v.ident.to_string())
+    });
+
+    let lookup = lookup_global(jl_type, "JuliaEnum");
+
+    let julia_enum_impl = quote! {
+        const _: () = {
+            static CACHE: ::jlrs::layout::julia_enum::EnumLayoutCache =
+                ::jlrs::layout::julia_enum::EnumLayoutCache::new();
+
+            unsafe impl ::jlrs::layout::valid_layout::ValidLayout for #name {
+                fn valid_layout(v: ::jlrs::wrappers::ptr::value::Value) -> bool {
+                    ::jlrs::layout::julia_enum::valid_enum_layout(&CACHE, v)
+                }
+
+                const IS_REF: bool = false;
+            }
+
+            impl ::jlrs::layout::julia_enum::RegisterEnum for #name {
+                fn register(
+                    frame: &mut ::jlrs::memory::target::frame::GcFrame,
+                ) -> ::jlrs::error::JlrsResult<()> {
+                    unsafe {
+                        let global = ::jlrs::memory::target::Target::unrooted(&*frame);
+                        let ty = #lookup.value();
+
+                        ::jlrs::layout::julia_enum::register_enum::<#repr>(
+                            frame,
+                            &CACHE,
+                            ty,
+                            &[#((#jl_names, #name::#variants as #repr),)*],
+                        )
+                    }
+                }
+            }
+        };
+
+        unsafe impl ::jlrs::layout::valid_layout::ValidField for #name {
+            fn valid_field(v: ::jlrs::wrappers::ptr::value::Value) -> bool {
+                <Self as ::jlrs::layout::valid_layout::ValidLayout>::valid_layout(v)
+            }
+        }
+
+        unsafe impl ::jlrs::layout::typecheck::Typecheck for #name {
+            fn typecheck(dt: ::jlrs::wrappers::ptr::datatype::DataType) -> bool {
+                <Self as ::jlrs::layout::valid_layout::ValidLayout>::valid_layout(dt.as_value())
+            }
+        }
+
+        unsafe impl ::jlrs::convert::unbox::Unbox for #name {
+            type Output = Self;
+        }
+
+        unsafe impl ::jlrs::convert::into_julia::IntoJulia for #name {
+            fn julia_type<'scope, T>(target: T) -> ::jlrs::wrappers::ptr::datatype::DataTypeData<'scope, T>
+            where
+                T: ::jlrs::memory::target::Target<'scope>,
+            {
+                unsafe {
+                    let global = target.unrooted();
+                    #lookup
+                        .value()
+                        .cast::<::jlrs::wrappers::ptr::datatype::DataType>()
+                        .expect("Type is not a DataType")
+                        .root(target)
+                }
+            }
+        }
+
+        unsafe impl ::jlrs::convert::into_julia::IsBits for #name {}
+
+        unsafe impl ::jlrs::convert::construct_type::ConstructType for #name {
+            fn construct_type<'target, 'current, 'borrow, Tgt>(
+                target: ::jlrs::memory::target::ExtendedTarget<'target, 'current, 'borrow, Tgt>,
+            ) -> ::jlrs::wrappers::ptr::value::ValueData<'target, 'static, Tgt>
+            where
+                Tgt: ::jlrs::memory::target::Target<'target>,
+            {
+                unsafe {
+                    let (output, _) = target.split();
+                    let global = output.unrooted();
+                    #lookup.root(output)
+                }
+            }
+        }
+    };
+
+    julia_enum_impl.into()
+}
+
+// Maps an enum whose variants carry data to an abstract type. A hidden struct is generated for
+// each variant, which is converted to and from an instance of the concrete type associated with
+// that variant.
+fn impl_julia_sum_type(ast: &syn::DeriveInput, data: &syn::DataEnum, jl_type: &str) -> TokenStream {
+    let name = &ast.ident;
+    if ast.generics.type_params().next().is_some() || ast.generics.const_params().next().is_some() {
+        panic!("JuliaEnum cannot be derived for enums with type parameters.");
+    }
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let lifetimes = ast
+        .generics
+        .lifetimes()
+        .map(|l| &l.lifetime)
+        .collect::<Vec<_>>();
+
+    let mut hidden_structs = Vec::with_capacity(data.variants.len());
+    let mut hidden_names = Vec::with_capacity(data.variants.len());
+    let mut from_julia = Vec::with_capacity(data.variants.len());
+    let mut into_julia = Vec::with_capacity(data.variants.len());
+
+    for variant in data.variants.iter() {
+        let variant_name = &variant.ident;
+        let variant_jl_type = JlrsVariantAttrs::parse(variant).julia_type.unwrap_or_else(|| {
+            panic!(
+                "JuliaEnum can only be derived for enums with data if the Julia type of every variant is set with #[jlrs(julia_type = \"Main.MyModule.Submodule.StructType\")], it's missing for {}.",
+                variant_name
+            )
+        });
+
+        let hidden_name = quote::format_ident!("__Jlrs{}{}", name, variant_name);
+        let field_names = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(idx, f)| {
+                f.ident
+                    .clone()
+                    .unwrap_or_else(|| quote::format_ident!("_{}", idx))
+            })
+            .collect::<Vec<_>>();
+        let field_types = variant.fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+
+        let used_lifetimes = lifetimes
+            .iter()
+            .filter(|l| {
+                field_types
+                    .iter()
+                    .any(|ty| contains_lifetime(quote!(#ty), &l.ident))
+            })
+            .collect::<Vec<_>>();
+
+        hidden_structs.push(quote! {
+            #[repr(C)]
+            #[derive(Clone, ::jlrs::prelude::Unbox, ::jlrs::prelude::ValidLayout, ::jlrs::prelude::Typecheck, ::jlrs::prelude::IntoJulia)]
+            #[jlrs(julia_type = #variant_jl_type)]
+            struct #hidden_name<#(#used_lifetimes),*> {
+                #(#field_names: #field_types,)*
+            }
+        });
+
+        let bindings = (0..field_names.len())
+            .map(|idx| quote::format_ident!("__jlrs_field_{}", idx))
+            .collect::<Vec<_>>();
+
+        let construct = match &variant.fields {
+            syn::Fields::Named(_) => {
+                quote! { #name::#variant_name { #(#field_names: #bindings),* } }
+            }
+            syn::Fields::Unnamed(_) => quote! { #name::#variant_name(#(#bindings),*) },
+            syn::Fields::Unit => quote! { #name::#variant_name },
+        };
+
+        let destructure = quote! { #hidden_name { #(#field_names: #bindings),* } };
+
+        from_julia.push(quote! {
+            if <#hidden_name as ::jlrs::layout::typecheck::Typecheck>::typecheck(value.datatype()) {
+                let #destructure = <#hidden_name as ::jlrs::convert::unbox::Unbox>::unbox(value);
+                return #construct;
+            }
+        });
+
+        into_julia.push(quote! {
+            #construct => <#hidden_name as ::jlrs::convert::into_julia::IntoJulia>::into_julia(#destructure, target),
+        });
+
+        hidden_names.push(hidden_name);
+    }
+
+    let lookup = lookup_global(jl_type, "JuliaEnum");
+    let into_julia_generics = with_static_data_lifetimes(&ast.generics);
+    let (into_julia_impl_generics, into_julia_ty_generics, into_julia_where_clause) =
+        into_julia_generics.split_for_impl();
+
+    let julia_enum_impl = quote! {
+        const _: () = {
+            #(#hidden_structs)*
+
+            unsafe impl #impl_generics ::jlrs::layout::typecheck::Typecheck for #name #ty_generics #where_clause {
+                fn typecheck(dt: ::jlrs::wrappers::ptr::datatype::DataType) -> bool {
+                    false #(|| <#hidden_names as ::jlrs::layout::typecheck::Typecheck>::typecheck(dt))*
+                }
+            }
+
+            unsafe impl #impl_generics ::jlrs::convert::unbox::Unbox for #name #ty_generics #where_clause {
+                type Output = Self;
+
+                unsafe fn unbox(value: ::jlrs::wrappers::ptr::value::Value) -> Self::Output {
+                    #(#from_julia)*
+                    unreachable!("The value is not an instance of one of the variants")
+                }
+            }
+
+            impl #into_julia_impl_generics ::jlrs::layout::julia_enum::IntoJuliaVariant for #name #into_julia_ty_generics #into_julia_where_clause {
+                fn into_julia_variant<'target, T>(self, target: T) -> ::jlrs::wrappers::ptr::value::ValueData<'target, 'static, T>
+                where
+                    T: ::jlrs::memory::target::Target<'target>,
+                {
+                    match self {
+                        #(#into_julia)*
+                    }
+                }
+            }
+
+            unsafe impl #impl_generics ::jlrs::convert::construct_type::ConstructType for #name #ty_generics #where_clause {
+                fn construct_type<'target, 'current, 'borrow, Tgt>(
+                    target: ::jlrs::memory::target::ExtendedTarget<'target, 'current, 'borrow, Tgt>,
+                ) -> ::jlrs::wrappers::ptr::value::ValueData<'target, 'static, Tgt>
+                where
+                    Tgt: ::jlrs::memory::target::Target<'target>,
+                {
+                    unsafe {
+                        let (output, _) = target.split();
+                        let global = output.unrooted();
+                        #lookup.root(output)
+                    }
+                }
+            }
+        };
+    };
+
+    julia_enum_impl.into()
+}
+
+// Generates an expression that looks up the global `jl_type`, e.g. `"Main.MyModule.MyType"`. A
+// variable named `global` that can be used as an unrooted target must be in scope.
+fn lookup_global(jl_type: &str, derive_name: &str) -> TS2 {
+    let mut type_it = jl_type.split('.');
+    let func = match type_it.next() {
+        Some("Main") => quote::format_ident!("main"),
+        Some("Base") => quote::format_ident!("base"),
+        Some("Core") => quote::format_ident!("core"),
+        _ => panic!("{} can only be derived if the first module of \"julia_type\" is either \"Main\", \"Base\" or \"Core\".", derive_name),
+    };
+
+    let mut modules = type_it.collect::<Vec<_>>();
+    let ty = modules.pop().unwrap_or_else(|| panic!("{} can only be derived if the corresponding Julia type is set with #[jlrs(julia_type = \"Main.MyModule.Submodule.Type\")]", derive_name));
+    let modules_it = modules.iter();
+    let modules_it_b = modules_it.clone();
+
+    quote! {
+        ::jlrs::wrappers::ptr::module::Module::#func(&global)
+            #(
+                .submodule(&global, #modules_it)
+                .expect(&format!("Submodule {} cannot be found", #modules_it_b))
+                .wrapper()
+            )*
+            .global(&global, #ty)
+            .expect(&format!("Type {} cannot be found in module", #ty))
+    }
+}
+
+// Returns the integer type of an enum with the attribute `#[repr(int_type)]`.
+fn enum_repr(ast: &syn::DeriveInput) -> Option<syn::Ident> {
+    for attr in &ast.attrs {
+        if attr.path.is_ident("repr") {
+            if let Ok(Meta::List(p)) = attr.parse_meta() {
+                for item in &p.nested {
+                    if let syn::NestedMeta::Meta(syn::Meta::Path(m)) = item {
+                        if let Some(ident) = m.get_ident() {
+                            match ident.to_string().as_str() {
+                                "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32"
+                                | "u64" | "usize" => return Some(ident.clone()),
+                                _ => (),
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// Returns `true` if `tokens` contains the identifier `ident`.
+fn contains_ident(tokens: TS2, ident: &syn::Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(i) => &i == ident,
+        proc_macro2::TokenTree::Group(group) => contains_ident(group.stream(), ident),
+        _ => false,
+    })
+}
+
+// Returns `true` if `tokens` contains the lifetime `'lifetime`.
+fn contains_lifetime(tokens: TS2, lifetime: &syn::Ident) -> bool {
+    let mut is_lifetime = false;
+    for token in tokens {
+        match token {
+            proc_macro2::TokenTree::Punct(p) if p.as_char() == '\'' => {
+                is_lifetime = true;
+                continue;
+            }
+            proc_macro2::TokenTree::Ident(ident) if is_lifetime && &ident == lifetime => {
+                return true;
+            }
+            proc_macro2::TokenTree::Group(group) if contains_lifetime(group.stream(), lifetime) => {
+                return true;
+            }
+            _ => (),
+        }
+
+        is_lifetime = false;
+    }
+
+    false
+}
+
+// Returns `true` if `ty` is a reference, a `Ref`, or a pointer wrapper, optionally wrapped in an
+// `Option`. Raw pointers are mapped to `Ptr{T}` and aren't considered pointer fields.
+fn is_pointer_field(ty: &syn::Type) -> bool {
+    const WRAPPERS: &[&str] = &[
+        "Value",
+        "Array",
+        "TypedArray",
+        "DataType",
+        "Module",
+        "Symbol",
+        "JuliaString",
+        "SimpleVector",
+        "UnionAll",
+        "Union",
+        "TypeVar",
+        "TypeName",
+        "Function",
+        "Task",
+    ];
+
+    match ty {
+        syn::Type::Reference(_) => true,
+        syn::Type::Group(g) => is_pointer_field(&g.elem),
+        syn::Type::Paren(p) => is_pointer_field(&p.elem),
+        syn::Type::Path(p) => {
+            let segment = match p.path.segments.last() {
+                Some(segment) => segment,
+                None => return false,
+            };
+
+            let ident = segment.ident.to_string();
+            if ident.ends_with("Ref") || WRAPPERS.contains(&ident.as_str()) {
+                return true;
+            }
+
+            if ident == "Option" {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    return args.args.iter().any(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => is_pointer_field(ty),
+                        _ => false,
+                    });
+                }
+            }
+
+            false
+        }
+        _ => false,
+    }
+}
+
 fn is_repr_c(ast: &syn::DeriveInput) -> bool {
     for attr in &ast.attrs {
         if attr.path.is_ident("repr") {